@@ -0,0 +1,40 @@
+//
+//  AsyncFunction.swift
+//  SwiftRustIntegrationTestRunner
+//
+
+import Foundation
+
+extension AsyncRustError: Error {}
+
+func swift_async_reflect_u8(arg: UInt8) async -> UInt8 {
+    await Task.yield()
+    return arg
+}
+
+func swift_async_return_string() async -> String {
+    await Task.yield()
+    return "hello from swift"
+}
+
+var swiftAsyncPendingTaskWasCancelled = false
+
+/// Only returns once its task has been cancelled.
+func swift_async_pending_until_cancelled() async {
+    while !Task.isCancelled {
+        try? await Task.sleep(nanoseconds: 1_000_000)
+    }
+    swiftAsyncPendingTaskWasCancelled = true
+}
+
+class AsyncSwiftArg {
+    let val: UInt32
+
+    init(val: UInt32) {
+        self.val = val
+    }
+
+    func value() -> UInt32 {
+        val
+    }
+}
@@ -0,0 +1,10 @@
+//
+//  Char.swift
+//  SwiftRustIntegrationTestRunner
+//
+
+import Foundation
+
+func swift_reflect_char(arg: Unicode.Scalar) -> Unicode.Scalar {
+    arg
+}
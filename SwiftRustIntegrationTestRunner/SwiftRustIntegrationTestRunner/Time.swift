@@ -0,0 +1,10 @@
+//
+//  Time.swift
+//  SwiftRustIntegrationTestRunner
+//
+
+import Foundation
+
+func swift_add_one_second(arg: Date) -> Date {
+    arg.addingTimeInterval(1)
+}
@@ -0,0 +1,50 @@
+//
+//  ArrayTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing fixed size arrays between Swift and Rust.
+/// See crates/swift-integration-tests/src/array.rs
+class ArrayTests: XCTestCase {
+    func testReflectArrayU8() throws {
+        let reflected = rust_reflect_array_u8((1, 2, 3, 4))
+        XCTAssertEqual(reflected.0, 1)
+        XCTAssertEqual(reflected.1, 2)
+        XCTAssertEqual(reflected.2, 3)
+        XCTAssertEqual(reflected.3, 4)
+    }
+
+    func testReflectArrayF64() throws {
+        let reflected = rust_reflect_array_f64((1.5, 2.5, 3.5))
+        XCTAssertEqual(reflected.0, 1.5)
+        XCTAssertEqual(reflected.1, 2.5)
+        XCTAssertEqual(reflected.2, 3.5)
+    }
+
+    func testReflectArrayBool() throws {
+        let reflected = rust_reflect_array_bool((true, false))
+        XCTAssertEqual(reflected.0, true)
+        XCTAssertEqual(reflected.1, false)
+    }
+
+    func testReflectArrayOfStructs() throws {
+        let reflected = rust_reflect_array_of_structs(
+            (ArrayTestPoint(x: 1, y: 2), ArrayTestPoint(x: 3, y: 4))
+        )
+        XCTAssertEqual(reflected.0.x, 1)
+        XCTAssertEqual(reflected.0.y, 2)
+        XCTAssertEqual(reflected.1.x, 3)
+        XCTAssertEqual(reflected.1.y, 4)
+    }
+
+    func testReflectStructWithArrayField() throws {
+        let reflected = rust_reflect_struct_with_array_field(
+            StructWithArrayField(hash: (5, 6, 7, 8))
+        )
+        XCTAssertEqual(reflected.hash.0, 5)
+        XCTAssertEqual(reflected.hash.3, 8)
+    }
+}
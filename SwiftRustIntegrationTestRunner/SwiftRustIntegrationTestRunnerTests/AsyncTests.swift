@@ -17,18 +17,18 @@ class AsyncTests: XCTestCase {
     }
 
     func testSwiftCallsRustAsyncFn() async throws {
-        await rust_async_return_null()
+        try await rust_async_return_null()
     }
    
     /// Verify that we can pass and return a u8 to an async Rust function
     func testSwiftCallsRustAsyncFnReflectU8() async throws {
-        let num = await rust_async_reflect_u8(123)
+        let num = try await rust_async_reflect_u8(123)
         XCTAssertEqual(num, 123)
     }
     
      /// Verify that we can pass and return a String to an async Rust function
     func testSwiftCallsRustAsyncFnReflectString() async throws {
-        let string = await rust_async_reflect_string("hello world")
+        let string = try await rust_async_reflect_string("hello world")
         XCTAssertEqual(string.toString(), "hello world")
     }
     
@@ -36,13 +36,169 @@ class AsyncTests: XCTestCase {
     func testSwiftCallsRustAsyncMethodReflectU16() async throws {
         let test = TestRustAsyncSelf()
 
-        let num = await test.reflect_u16(567)
+        let num = try await test.reflect_u16(567)
         XCTAssertEqual(num, 567)
     }
 
+    /// Verify that `self` is kept alive while an async Rust method is running, even if the caller
+    /// does not hold on to it.
+    func testRustAsyncMethodKeepsSelfAlive() async throws {
+        let num = try await TestRustAsyncSelf().reflect_u16(123)
+        XCTAssertEqual(num, 123)
+    }
+
     
     func testSwiftCallsRustAsyncFnRetStruct() async throws {
-        let _: AsyncRustFnReturnStruct = await rust_async_return_struct()
+        let _: AsyncRustFnReturnStruct = try await rust_async_return_struct()
+    }
+
+    /// Verify that an async Rust function that returns `Ok` returns the value.
+    func testSwiftCallsRustAsyncFnReturnsOk() async throws {
+        let num = try await rust_async_result_u8(true)
+        XCTAssertEqual(num, 123)
+    }
+
+    /// Verify that an async Rust function that returns `Err` throws the error.
+    func testSwiftCallsRustAsyncFnThrowsErr() async throws {
+        do {
+            let _ = try await rust_async_result_u8(false)
+            XCTFail("The function should have thrown.")
+        } catch let error as RustString {
+            XCTAssertEqual(error.toString(), "error")
+        }
+    }
+
+    /// Verify that an async Rust function can throw an opaque Rust type.
+    func testSwiftCallsRustAsyncFnThrowsOpaqueErr() async throws {
+        try await rust_async_result_opaque_err(true)
+
+        do {
+            try await rust_async_result_opaque_err(false)
+            XCTFail("The function should have thrown.")
+        } catch let error as AsyncRustError {
+            XCTAssertEqual(error.message().toString(), "opaque error")
+        }
+    }
+
+    /// Verify that a panic in an async Rust function that returns a `Result` gets thrown as a
+    /// `RustPanic` instead of leaving the Swift task waiting forever.
+    func testSwiftCallsRustAsyncFnThrowsPanic() async throws {
+        do {
+            let _ = try await rust_async_result_panics()
+            XCTFail("The function should have thrown.")
+        } catch let error as RustPanic {
+            XCTAssertEqual(error.message, "async panic")
+        }
+    }
+
+    /// Verify that a panic in an async Rust function that does not return a `Result` also gets
+    /// thrown as a `RustPanic`.
+    func testSwiftCallsRustAsyncFnWithoutResultThrowsPanic() async throws {
+        do {
+            try await rust_async_panics()
+            XCTFail("The function should have thrown.")
+        } catch let error as RustPanic {
+            XCTAssertEqual(error.message, "async panic without a result")
+        }
+    }
+
+    /// Verify that Swift can call an async Rust function whose future is not `Send`, and that the
+    /// future gets polled again after being woken up from another thread.
+    func testSwiftCallsRustAsyncLocalFn() async throws {
+        let sum = try await rust_async_local_rc_sum(1, 2)
+        XCTAssertEqual(sum, 3)
+    }
+
+    /// Verify that we can pass a string to an async Rust function that reads it after Swift has
+    /// freed its own copy.
+    func testSwiftCallsRustAsyncFnWithStr() async throws {
+        let len = try await rust_async_str_len("hello world")
+        XCTAssertEqual(len, 11)
+    }
+
+    /// Verify that we can pass a slice to an async Rust function that reads it after the call
+    /// returns.
+    func testSwiftCallsRustAsyncFnWithSlice() async throws {
+        let buffer = UnsafeMutableBufferPointer<UInt8>.allocate(capacity: 3)
+        _ = buffer.initialize(from: [1, 2, 3])
+        defer { buffer.deallocate() }
+
+        let sum = try await rust_async_sum_slice(UnsafeBufferPointer(buffer))
+        XCTAssertEqual(sum, 6)
+    }
+
+    /// Verify that we can pass an owned opaque Rust type to an async Rust function.
+    func testSwiftCallsRustAsyncFnWithOwnedOpaqueRustType() async throws {
+        let arg = TestRustAsyncSelf()
+        _ = arg.add_to_count(5)
+
+        let count = try await rust_async_take_opaque(arg)
+        XCTAssertEqual(count, 5)
+    }
+
+    /// Verify that an owned Swift type that gets passed to an async Rust function stays alive until
+    /// the future is done with it.
+    func testSwiftCallsRustAsyncLocalFnWithOwnedSwiftType() async throws {
+        let value = try await rust_async_local_read_swift_arg(AsyncSwiftArg(val: 7))
+        XCTAssertEqual(value, 7)
+    }
+
+    /// Verify that cancelling the Swift Task that is awaiting an async Rust function drops the
+    /// Rust future.
+    func testCancellingSwiftTaskDropsRustFuture() async throws {
+        let task = Task {
+            try await rust_async_pending_until_cancelled()
+        }
+        task.cancel()
+
+        do {
+            try await task.value
+            XCTFail("The task should have been cancelled.")
+        } catch is CancellationError {
+        }
+
+        XCTAssert(rust_async_pending_future_was_dropped())
+    }
+
+    /// Verify that async Rust functions are counted as in-flight until their task completes.
+    func testRustAsyncRuntimeCountsInFlightTasks() async throws {
+        let task = Task {
+            try await rust_async_pending_until_cancelled()
+        }
+        while RustAsyncRuntime.inFlightTasks == 0 {
+            await Task.yield()
+        }
+        task.cancel()
+        _ = try? await task.value
+
+        for _ in 0..<100 where RustAsyncRuntime.inFlightTasks > 0 {
+            try await Task.sleep(nanoseconds: 10_000_000)
+        }
+        XCTAssertEqual(RustAsyncRuntime.inFlightTasks, 0)
+    }
+
+    /// Verify that Rust can await an async Swift function that returns a u8.
+    func testRustCallsSwiftAsyncFnReflectU8() async throws {
+        let num = try await rust_calls_swift_async_reflect_u8(45)
+        XCTAssertEqual(num, 45)
+    }
+
+    /// Verify that Rust can await an async Swift function that returns a String.
+    func testRustCallsSwiftAsyncFnReturnString() async throws {
+        let string = try await rust_calls_swift_async_return_string()
+        XCTAssertEqual(string.toString(), "hello from swift")
+    }
+
+    /// Verify that dropping the Rust future that is awaiting an async Swift function cancels the
+    /// Swift Task.
+    func testDroppingRustFutureCancelsSwiftTask() async throws {
+        rust_drops_swift_async_future()
+
+        let deadline = Date().addingTimeInterval(5)
+        while !swiftAsyncPendingTaskWasCancelled && Date() < deadline {
+            await Task.yield()
+        }
+        XCTAssert(swiftAsyncPendingTaskWasCancelled)
     }
 }
 
@@ -0,0 +1,30 @@
+//
+//  CharTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing `char`s between Swift and Rust.
+/// See crates/swift-integration-tests/src/char.rs
+class CharTests: XCTestCase {
+    func testReflectChar() throws {
+        XCTAssertEqual(rust_reflect_char("a"), "a")
+        XCTAssertEqual(rust_reflect_char("🦀"), "🦀")
+    }
+
+    func testReflectOptionChar() throws {
+        XCTAssertEqual(rust_reflect_option_char("é"), "é")
+        XCTAssertNil(rust_reflect_option_char(nil))
+    }
+
+    func testReflectStructWithCharField() throws {
+        let reflected = rust_reflect_struct_with_char_field(StructWithCharField(letter: "z"))
+        XCTAssertEqual(reflected.letter, "z")
+    }
+
+    func testRustCallsSwiftReflectChar() throws {
+        XCTAssertEqual(rust_calls_swift_reflect_char("ß"), "ß")
+    }
+}
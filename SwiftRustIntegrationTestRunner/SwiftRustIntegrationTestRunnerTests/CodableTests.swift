@@ -0,0 +1,71 @@
+//
+//  CodableTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for shared structs and enums that use the `#[swift_bridge(Codable)]` attribute.
+/// See crates/swift-integration-tests/src/shared_types/codable.rs
+class CodableTests: XCTestCase {
+
+    /// Verify that Swift can decode JSON that was serialized by the Rust type's `Serialize`
+    /// implementation.
+    func testSwiftDecodesRustJson() throws {
+        let value = CodableStruct(
+            id: 5,
+            name: "hello".intoRustString(),
+            score: 1.5,
+            size: .Large,
+            point: CodableTupleStruct(_0: 3, _1: -4)
+        )
+
+        let json = rust_serialize_codable_struct(value).toString()
+        XCTAssertEqual(json, #"{"id":5,"name":"hello","score":1.5,"size":"Large","point":[3,-4]}"#)
+
+        let decoded = try JSONDecoder().decode(CodableStruct.self, from: json.data(using: .utf8)!)
+        XCTAssertEqual(decoded.id, 5)
+        XCTAssertEqual(decoded.name.toString(), "hello")
+        XCTAssertEqual(decoded.score, 1.5)
+        XCTAssertEqual(decoded.size, .Large)
+        XCTAssertEqual(decoded.point._0, 3)
+        XCTAssertEqual(decoded.point._1, -4)
+    }
+
+    /// Verify that Rust can deserialize JSON that was encoded by Swift.
+    func testRustDecodesSwiftJson() throws {
+        let value = CodableStruct(
+            id: 7,
+            name: "world".intoRustString(),
+            score: nil,
+            size: .Small,
+            point: CodableTupleStruct(_0: 0, _1: 1)
+        )
+
+        let json = String(data: try JSONEncoder().encode(value), encoding: .utf8)!
+
+        let decoded = rust_deserialize_codable_struct(json)
+        XCTAssertEqual(decoded.id, 7)
+        XCTAssertEqual(decoded.name.toString(), "world")
+        XCTAssertNil(decoded.score)
+        XCTAssertEqual(decoded.size, .Small)
+        XCTAssertEqual(decoded.point._0, 0)
+        XCTAssertEqual(decoded.point._1, 1)
+    }
+
+    /// Verify that a missing `Option` field decodes as `nil`, the same as it does in serde.
+    func testMissingOptionalFieldDecodesAsNil() throws {
+        let json = #"{"id":1,"name":"a","size":"Small","point":[0,0]}"#
+
+        let decoded = try JSONDecoder().decode(CodableStruct.self, from: json.data(using: .utf8)!)
+        XCTAssertNil(decoded.score)
+    }
+
+    /// Verify that decoding an unknown enum variant throws an error.
+    func testUnknownEnumVariantThrows() throws {
+        XCTAssertThrowsError(
+            try JSONDecoder().decode([CodableEnum].self, from: #"["Medium"]"#.data(using: .utf8)!)
+        )
+    }
+}
@@ -0,0 +1,64 @@
+//
+//  ConversionInitializerTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for the Swift initializers that opaque Rust types get from their `From`, `TryFrom` and
+/// `FromStr` implementations.
+/// See crates/swift-integration-tests/src/opaque_type_attributes/conversions.rs
+class ConversionInitializerTests: XCTestCase {
+
+    /// Verify that `init(_:)` calls the Rust type's `From` implementation.
+    func testFrom() throws {
+        XCTAssertEqual(Percentage(UInt8(42)).value(), 42)
+        XCTAssertEqual(Percentage(UInt8(250)).value(), 100)
+    }
+
+    /// Verify that we can create a Rust type from a Swift string using its `From<&str>`
+    /// implementation.
+    func testFromStr() throws {
+        XCTAssertEqual(Label("hello").text().toString(), "hello")
+    }
+
+    /// Verify that `init?(_:)` returns nil when the Rust type's `TryFrom` implementation fails.
+    func testTryFrom() throws {
+        XCTAssertEqual(Percentage(Int64(75))?.value(), 75)
+        XCTAssertNil(Percentage(Int64(101)))
+        XCTAssertNil(Percentage(Int64(-1)))
+    }
+
+    /// Verify that `init(_:) throws` throws the Rust type's `TryFrom::Error`.
+    func testTryFromThrows() throws {
+        let username = try Username("swift")
+        XCTAssertEqual(username.name().toString(), "swift")
+
+        do {
+            let _ = try Username("swift bridge")
+            XCTFail("Expected an error")
+        } catch let error as RustString {
+            XCTAssertEqual(error.toString(), "The username \"swift bridge\" contains whitespace.")
+        }
+
+        XCTAssertThrowsError(try Username(""))
+    }
+
+    /// Verify that `LosslessStringConvertible` uses the Rust type's `FromStr` and `Display`
+    /// implementations.
+    func testLosslessStringConvertible() throws {
+        XCTAssertEqual(Percentage("42%")?.value(), 42)
+        XCTAssertEqual(Percentage("7")?.value(), 7)
+        XCTAssertNil(Percentage("not a number"))
+        XCTAssertNil(Percentage("200%"))
+
+        let percentage = Percentage(UInt8(30))
+        XCTAssertEqual(Percentage(percentage.description)?.value(), 30)
+        XCTAssertEqual(parse("55%", as: Percentage.self)?.value(), 55)
+    }
+
+    private func parse<T: LosslessStringConvertible>(_ description: String, as type: T.Type) -> T? {
+        T(description)
+    }
+}
@@ -0,0 +1,37 @@
+//
+//  Int128Tests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing 128 bit integers between Swift and Rust.
+/// See crates/swift-integration-tests/src/int128.rs
+class Int128Tests: XCTestCase {
+    func testReflectU128() throws {
+        let value = RustU128(low: 1, high: 2)
+        XCTAssertEqual(rust_reflect_u128(value), value)
+        XCTAssertEqual(rust_reflect_u128(5).description, "5")
+    }
+
+    func testReflectI128() throws {
+        XCTAssertEqual(rust_reflect_i128(-5), RustI128(-5))
+        XCTAssertEqual(rust_reflect_i128(-5).description, "-5")
+    }
+
+    func testReflectOptionU128() throws {
+        XCTAssertEqual(rust_reflect_option_u128(123), RustU128(123))
+        XCTAssertNil(rust_reflect_option_u128(nil))
+    }
+
+    func testReflectOptionI128() throws {
+        XCTAssertEqual(rust_reflect_option_i128(-123), RustI128(-123))
+        XCTAssertNil(rust_reflect_option_i128(nil))
+    }
+
+    func testExtremeValues() throws {
+        XCTAssertEqual(rust_u128_max().description, "340282366920938463463374607431768211455")
+        XCTAssertEqual(rust_i128_min().description, "-170141183460469231731687303715884105728")
+    }
+}
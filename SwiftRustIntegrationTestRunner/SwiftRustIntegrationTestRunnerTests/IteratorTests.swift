@@ -0,0 +1,54 @@
+//
+//  IteratorTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for Rust functions that return an `impl Iterator<Item = T>` or a
+/// `Box<dyn Iterator<Item = T>>`.
+/// See crates/swift-integration-tests/src/iterator.rs
+class IteratorTests: XCTestCase {
+    func testIteratorOfPrimitives() throws {
+        XCTAssertEqual(Array(rust_count_up_to(5)), [1, 2, 3, 4, 5])
+    }
+
+    func testNextIsCalledLazily() throws {
+        let iterator = rust_count_up_to(UInt32.max)
+
+        XCTAssertEqual(iterator.next(), 1)
+        XCTAssertEqual(iterator.next(), 2)
+        XCTAssertEqual(Array(iterator.prefix(2)), [3, 4])
+    }
+
+    func testBoxedIteratorOfStrings() throws {
+        let words = rust_words("hello swift bridge").map { $0.toString() }
+        XCTAssertEqual(words, ["hello", "swift", "bridge"])
+    }
+
+    func testIteratorOfSharedStructs() throws {
+        var count: UInt32 = 0
+        for point in rust_diagonal_points(3) {
+            XCTAssertEqual(point.x, count)
+            XCTAssertEqual(point.y, count)
+            count += 1
+        }
+
+        XCTAssertEqual(count, 3)
+    }
+
+    func testEmptyIterator() throws {
+        let iterator = rust_empty_iterator()
+
+        XCTAssertNil(iterator.next())
+        XCTAssertNil(iterator.next())
+    }
+
+    /// Verify that an iterator that borrows from an opaque Rust type keeps that type alive.
+    func testIteratorKeepsOwnerAlive() throws {
+        let rows = IteratorTestTable(3).rows()
+
+        XCTAssertEqual(rows.map { $0.id() }, [0, 1, 2])
+    }
+}
@@ -0,0 +1,27 @@
+//
+//  NonZeroTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing NonZero integers between Swift and Rust.
+/// See crates/swift-integration-tests/src/non_zero.rs
+class NonZeroTests: XCTestCase {
+    func testReflectNonZero() throws {
+        XCTAssertEqual(rust_reflect_non_zero_u32(5), 5)
+        XCTAssertEqual(rust_reflect_non_zero_i64(-5), -5)
+    }
+
+    /// Verify that zero is used to represent `None`.
+    func testReflectOptionNonZero() throws {
+        XCTAssertEqual(rust_reflect_option_non_zero_u8(7), 7)
+        XCTAssertNil(rust_reflect_option_non_zero_u8(nil))
+    }
+
+    func testReflectStructWithNonZeroField() throws {
+        let reflected = rust_reflect_struct_with_non_zero_field(StructWithNonZeroField(id: 10))
+        XCTAssertEqual(reflected.id, 10)
+    }
+}
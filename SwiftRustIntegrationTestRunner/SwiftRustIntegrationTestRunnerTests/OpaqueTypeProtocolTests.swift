@@ -0,0 +1,109 @@
+//
+//  OpaqueTypeProtocolTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for the Swift protocols that opaque Rust types can conform to.
+/// See crates/swift-integration-tests/src/opaque_type_attributes/protocols.rs
+class OpaqueTypeProtocolTests: XCTestCase {
+
+    /// Verify that `==` calls the Rust type's `PartialEq` implementation.
+    func testEquatable() throws {
+        XCTAssertEqual(EquatableRustType(1.5), EquatableRustType(1.5))
+        XCTAssertNotEqual(EquatableRustType(1.5), EquatableRustType(2.5))
+        XCTAssertNotEqual(EquatableRustType(Float.nan), EquatableRustType(Float.nan))
+    }
+
+    /// Verify that references can be compared to owned values.
+    func testEquatableReference() throws {
+        let owned = EquatableRustType(1.5)
+        let reference: EquatableRustTypeRef = EquatableRustType(1.5)
+
+        XCTAssert(owned == reference)
+    }
+
+    /// Verify that we can use Rust types in a Set, and that hashing calls the Rust type's `Hash`
+    /// implementation.
+    func testHashable() throws {
+        let a = HashableRustType(1, "a")
+        let aCopy = HashableRustType(1, "a")
+        let b = HashableRustType(1, "b")
+
+        XCTAssertEqual(a.hashValue, aCopy.hashValue)
+        XCTAssertEqual(a.hashValue, b.hashValue)
+        XCTAssertEqual(a, aCopy)
+        XCTAssertNotEqual(a, b)
+
+        let set: Set<HashableRustTypeRef> = [a, aCopy, b]
+        XCTAssertEqual(set.count, 2)
+    }
+
+    /// Verify that we can use Rust types as dictionary keys.
+    func testHashableDictionaryKey() throws {
+        var dictionary: [HashableRustTypeRef: Int] = [:]
+        dictionary[HashableRustType(5, "five")] = 5
+
+        XCTAssertEqual(dictionary[HashableRustType(5, "five")], 5)
+        XCTAssertNil(dictionary[HashableRustType(5, "cinco")])
+    }
+
+    /// Verify that `<` calls the Rust type's `Ord` implementation, which orders by descending
+    /// priority.
+    func testComparable() throws {
+        let values = [ComparableRustType(1), ComparableRustType(3), ComparableRustType(2)]
+
+        let sorted = values.sorted()
+        XCTAssertEqual(sorted.map { $0.priority() }, [3, 2, 1])
+
+        XCTAssert(ComparableRustType(3) < ComparableRustType(1))
+        XCTAssert(ComparableRustType(2) <= ComparableRustType(2))
+    }
+
+    /// Verify that references can be compared to each other and to owned values.
+    func testComparableReference() throws {
+        let high = ComparableRustType(10)
+        let low = ComparableRustType(0)
+
+        let highRef: ComparableRustTypeRef = high.reference_to()
+        let lowRef: ComparableRustTypeRef = low.reference_to()
+
+        XCTAssert(highRef < lowRef)
+        XCTAssert(highRef < low)
+        XCTAssertEqual([lowRef, highRef].max()!, lowRef)
+    }
+
+    /// Verify that `description` calls the Rust type's `Display` implementation and
+    /// `debugDescription` calls its `Debug` implementation.
+    func testCustomStringConvertible() throws {
+        let value = StringConvertibleRustType(1, -2)
+
+        XCTAssertEqual(value.description, "(1, -2)")
+        XCTAssertEqual("\(value)", "(1, -2)")
+        XCTAssertEqual(value.debugDescription, "StringConvertibleRustType { x: 1, y: -2 }")
+        XCTAssertEqual(String(reflecting: value), "StringConvertibleRustType { x: 1, y: -2 }")
+    }
+
+    /// Verify that `clone()` calls the Rust type's `Clone` implementation and returns a new owned
+    /// instance that is independent of the original.
+    func testClone() throws {
+        let original = CloneableRustType(1)
+        let clone: CloneableRustType = original.clone()
+
+        original.set_value(2)
+        XCTAssertEqual(original.value(), 2)
+        XCTAssertEqual(clone.value(), 1)
+
+        let reference: CloneableRustTypeRef = original
+        XCTAssertEqual(reference.clone().value(), 2)
+    }
+
+    /// Verify that `init()` uses the Rust type's `Default` implementation and that the type's
+    /// other initializers are still available.
+    func testDefault() throws {
+        XCTAssertEqual(DefaultRustType().value(), 7)
+        XCTAssertEqual(DefaultRustType(3).value(), 3)
+    }
+}
@@ -0,0 +1,89 @@
+//
+//  OperatorTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for the `#[swift_bridge(operators(...))]` attribute.
+/// See crates/swift-integration-tests/src/operators.rs
+class OperatorTests: XCTestCase {
+
+    /// Verify that a shared struct's operators and subscript call its Rust `std::ops`
+    /// implementations.
+    func testSharedStructOperators() throws {
+        let a = OperatorsVec2(x: 1, y: 2)
+        let b = OperatorsVec2(x: 10, y: 20)
+
+        let sum = a + b
+        XCTAssertEqual(sum.x, 11)
+        XCTAssertEqual(sum.y, 22)
+
+        let difference = b - a
+        XCTAssertEqual(difference.x, 9)
+        XCTAssertEqual(difference.y, 18)
+
+        let negated = -a
+        XCTAssertEqual(negated.x, -1)
+        XCTAssertEqual(negated.y, -2)
+
+        XCTAssertEqual(b[0], 10)
+        XCTAssertEqual(b[1], 20)
+    }
+
+    /// Verify that an opaque Rust type's operators and subscript call its Rust `std::ops`
+    /// implementations.
+    func testOpaqueTypeOperators() throws {
+        // 1 + 2x
+        let a = OperatorsPolynomial(1, 2, 0)
+        // 3 + x^2
+        let b = OperatorsPolynomial(3, 0, 1)
+
+        // 4 + 2x + x^2
+        let sum = a + b
+        XCTAssertEqual(sum.degree(), 2)
+        XCTAssertEqual([sum[0], sum[1], sum[2]], [4, 2, 1])
+
+        // 3 + 6x + x^2 + 2x^3
+        let product = a * b
+        XCTAssertEqual(product.degree(), 3)
+        XCTAssertEqual([product[0], product[1], product[2], product[3]], [3, 6, 1, 2])
+
+        let negated = -a
+        XCTAssertEqual([negated[0], negated[1]], [-1, -2])
+
+        // Coefficients past the degree are zero.
+        XCTAssertEqual(a[10], 0)
+    }
+
+    /// Verify that the same value can be used as both operands, since opaque operands are
+    /// passed by reference.
+    func testOpaqueTypeOperandsAreNotConsumed() throws {
+        let a = OperatorsPolynomial(1, 1, 0)
+
+        // 1 + 2x + x^2
+        let square = a * a
+        XCTAssertEqual([square[0], square[1], square[2]], [1, 2, 1])
+
+        // `a` is still usable.
+        XCTAssertEqual(a.degree(), 1)
+    }
+
+    /// Verify that the operators of a `Clone` opaque Rust type call its `std::ops`
+    /// implementations on clones of the operands.
+    func testCloneOpaqueTypeOperatorsTakeOwnedValues() throws {
+        let a = OperatorsCounter(10)
+        let b = OperatorsCounter(3)
+
+        let difference = a - b
+        XCTAssertEqual(difference.count(), 7)
+
+        let negated = -a
+        XCTAssertEqual(negated.count(), -10)
+
+        // The operands were cloned, so they are still usable.
+        XCTAssertEqual(a.count(), 10)
+        XCTAssertEqual(b.count(), 3)
+    }
+}
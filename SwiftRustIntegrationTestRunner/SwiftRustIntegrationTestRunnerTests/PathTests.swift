@@ -0,0 +1,49 @@
+//
+//  PathTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing `&Path`, `PathBuf`, `&OsStr` and `OsString` between Swift and Rust.
+/// See crates/swift-integration-tests/src/path.rs
+class PathTests: XCTestCase {
+    func testPassStringAsRefPath() throws {
+        XCTAssertEqual(rust_path_file_name("/tmp/some_file.txt").toString(), "some_file.txt")
+    }
+
+    func testPassFileURLAsRefPath() throws {
+        let url = URL(fileURLWithPath: "/tmp/some file ü.txt")
+        XCTAssertEqual(rust_path_file_name(url).toString(), "some file ü.txt")
+    }
+
+    func testPassFileURLAsPathBuf() throws {
+        let url = URL(fileURLWithPath: "/tmp")
+        let joined = rust_path_join(url, "child")
+
+        XCTAssertEqual(joined.toString(), "/tmp/child")
+        XCTAssertEqual(joined.toFileURL(), URL(fileURLWithPath: "/tmp/child"))
+    }
+
+    func testReflectOsString() throws {
+        XCTAssertEqual(rust_reflect_os_string("héllo").toString(), "héllo")
+    }
+
+    func testOptionPaths() throws {
+        XCTAssertEqual(rust_reflect_option_path_buf("/tmp")!.toString(), "/tmp")
+        XCTAssertNil(rust_reflect_option_path_buf(nil as String?))
+
+        XCTAssertTrue(rust_option_ref_path_is_some("/tmp"))
+        XCTAssertFalse(rust_option_ref_path_is_some(nil as String?))
+    }
+
+    func testRefOsStrToFileURL() throws {
+        XCTAssertEqual(rust_static_os_str().toFileURL().path, "/tmp/héllo")
+    }
+
+    /// Verify that a `&Path` that is not valid UTF-8 gets converted lossily instead of trapping.
+    func testNonUtf8RefPathToString() throws {
+        XCTAssertEqual(rust_static_non_utf8_path().toString(), "/tmp/\u{FFFD}")
+    }
+}
@@ -29,5 +29,15 @@ class SharedStructAttributeTests: XCTestCase {
             123
         )
     }
+
+    /// Verify that `init()` uses the struct's Rust `Default` implementation.
+    /// See crates/swift-integration-tests/src/struct_attributes/default.rs
+    func testSharedStructDefaultInitializer() throws {
+        let val = DefaultStruct()
+
+        XCTAssertEqual(val.count, 3)
+        XCTAssertEqual(val.ratio, 0.5)
+        XCTAssertEqual(val.enabled, true)
+    }
 }
 
@@ -0,0 +1,93 @@
+//
+//  StreamTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for Rust functions that return an `impl Stream<Item = T>` or a
+/// `Pin<Box<dyn Stream<Item = T> + Send>>`.
+/// See crates/swift-integration-tests/src/stream.rs
+class StreamTests: XCTestCase {
+    func testStreamOfPrimitives() async throws {
+        var items: [UInt32] = []
+        for try await item in rust_count_stream(5) {
+            items.append(item)
+        }
+
+        XCTAssertEqual(items, [1, 2, 3, 4, 5])
+    }
+
+    func testBoxedStreamOfStrings() async throws {
+        var words: [String] = []
+        for try await word in rust_words_stream("hello swift bridge") {
+            words.append(word.toString())
+        }
+
+        XCTAssertEqual(words, ["hello", "swift", "bridge"])
+    }
+
+    func testStreamOfChars() async throws {
+        var chars: [Unicode.Scalar] = []
+        for try await char in rust_chars_stream("héllo") {
+            chars.append(char)
+        }
+
+        XCTAssertEqual(String(String.UnicodeScalarView(chars)), "héllo")
+    }
+
+    /// Verify that a panic while polling the stream gets thrown from the loop as a `RustPanic`.
+    func testStreamThrowsPanic() async throws {
+        do {
+            for try await _ in rust_panicking_stream() {
+                XCTFail("The stream should not yield an item.")
+            }
+            XCTFail("Iteration should have thrown a RustPanic.")
+        } catch let error as RustPanic {
+            XCTAssertEqual(error.message, "stream panic")
+        }
+    }
+
+    /// Verify that the Rust stream gets dropped when Swift stops iterating over it early.
+    func testStreamIsDroppedWhenIterationEnds() async throws {
+        let liveStreams = rust_live_stream_count()
+
+        do {
+            var items: [UInt32] = []
+            for try await item in rust_count_stream(UInt32.max) {
+                items.append(item)
+                if items.count == 3 {
+                    break
+                }
+            }
+
+            XCTAssertEqual(items, [1, 2, 3])
+        }
+
+        XCTAssertEqual(rust_live_stream_count(), liveStreams)
+    }
+
+    /// Verify that cancelling the task that is iterating over a stream stops waiting for the
+    /// next item and drops the Rust stream.
+    func testCancellingIterationDropsStream() async throws {
+        let liveStreams = rust_live_stream_count()
+
+        let task = Task {
+            for try await _ in rust_never_ending_stream() {
+                XCTFail("The stream should never yield an item.")
+            }
+        }
+
+        try await Task.sleep(nanoseconds: 10_000_000)
+        task.cancel()
+
+        do {
+            try await task.value
+            XCTFail("Iteration should have thrown a CancellationError.")
+        } catch is CancellationError {
+        }
+
+        XCTAssertEqual(rust_live_stream_count(), liveStreams)
+    }
+}
@@ -0,0 +1,52 @@
+//
+//  TimeTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing `Duration`s and `SystemTime`s between Swift and Rust.
+/// See crates/swift-integration-tests/src/time.rs
+class TimeTests: XCTestCase {
+    func testReflectDuration() throws {
+        XCTAssertEqual(rust_reflect_duration(1.5), 1.5)
+        XCTAssertEqual(rust_duration_secs_f64(2.25), 2.25)
+    }
+
+    func testReflectSystemTime() throws {
+        let date = Date(timeIntervalSince1970: 1_650_000_000.5)
+        XCTAssertEqual(rust_reflect_system_time(date), date)
+    }
+
+    /// Verify that we can pass dates from before the UNIX epoch.
+    func testReflectSystemTimeBeforeEpoch() throws {
+        let date = Date(timeIntervalSince1970: -100.25)
+        XCTAssertEqual(rust_reflect_system_time(date), date)
+    }
+
+    func testReflectOptionDuration() throws {
+        XCTAssertEqual(rust_reflect_option_duration(3), 3)
+        XCTAssertNil(rust_reflect_option_duration(nil))
+    }
+
+    func testReflectOptionSystemTime() throws {
+        let date = Date(timeIntervalSince1970: 10)
+        XCTAssertEqual(rust_reflect_option_system_time(date), date)
+        XCTAssertNil(rust_reflect_option_system_time(nil))
+    }
+
+    func testReflectStructWithTimeFields() throws {
+        let date = Date(timeIntervalSince1970: 20)
+        let reflected = rust_reflect_struct_with_time_fields(
+            StructWithTimeFields(timeout: 0.25, created_at: date)
+        )
+        XCTAssertEqual(reflected.timeout, 0.25)
+        XCTAssertEqual(reflected.created_at, date)
+    }
+
+    func testRustCallsSwiftWithSystemTime() throws {
+        let date = Date(timeIntervalSince1970: 30)
+        XCTAssertEqual(rust_calls_swift_add_one_second(date), Date(timeIntervalSince1970: 31))
+    }
+}
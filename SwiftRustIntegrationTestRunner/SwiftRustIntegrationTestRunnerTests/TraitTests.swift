@@ -0,0 +1,96 @@
+//
+//  TraitTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for traits that are declared in the bridge module.
+/// See crates/swift-integration-tests/src/traits.rs
+class TraitTests: XCTestCase {
+
+    /// Verify that the Swift classes of a trait's implementors conform to the trait's protocol.
+    func testImplementorConformsToProtocol() throws {
+        let circle = Circle(2)
+        let shape: any Shape = circle
+
+        XCTAssertEqual(shape.area(), Double.pi * 4, accuracy: 0.0001)
+        XCTAssertEqual(shape.name().toString(), "circle")
+
+        shape.scale(0.5)
+        XCTAssertEqual(circle.area(), Double.pi, accuracy: 0.0001)
+    }
+
+    /// Verify that we can call the methods of a `Box<dyn Trait>` that was returned from Rust.
+    func testReturnBoxedTraitObject() throws {
+        let square = make_square(3)
+
+        XCTAssertEqual(square.area(), 9)
+        XCTAssertEqual(square.name().toString(), "square")
+
+        square.scale(2)
+        XCTAssertEqual(square.area(), 36)
+    }
+
+    /// Verify that we can pass both Rust trait objects and implementors to Rust as a
+    /// `Box<dyn Trait>`.
+    func testPassBoxedTraitObject() throws {
+        XCTAssertEqual(shape_area(make_square(2)), 4)
+        XCTAssertEqual(shape_area(Circle(1)), Double.pi, accuracy: 0.0001)
+
+        let scaled = scale_shape(Circle(1), 3)
+        XCTAssertEqual(scaled.name().toString(), "circle")
+        XCTAssertEqual(scaled.area(), Double.pi * 9, accuracy: 0.0001)
+    }
+
+    /// Verify that we can pass a Swift value that conforms to a trait's protocol to Rust as a
+    /// `Box<dyn Trait>`, and that Rust releases the value when it drops the trait object.
+    func testPassSwiftImplementedTraitObject() throws {
+        var wasDeinitialized = false
+        var triangle: SwiftTriangle? = SwiftTriangle(base: 4, height: 3, onDeinit: {
+            wasDeinitialized = true
+        })
+
+        XCTAssertEqual(describe_shape(triangle!).toString(), "triangle with an area of 6")
+
+        let scaled = scale_shape(triangle!, 2)
+        triangle = nil
+        XCTAssertFalse(wasDeinitialized)
+
+        XCTAssertEqual(scaled.area(), 24)
+        XCTAssertEqual(scaled.name().toString(), "triangle")
+
+        let _ = shape_area(scaled)
+        XCTAssert(wasDeinitialized)
+    }
+}
+
+class SwiftTriangle: Shape {
+    var base: Double
+    var height: Double
+    let onDeinit: () -> Void
+
+    init(base: Double, height: Double, onDeinit: @escaping () -> Void) {
+        self.base = base
+        self.height = height
+        self.onDeinit = onDeinit
+    }
+
+    deinit {
+        onDeinit()
+    }
+
+    func area() -> Double {
+        base * height / 2
+    }
+
+    func name() -> RustString {
+        "triangle".intoRustString()
+    }
+
+    func scale(_ factor: Double) {
+        base *= factor
+        height *= factor
+    }
+}
@@ -34,10 +34,28 @@ fn core_swift() -> String {
 
     core_swift += include_str!("src/std_bridge/string.swift");
     core_swift += include_str!("src/std_bridge/rust_vec.swift");
+    core_swift += include_str!("src/std_bridge/iterator.swift");
+    core_swift += include_str!("src/std_bridge/int128.swift");
+    core_swift += include_str!("src/std_bridge/time.swift");
+    // These are only used by async functions and streams, so they're only available when async
+    // support is enabled.
+    if std::env::var("CARGO_FEATURE_ASYNC_RUNTIME_AGNOSTIC").is_ok() {
+        core_swift += include_str!("src/std_bridge/stream.swift");
+        core_swift += include_str!("src/std_bridge/rust_panic.swift");
+        core_swift += include_str!("src/std_bridge/local_task.swift");
+        core_swift += include_str!("src/std_bridge/async_runtime.swift");
+    }
 
     for path in vec![
         "src/std_bridge/string.swift",
         "src/std_bridge/rust_vec.swift",
+        "src/std_bridge/iterator.swift",
+        "src/std_bridge/int128.swift",
+        "src/std_bridge/time.swift",
+        "src/std_bridge/stream.swift",
+        "src/std_bridge/rust_panic.swift",
+        "src/std_bridge/local_task.swift",
+        "src/std_bridge/async_runtime.swift",
     ] {
         println!(
             "cargo:rerun-if-changed={}",
@@ -76,6 +94,16 @@ typedef struct RustStr { uint8_t* const start; uintptr_t len; } RustStr;
 typedef struct __private__FfiSlice { void* const start; uintptr_t len; } __private__FfiSlice;
 typedef struct __private__PointerToSwiftType { void* ptr; } __private__RustHandleToSwiftType;
 void* __swift_bridge__null_pointer(void);
+void* __swift_bridge__$CancellationToken$new(void);
+void __swift_bridge__$CancellationToken$cancel(void* token);
+void __swift_bridge__$CancellationToken$_free(void* token);
+void __swift_bridge__$LocalTask$poll(void* task);
+uintptr_t __swift_bridge__$AsyncRuntime$in_flight_tasks(void);
+bool __swift_bridge__$AsyncRuntime$shutdown(double timeout_secs);
+typedef struct RustU128 { uint64_t low; uint64_t high; } RustU128;
+typedef struct RustI128 { uint64_t low; int64_t high; } RustI128;
+typedef struct __private__FfiDuration { uint64_t secs; uint32_t nanos; } __private__FfiDuration;
+typedef struct __private__FfiSystemTime { int64_t secs; uint32_t nanos; } __private__FfiSystemTime;
 
 typedef struct __private__OptionU8 { uint8_t val; bool is_some; } __private__OptionU8;
 typedef struct __private__OptionI8 { int8_t val; bool is_some; } __private__OptionI8;
@@ -85,11 +113,15 @@ typedef struct __private__OptionU32 { uint32_t val; bool is_some; } __private__O
 typedef struct __private__OptionI32 { int32_t val; bool is_some; } __private__OptionI32;
 typedef struct __private__OptionU64 { uint64_t val; bool is_some; } __private__OptionU64;
 typedef struct __private__OptionI64 { int64_t val; bool is_some; } __private__OptionI64;
+typedef struct __private__OptionU128 { struct RustU128 val; bool is_some; } __private__OptionU128;
+typedef struct __private__OptionI128 { struct RustI128 val; bool is_some; } __private__OptionI128;
 typedef struct __private__OptionUsize { uintptr_t val; bool is_some; } __private__OptionUsize;
 typedef struct __private__OptionIsize { intptr_t val; bool is_some; } __private__OptionIsize;
 typedef struct __private__OptionF32 { float val; bool is_some; } __private__OptionF32;
 typedef struct __private__OptionF64 { double val; bool is_some; } __private__OptionDouble;
 typedef struct __private__OptionBool { bool val; bool is_some; } __private__OptionBool;
+typedef struct __private__OptionDuration { struct __private__FfiDuration val; bool is_some; } __private__OptionDuration;
+typedef struct __private__OptionSystemTime { struct __private__FfiSystemTime val; bool is_some; } __private__OptionSystemTime;
 "#
     .to_string();
 
@@ -2,7 +2,7 @@ use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
 use std::str::FromStr;
 
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 use quote::{quote, quote_spanned};
 use syn::{FnArg, Pat, PatType, Path, ReturnType, Type};
@@ -11,10 +11,18 @@ use crate::parse::{HostLang, OpaqueRustTypeGenerics, TypeDeclarations};
 use crate::SWIFT_BRIDGE_PREFIX;
 
 use self::bridged_option::BridgedOption;
+pub(crate) use self::bridged_trait_object::BridgedTraitObject;
+pub(crate) use self::built_in_iterator::BuiltInIterator;
+pub(crate) use self::built_in_result::BuiltInResult;
+pub(crate) use self::built_in_stream::BuiltInStream;
 pub(crate) use self::shared_enum::{EnumVariant, SharedEnum};
 pub(crate) use self::shared_struct::{SharedStruct, StructFields, StructSwiftRepr};
 
 mod bridged_option;
+mod bridged_trait_object;
+mod built_in_iterator;
+mod built_in_result;
+mod built_in_stream;
 mod shared_enum;
 mod shared_struct;
 
@@ -29,6 +37,8 @@ pub(crate) enum BridgedType {
 pub(crate) enum CustomBridgedType {
     Shared(SharedType),
     Opaque(OpaqueForeignType),
+    /// `Box<dyn SomeTrait>`
+    TraitObject(BridgedTraitObject),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -51,6 +61,20 @@ pub(crate) enum StdLibType {
     F32,
     F64,
     Bool,
+    /// `char`
+    Char,
+    /// `u128`
+    U128,
+    /// `i128`
+    I128,
+    /// `NonZeroU8`, `NonZeroI32`, etc
+    NonZero(BuiltInNonZero),
+    /// `std::time::Duration`
+    Duration,
+    /// `std::time::SystemTime`
+    SystemTime,
+    /// `&Path`, `PathBuf`, `&OsStr` or `OsString`
+    Path(BuiltInPath),
     /// `*const T` or `*mut T`
     Pointer(BuiltInPointer),
     /// `&[T]` or `&mut [T]`
@@ -60,6 +84,14 @@ pub(crate) enum StdLibType {
     String,
     Vec(BuiltInVec),
     Option(BridgedOption),
+    /// `[T; N]`
+    Array(BuiltInArray),
+    /// `impl Iterator<Item = T>` or `Box<dyn Iterator<Item = T>>`
+    Iterator(BuiltInIterator),
+    /// `Result<T, E>`
+    Result(BuiltInResult),
+    /// `impl Stream<Item = T>` or `Pin<Box<dyn Stream<Item = T> + Send>>`
+    Stream(BuiltInStream),
 }
 
 /// TODO: Add this to `OpaqueForeignType`
@@ -135,6 +167,235 @@ pub(crate) struct BuiltInVec {
     pub ty: Box<BridgedType>,
 }
 
+/// NonZeroU8, NonZeroI32, etc
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BuiltInNonZero {
+    /// The integer type that the NonZero type wraps. i.e. `U32` for `NonZeroU32`.
+    pub ty: Box<BridgedType>,
+}
+
+impl BuiltInNonZero {
+    /// "NonZeroU32" -> Some(U32)
+    /// "std :: num :: NonZeroU32" -> Some(U32)
+    fn new_with_str(string: &str) -> Option<Self> {
+        let string = string
+            .trim_start_matches("std :: num :: ")
+            .trim_start_matches("core :: num :: ");
+
+        let ty = match string {
+            "NonZeroU8" => StdLibType::U8,
+            "NonZeroI8" => StdLibType::I8,
+            "NonZeroU16" => StdLibType::U16,
+            "NonZeroI16" => StdLibType::I16,
+            "NonZeroU32" => StdLibType::U32,
+            "NonZeroI32" => StdLibType::I32,
+            "NonZeroU64" => StdLibType::U64,
+            "NonZeroI64" => StdLibType::I64,
+            "NonZeroUsize" => StdLibType::Usize,
+            "NonZeroIsize" => StdLibType::Isize,
+            _ => return None,
+        };
+
+        Some(BuiltInNonZero {
+            ty: Box::new(BridgedType::StdLib(ty)),
+        })
+    }
+
+    /// U32 -> std::num::NonZeroU32
+    fn to_rust_type_path(&self) -> TokenStream {
+        let suffix = match self.ty.deref() {
+            BridgedType::StdLib(StdLibType::U8) => "U8",
+            BridgedType::StdLib(StdLibType::I8) => "I8",
+            BridgedType::StdLib(StdLibType::U16) => "U16",
+            BridgedType::StdLib(StdLibType::I16) => "I16",
+            BridgedType::StdLib(StdLibType::U32) => "U32",
+            BridgedType::StdLib(StdLibType::I32) => "I32",
+            BridgedType::StdLib(StdLibType::U64) => "U64",
+            BridgedType::StdLib(StdLibType::I64) => "I64",
+            BridgedType::StdLib(StdLibType::Usize) => "Usize",
+            BridgedType::StdLib(StdLibType::Isize) => "Isize",
+            _ => unreachable!("NonZero types always wrap an integer"),
+        };
+        let ty = Ident::new(&format!("NonZero{}", suffix), Span::call_site());
+
+        quote! { std::num::#ty }
+    }
+}
+
+/// `&Path`, `PathBuf`, `&OsStr` or `OsString`.
+///
+/// On the Swift side these look the same as `&str` and `String`. Borrowed paths are passed as a
+/// `RustStr` that points to the path's raw bytes and owned paths are passed as a `RustString`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum BuiltInPath {
+    /// `&Path`
+    RefPath,
+    /// `PathBuf`
+    PathBuf,
+    /// `&OsStr`
+    RefOsStr,
+    /// `OsString`
+    OsString,
+}
+
+impl BuiltInPath {
+    /// "PathBuf" -> Some(PathBuf)
+    /// "std :: ffi :: OsString" -> Some(OsString)
+    fn new_with_owned_str(string: &str) -> Option<Self> {
+        match string {
+            "PathBuf" | "std :: path :: PathBuf" => Some(BuiltInPath::PathBuf),
+            "OsString" | "std :: ffi :: OsString" => Some(BuiltInPath::OsString),
+            _ => None,
+        }
+    }
+
+    /// "Path" -> Some(RefPath)
+    /// "std :: ffi :: OsStr" -> Some(RefOsStr)
+    fn new_with_borrowed_str(string: &str) -> Option<Self> {
+        match string {
+            "Path" | "std :: path :: Path" => Some(BuiltInPath::RefPath),
+            "OsStr" | "std :: ffi :: OsStr" => Some(BuiltInPath::RefOsStr),
+            _ => None,
+        }
+    }
+
+    fn is_owned(&self) -> bool {
+        matches!(self, BuiltInPath::PathBuf | BuiltInPath::OsString)
+    }
+
+    /// The string type that this path is bridged the same way as.
+    /// `&Path` -> `&str`, `PathBuf` -> `String`
+    fn string_equivalent(&self) -> BridgedType {
+        if self.is_owned() {
+            BridgedType::StdLib(StdLibType::String)
+        } else {
+            BridgedType::StdLib(StdLibType::Str)
+        }
+    }
+
+    fn to_rust_type_path(self) -> TokenStream {
+        match self {
+            BuiltInPath::RefPath => quote! { &std::path::Path },
+            BuiltInPath::PathBuf => quote! { std::path::PathBuf },
+            BuiltInPath::RefOsStr => quote! { &std::ffi::OsStr },
+            BuiltInPath::OsString => quote! { std::ffi::OsString },
+        }
+    }
+
+    // Owned paths that aren't valid UTF-8 get lossily converted, since a Swift String must
+    // always be valid unicode.
+    fn convert_rust_value_to_ffi_value(
+        &self,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+    ) -> TokenStream {
+        if self.is_owned() {
+            quote! {
+                #swift_bridge_path::string::RustString::from_os_string( #expression ).box_into_raw()
+            }
+        } else {
+            quote! {
+                #swift_bridge_path::string::RustStr::from_os_str(
+                    AsRef::<std::ffi::OsStr>::as_ref( #expression )
+                )
+            }
+        }
+    }
+
+    fn convert_ffi_value_to_rust_value(&self, value: &TokenStream, span: Span) -> TokenStream {
+        match self {
+            BuiltInPath::RefPath => {
+                quote_spanned! {span=> std::path::Path::new(#value.to_os_str()) }
+            }
+            BuiltInPath::PathBuf => {
+                quote_spanned! {span=>
+                    std::path::PathBuf::from(unsafe { Box::from_raw(#value).0 })
+                }
+            }
+            BuiltInPath::RefOsStr => {
+                quote_spanned! {span=> #value.to_os_str() }
+            }
+            BuiltInPath::OsString => {
+                quote_spanned! {span=>
+                    std::ffi::OsString::from(unsafe { Box::from_raw(#value).0 })
+                }
+            }
+        }
+    }
+}
+
+/// [T; N]
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BuiltInArray {
+    pub ty: Box<BridgedType>,
+    pub len: usize,
+}
+
+impl BuiltInArray {
+    /// Whether or not we support passing arrays of this type over FFI.
+    ///
+    /// We currently support arrays of primitives and arrays of shared types.
+    fn supports_element(ty: &BridgedType) -> bool {
+        match ty {
+            BridgedType::StdLib(stdlib_ty) => stdlib_ty.is_primitive(),
+            BridgedType::Foreign(CustomBridgedType::Shared(_)) => true,
+            BridgedType::Foreign(CustomBridgedType::Opaque(_)) => false,
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => false,
+        }
+    }
+
+    /// Arrays of primitives can be passed over FFI as is, while arrays of shared types need
+    /// each element to be converted to/from its FFI representation.
+    fn elements_need_conversion(&self) -> bool {
+        match self.ty.deref() {
+            BridgedType::StdLib(stdlib_ty) => !stdlib_ty.is_primitive(),
+            _ => true,
+        }
+    }
+
+    /// The name of the C struct that is used to pass the array over FFI.
+    ///
+    /// [u8; 32] -> __swift_bridge__$Array$u8$32
+    /// [SomeStruct; 2] -> __swift_bridge__$Array$SomeStruct$2
+    pub(crate) fn ffi_name_string(&self) -> String {
+        let element = match self.ty.deref() {
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct.swift_name_string()
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
+                shared_enum.swift_name_string()
+            }
+            other => other.to_rust_type_path().to_string(),
+        };
+
+        format!("{}$Array${}${}", SWIFT_BRIDGE_PREFIX, element, self.len)
+    }
+
+    /// The C typedef for the struct that is used to pass the array over FFI.
+    ///
+    /// The same array type can be used in multiple bridge modules, so we guard against declaring
+    /// the struct more than once.
+    pub(crate) fn c_typedef(&self) -> String {
+        format!(
+            r#"#ifndef {name}$Declared
+#define {name}$Declared
+typedef struct {name} {{ {element} array[{len}]; }} {name};
+#endif"#,
+            name = self.ffi_name_string(),
+            element = self.ty.to_c(),
+            len = self.len
+        )
+    }
+
+    /// `(UInt8, UInt8, UInt8)`
+    fn to_swift_tuple_type(&self, type_pos: TypePosition, types: &TypeDeclarations) -> String {
+        let element = self.ty.to_swift_type(type_pos, types);
+        let elements = vec![element; self.len];
+
+        format!("({})", elements.join(", "))
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) enum PointerKind {
     Const,
@@ -162,6 +423,28 @@ impl BridgedType {
     }
 }
 
+impl StdLibType {
+    /// Whether or not this is a number or a bool.
+    pub(crate) fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            StdLibType::U8
+                | StdLibType::I8
+                | StdLibType::U16
+                | StdLibType::I16
+                | StdLibType::U32
+                | StdLibType::I32
+                | StdLibType::U64
+                | StdLibType::I64
+                | StdLibType::Usize
+                | StdLibType::Isize
+                | StdLibType::F32
+                | StdLibType::F64
+                | StdLibType::Bool
+        )
+    }
+}
+
 #[cfg(test)]
 impl BridgedType {
     fn unwrap_stdlib(&self) -> &StdLibType {
@@ -302,6 +585,18 @@ impl BridgedType {
             Type::Path(path) => {
                 if let Some(ty) = types.get_with_type_path(path) {
                     Some(ty.to_bridged_type(false, false))
+                } else if let Some(trait_object) =
+                    BridgedTraitObject::new_with_type_path(path, types)
+                {
+                    Some(BridgedType::Foreign(CustomBridgedType::TraitObject(
+                        trait_object,
+                    )))
+                } else if let Some(iterator) = BuiltInIterator::new_with_type_path(path, types) {
+                    Some(BridgedType::StdLib(StdLibType::Iterator(iterator)))
+                } else if let Some(result) = BuiltInResult::new_with_type_path(path, types) {
+                    Some(BridgedType::StdLib(StdLibType::Result(result)))
+                } else if let Some(stream) = BuiltInStream::new_with_type_path(path, types) {
+                    Some(BridgedType::StdLib(StdLibType::Stream(stream)))
                 } else {
                     Self::new_with_str(
                         path.path.segments.to_token_stream().to_string().as_str(),
@@ -338,6 +633,9 @@ impl BridgedType {
                         if path == "str" {
                             return Some(BridgedType::StdLib(StdLibType::Str));
                         }
+                        if let Some(path) = BuiltInPath::new_with_borrowed_str(&path) {
+                            return Some(BridgedType::StdLib(StdLibType::Path(path)));
+                        }
 
                         None
                     }
@@ -347,6 +645,42 @@ impl BridgedType {
                 }),
                 _ => None,
             },
+            Type::Array(array) => {
+                let len = match &array.len {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(len),
+                        ..
+                    }) => len.base10_parse::<usize>().ok()?,
+                    _ => return None,
+                };
+                // C doesn't allow zero length arrays.
+                if len == 0 {
+                    return None;
+                }
+
+                let ty = Self::new_with_type(&array.elem, types)?;
+                if !BuiltInArray::supports_element(&ty) {
+                    return None;
+                }
+
+                Some(BridgedType::StdLib(StdLibType::Array(BuiltInArray {
+                    ty: Box::new(ty),
+                    len,
+                })))
+            }
+            Type::ImplTrait(impl_trait) => {
+                if let Some(iterator) =
+                    BuiltInIterator::new_with_impl_trait(&impl_trait.bounds, types)
+                {
+                    Some(BridgedType::StdLib(StdLibType::Iterator(iterator)))
+                } else {
+                    BuiltInStream::new_with_impl_trait(&impl_trait.bounds, types)
+                        .map(|stream| BridgedType::StdLib(StdLibType::Stream(stream)))
+                }
+            }
+            Type::Tuple(tuple) if tuple.elems.is_empty() => {
+                Some(BridgedType::StdLib(StdLibType::Null))
+            }
             _ => None,
         }
     }
@@ -414,9 +748,25 @@ impl BridgedType {
             "isize" => BridgedType::StdLib(StdLibType::Isize),
             "f32" => BridgedType::StdLib(StdLibType::F32),
             "f64" => BridgedType::StdLib(StdLibType::F64),
+            "u128" => BridgedType::StdLib(StdLibType::U128),
+            "i128" => BridgedType::StdLib(StdLibType::I128),
             "String" => BridgedType::StdLib(StdLibType::String),
             "bool" => BridgedType::StdLib(StdLibType::Bool),
+            "char" => BridgedType::StdLib(StdLibType::Char),
+            "Duration" | "std :: time :: Duration" | "core :: time :: Duration" => {
+                BridgedType::StdLib(StdLibType::Duration)
+            }
+            "SystemTime" | "std :: time :: SystemTime" => {
+                BridgedType::StdLib(StdLibType::SystemTime)
+            }
             _ => {
+                if let Some(non_zero) = BuiltInNonZero::new_with_str(string) {
+                    return Some(BridgedType::StdLib(StdLibType::NonZero(non_zero)));
+                }
+                if let Some(path) = BuiltInPath::new_with_owned_str(string) {
+                    return Some(BridgedType::StdLib(StdLibType::Path(path)));
+                }
+
                 let bridged_type = types.get(string)?;
                 let bridged_type = bridged_type.to_bridged_type(false, false);
                 bridged_type
@@ -449,6 +799,13 @@ impl BridgedType {
                     StdLibType::F32 => quote! { f32 },
                     StdLibType::F64 => quote! { f64 },
                     StdLibType::Bool => quote! { bool },
+                    StdLibType::Char => quote! { char },
+                    StdLibType::U128 => quote! { u128 },
+                    StdLibType::I128 => quote! { i128 },
+                    StdLibType::NonZero(non_zero) => non_zero.to_rust_type_path(),
+                    StdLibType::Duration => quote! { std::time::Duration },
+                    StdLibType::SystemTime => quote! { std::time::SystemTime },
+                    StdLibType::Path(path) => path.to_rust_type_path(),
                     StdLibType::Pointer(ptr) => {
                         let ptr_kind = &ptr.kind;
 
@@ -477,6 +834,14 @@ impl BridgedType {
                         let ty = opt.ty.to_rust_type_path();
                         quote! { Option<#ty> }
                     }
+                    StdLibType::Array(array) => {
+                        let ty = array.ty.to_rust_type_path();
+                        let len = Literal::usize_unsuffixed(array.len);
+                        quote! { [#ty; #len] }
+                    }
+                    StdLibType::Iterator(iterator) => iterator.to_rust_type_path(),
+                    StdLibType::Result(result) => result.to_rust_type_path(),
+                    StdLibType::Stream(stream) => stream.to_rust_type_path(),
                 }
             }
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
@@ -489,6 +854,9 @@ impl BridgedType {
                 //
                 todo!("Shared enum to Rust type name")
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => {
+                trait_object.to_rust_type_path()
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let ty_name = &opaque.ty;
 
@@ -530,6 +898,17 @@ impl BridgedType {
                 StdLibType::Usize => quote! { usize },
                 StdLibType::Isize => quote! { isize },
                 StdLibType::Bool => quote! { bool },
+                StdLibType::Char => quote! { u32 },
+                StdLibType::U128 => quote! { #swift_bridge_path::int128::RustU128 },
+                StdLibType::I128 => quote! { #swift_bridge_path::int128::RustI128 },
+                StdLibType::NonZero(non_zero) => non_zero
+                    .ty
+                    .to_ffi_compatible_rust_type(swift_bridge_path, types),
+                StdLibType::Duration => quote! { #swift_bridge_path::time::FfiDuration },
+                StdLibType::SystemTime => quote! { #swift_bridge_path::time::FfiSystemTime },
+                StdLibType::Path(path) => path
+                    .string_equivalent()
+                    .to_ffi_compatible_rust_type(swift_bridge_path, types),
                 StdLibType::Pointer(ptr) => {
                     let kind = ptr.kind.to_token_stream();
 
@@ -607,6 +986,28 @@ impl BridgedType {
                         StdLibType::Bool => {
                             quote! { #swift_bridge_path::option::OptionBool }
                         }
+                        StdLibType::Char => {
+                            quote! { #swift_bridge_path::option::OptionU32 }
+                        }
+                        StdLibType::U128 => {
+                            quote! { #swift_bridge_path::option::OptionU128 }
+                        }
+                        StdLibType::I128 => {
+                            quote! { #swift_bridge_path::option::OptionI128 }
+                        }
+                        // We use zero to represent `None`, same as Rust does.
+                        StdLibType::NonZero(non_zero) => non_zero
+                            .ty
+                            .to_ffi_compatible_rust_type(swift_bridge_path, types),
+                        StdLibType::Duration => {
+                            quote! { #swift_bridge_path::option::OptionDuration }
+                        }
+                        StdLibType::SystemTime => {
+                            quote! { #swift_bridge_path::option::OptionSystemTime }
+                        }
+                        StdLibType::Path(path) => path
+                            .string_equivalent()
+                            .to_ffi_compatible_rust_type(swift_bridge_path, types),
                         StdLibType::Pointer(_) => {
                             todo!("Option<*const T> and Option<*mut T> are not yet supported")
                         }
@@ -625,6 +1026,18 @@ impl BridgedType {
                         StdLibType::Option(_) => {
                             todo!("Option<Option<T>> is not yet supported")
                         }
+                        StdLibType::Array(_) => {
+                            todo!("Option<[T; N]> is not yet supported")
+                        }
+                        StdLibType::Iterator(_) => {
+                            todo!("Option<impl Iterator<Item = T>> is not yet supported")
+                        }
+                        StdLibType::Result(_) => {
+                            todo!("Option<Result<T, E>> is not yet supported")
+                        }
+                        StdLibType::Stream(_) => {
+                            todo!("Option<impl Stream<Item = T>> is not yet supported")
+                        }
                     },
                     BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(
                         shared_struct,
@@ -638,6 +1051,9 @@ impl BridgedType {
                         let name = shared_enum.ffi_option_name_tokens();
                         quote! { #name }
                     }
+                    BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                        todo!("Option<Box<dyn Trait>> is not yet supported")
+                    }
                     BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                         let type_name = &opaque.ty;
 
@@ -652,6 +1068,21 @@ impl BridgedType {
                         }
                     }
                 },
+                StdLibType::Array(array) => {
+                    let ty = array
+                        .ty
+                        .to_ffi_compatible_rust_type(swift_bridge_path, types);
+                    let len = Literal::usize_unsuffixed(array.len);
+                    quote! { #swift_bridge_path::FfiArray<#ty, #len> }
+                }
+                StdLibType::Iterator(_) | StdLibType::Stream(_) => {
+                    quote! { *mut std::ffi::c_void }
+                }
+                StdLibType::Result(_) => {
+                    unreachable!(
+                        "Result<T, E> is only supported as the return type of async functions"
+                    )
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 let ty_name = &shared_struct.name;
@@ -673,6 +1104,9 @@ impl BridgedType {
 
                 quote! { #ffi_ty_name }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                quote! { *mut std::ffi::c_void }
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let ty_name = &opaque.ty;
 
@@ -725,6 +1159,50 @@ impl BridgedType {
                 StdLibType::Usize => "UInt".to_string(),
                 StdLibType::Isize => "Int".to_string(),
                 StdLibType::Bool => "Bool".to_string(),
+                StdLibType::Char => match type_pos {
+                    TypePosition::FnArg(func_host_lang)
+                    | TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            "Unicode.Scalar".to_string()
+                        } else {
+                            "UInt32".to_string()
+                        }
+                    }
+                    TypePosition::SharedStructField => "Unicode.Scalar".to_string(),
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => "UInt32".to_string(),
+                },
+                StdLibType::U128 => "RustU128".to_string(),
+                StdLibType::I128 => "RustI128".to_string(),
+                StdLibType::NonZero(non_zero) => non_zero.ty.to_swift_type(type_pos, types),
+                StdLibType::Duration => match type_pos {
+                    TypePosition::FnArg(func_host_lang)
+                    | TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            "TimeInterval".to_string()
+                        } else {
+                            "__private__FfiDuration".to_string()
+                        }
+                    }
+                    TypePosition::SharedStructField => "TimeInterval".to_string(),
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                        "__private__FfiDuration".to_string()
+                    }
+                },
+                StdLibType::SystemTime => match type_pos {
+                    TypePosition::FnArg(func_host_lang)
+                    | TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            "Date".to_string()
+                        } else {
+                            "__private__FfiSystemTime".to_string()
+                        }
+                    }
+                    TypePosition::SharedStructField => "Date".to_string(),
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                        "__private__FfiSystemTime".to_string()
+                    }
+                },
+                StdLibType::Path(path) => path.string_equivalent().to_swift_type(type_pos, types),
                 StdLibType::Pointer(ptr) => {
                     let maybe_mutable = match ptr.kind {
                         PointerKind::Const => "",
@@ -782,16 +1260,31 @@ impl BridgedType {
                     }
                 },
                 StdLibType::String => match type_pos {
-                    TypePosition::FnArg(_func_host_lang) => "GenericIntoRustString".to_string(),
-                    TypePosition::FnReturn(_func_host_lang) => "RustString".to_string(),
+                    TypePosition::FnArg(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            "GenericIntoRustString".to_string()
+                        } else {
+                            "UnsafeMutableRawPointer".to_string()
+                        }
+                    }
+                    TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            "RustString".to_string()
+                        } else {
+                            "UnsafeMutableRawPointer".to_string()
+                        }
+                    }
                     TypePosition::SharedStructField => "RustString".to_string(),
                     TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
                         "UnsafeMutableRawPointer?".to_string()
                     }
                 },
-                StdLibType::Vec(ty) => {
-                    format!("RustVec<{}>", ty.ty.to_swift_type(type_pos, types))
-                }
+                StdLibType::Vec(ty) => match type_pos {
+                    TypePosition::FnReturn(func_host_lang) if func_host_lang.is_swift() => {
+                        "UnsafeMutableRawPointer".to_string()
+                    }
+                    _ => format!("RustVec<{}>", ty.ty.to_swift_type(type_pos, types)),
+                },
                 StdLibType::Option(opt) => match type_pos {
                     TypePosition::FnArg(func_host_lang)
                     | TypePosition::FnReturn(func_host_lang) => {
@@ -808,6 +1301,60 @@ impl BridgedType {
                         unimplemented!()
                     }
                 },
+                StdLibType::Array(array) => match type_pos {
+                    TypePosition::FnArg(func_host_lang)
+                    | TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            array.to_swift_tuple_type(type_pos, types)
+                        } else {
+                            array.ffi_name_string()
+                        }
+                    }
+                    TypePosition::SharedStructField => array.to_swift_tuple_type(type_pos, types),
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => array.ffi_name_string(),
+                },
+                StdLibType::Iterator(iterator) => match type_pos {
+                    TypePosition::FnArg(func_host_lang)
+                    | TypePosition::FnReturn(func_host_lang) => {
+                        if func_host_lang.is_rust() {
+                            format!(
+                                "RustIterator<{}>",
+                                iterator.item.to_swift_type(type_pos, types)
+                            )
+                        } else {
+                            "UnsafeMutableRawPointer".to_string()
+                        }
+                    }
+                    TypePosition::SharedStructField => {
+                        todo!("Iterators in shared struct fields are not yet supported")
+                    }
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                        unimplemented!()
+                    }
+                },
+                StdLibType::Stream(stream) => match type_pos {
+                    TypePosition::FnReturn(HostLang::Rust) => {
+                        format!("RustStream<{}>", stream.item.to_swift_type(type_pos, types))
+                    }
+                    TypePosition::FnReturn(HostLang::Swift) => {
+                        "UnsafeMutableRawPointer".to_string()
+                    }
+                    _ => {
+                        todo!("Streams are only supported as the return type of Rust functions")
+                    }
+                },
+                // An async Rust function that returns `Result<T, E>` becomes an `async throws`
+                // Swift function that returns `T`.
+                StdLibType::Result(result) => {
+                    match type_pos {
+                        TypePosition::FnReturn(HostLang::Rust) => {
+                            result.ok_ty.to_swift_type(type_pos, types)
+                        }
+                        _ => {
+                            unreachable!("Result<T, E> is only supported as the return type of async functions")
+                        }
+                    }
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 match type_pos {
@@ -841,6 +1388,21 @@ impl BridgedType {
                     }
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => match type_pos {
+                TypePosition::FnArg(func_host_lang) | TypePosition::FnReturn(func_host_lang) => {
+                    if func_host_lang.is_rust() {
+                        trait_object.swift_existential()
+                    } else {
+                        "UnsafeMutableRawPointer".to_string()
+                    }
+                }
+                TypePosition::SharedStructField => {
+                    todo!("Trait objects in shared struct fields are not yet supported")
+                }
+                TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                    todo!("Async functions that return trait objects are not yet supported")
+                }
+            },
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.host_lang.is_rust() {
                     match type_pos {
@@ -875,7 +1437,11 @@ impl BridgedType {
                             unimplemented!()
                         }
                         TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
-                            unimplemented!()
+                            if opaque.has_swift_bridge_copy_annotation {
+                                todo!("Async functions that return opaque Copy types are not yet supported")
+                            }
+
+                            "UnsafeMutableRawPointer?".to_string()
                         }
                     }
                 } else {
@@ -917,6 +1483,13 @@ impl BridgedType {
                 StdLibType::Usize => "uintptr_t".to_string(),
                 StdLibType::Isize => "intptr_t".to_string(),
                 StdLibType::Bool => "bool".to_string(),
+                StdLibType::Char => "uint32_t".to_string(),
+                StdLibType::U128 => "struct RustU128".to_string(),
+                StdLibType::I128 => "struct RustI128".to_string(),
+                StdLibType::NonZero(non_zero) => non_zero.ty.to_c(),
+                StdLibType::Duration => "struct __private__FfiDuration".to_string(),
+                StdLibType::SystemTime => "struct __private__FfiSystemTime".to_string(),
+                StdLibType::Path(path) => path.string_equivalent().to_c(),
                 StdLibType::Pointer(ptr) => {
                     let maybe_const = match ptr.kind {
                         PointerKind::Const => " const ",
@@ -936,6 +1509,13 @@ impl BridgedType {
                 StdLibType::String => "void*".to_string(),
                 StdLibType::Vec(_) => "void*".to_string(),
                 StdLibType::Option(opt) => opt.to_c(),
+                StdLibType::Array(array) => format!("struct {}", array.ffi_name_string()),
+                StdLibType::Iterator(_) | StdLibType::Stream(_) => "void*".to_string(),
+                StdLibType::Result(_) => {
+                    unreachable!(
+                        "Result<T, E> is only supported as the return type of async functions"
+                    )
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 format!("struct {}", shared_struct.ffi_name_string())
@@ -943,6 +1523,7 @@ impl BridgedType {
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
                 format!("struct {}", shared_enum.ffi_name_string())
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => "void*".to_string(),
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.host_lang.is_rust() {
                     if opaque.has_swift_bridge_copy_annotation {
@@ -1030,6 +1611,27 @@ impl BridgedType {
                 | StdLibType::Bool => {
                     quote! { #expression }
                 }
+                StdLibType::Char => {
+                    quote! { u32::from( #expression ) }
+                }
+                StdLibType::U128 => {
+                    quote! { #swift_bridge_path::int128::RustU128::from_u128( #expression ) }
+                }
+                StdLibType::I128 => {
+                    quote! { #swift_bridge_path::int128::RustI128::from_i128( #expression ) }
+                }
+                StdLibType::NonZero(_) => {
+                    quote! { #expression.get() }
+                }
+                StdLibType::Duration => {
+                    quote! { #swift_bridge_path::time::FfiDuration::from_duration( #expression ) }
+                }
+                StdLibType::SystemTime => {
+                    quote! { #swift_bridge_path::time::FfiSystemTime::from_system_time( #expression ) }
+                }
+                StdLibType::Path(path) => {
+                    path.convert_rust_value_to_ffi_value(expression, swift_bridge_path)
+                }
                 StdLibType::Pointer(_) => {
                     quote! {
                         #expression
@@ -1056,6 +1658,31 @@ impl BridgedType {
                 StdLibType::Option(opt) => {
                     opt.convert_rust_value_to_ffi_value(expression, swift_bridge_path)
                 }
+                StdLibType::Array(array) => {
+                    if array.elements_need_conversion() {
+                        let convert_element = array.ty.convert_rust_value_to_ffi_compatible_value(
+                            &quote! { val },
+                            swift_bridge_path,
+                            types,
+                        );
+                        quote! {
+                            #swift_bridge_path::FfiArray::from_array( #expression.map(|val| #convert_element) )
+                        }
+                    } else {
+                        quote! {
+                            #swift_bridge_path::FfiArray::from_array( #expression )
+                        }
+                    }
+                }
+                StdLibType::Iterator(iterator) => {
+                    iterator.convert_rust_value_to_ffi_value(expression)
+                }
+                StdLibType::Stream(stream) => stream.convert_rust_value_to_ffi_value(expression),
+                StdLibType::Result(_) => {
+                    unreachable!(
+                        "Result<T, E> is only supported as the return type of async functions"
+                    )
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 quote! {
@@ -1067,6 +1694,9 @@ impl BridgedType {
                     #expression.into_ffi_repr()
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => {
+                trait_object.convert_rust_value_to_ffi_value(expression)
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let ty_name = &opaque.ty;
 
@@ -1128,6 +1758,36 @@ impl BridgedType {
                 | StdLibType::Bool => {
                     quote_spanned! {span=> #value }
                 }
+                StdLibType::Char => {
+                    quote_spanned! {span=>
+                        char::from_u32(#value).expect("Invalid Unicode scalar value")
+                    }
+                }
+                StdLibType::U128 => {
+                    quote_spanned! {span=> #value.into_u128() }
+                }
+                StdLibType::I128 => {
+                    quote_spanned! {span=> #value.into_i128() }
+                }
+                StdLibType::NonZero(non_zero) => {
+                    let ty = non_zero.to_rust_type_path();
+                    quote_spanned! {span=>
+                        match #ty::new(#value) {
+                            Some(val) => val,
+                            // Swift checks that the value isn't zero before passing it to Rust, so
+                            // this is unreachable. We abort since unwinding across the FFI
+                            // boundary is undefined behavior.
+                            None => std::process::abort(),
+                        }
+                    }
+                }
+                StdLibType::Duration => {
+                    quote_spanned! {span=> #value.into_duration() }
+                }
+                StdLibType::SystemTime => {
+                    quote_spanned! {span=> #value.into_system_time() }
+                }
+                StdLibType::Path(path) => path.convert_ffi_value_to_rust_value(value, span),
                 StdLibType::Pointer(_) => {
                     quote_spanned! {span=> #value }
                 }
@@ -1150,6 +1810,27 @@ impl BridgedType {
                 StdLibType::Option(bridged_option) => {
                     bridged_option.convert_ffi_value_to_rust_value(value)
                 }
+                StdLibType::Array(array) => {
+                    if array.elements_need_conversion() {
+                        let convert_element = array
+                            .ty
+                            .convert_ffi_value_to_rust_value(&quote! { val }, span);
+                        quote_spanned! {span=>
+                            #value.into_array().map(|val| #convert_element)
+                        }
+                    } else {
+                        quote_spanned! {span=> #value.into_array() }
+                    }
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Passing iterators from Swift to Rust is not yet supported")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Passing a Result from Swift to Rust is not yet supported")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Passing streams from Swift to Rust is not yet supported")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 quote_spanned! {span=>
@@ -1161,6 +1842,9 @@ impl BridgedType {
                     #value.into_rust_repr()
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => {
+                trait_object.convert_ffi_value_to_rust_value(value)
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.host_lang.is_rust() {
                     if opaque.has_swift_bridge_copy_annotation {
@@ -1235,7 +1919,16 @@ impl BridgedType {
                 | StdLibType::Isize
                 | StdLibType::F32
                 | StdLibType::F64
-                | StdLibType::Bool => value.to_string(),
+                | StdLibType::Bool
+                | StdLibType::U128
+                | StdLibType::I128
+                | StdLibType::NonZero(_) => value.to_string(),
+                StdLibType::Char => format!("Unicode.Scalar({})!", value),
+                StdLibType::Duration => format!("{}.toTimeInterval()", value),
+                StdLibType::SystemTime => format!("{}.toDate()", value),
+                StdLibType::Path(path) => path
+                    .string_equivalent()
+                    .convert_ffi_value_to_swift_value(value, type_pos, types),
                 StdLibType::Pointer(ptr) => match &ptr.pointee {
                     Pointee::BuiltIn(_) => value.to_string(),
                     Pointee::Void(_ty) => match ptr.kind {
@@ -1282,6 +1975,40 @@ impl BridgedType {
                     format!("RustVec(ptr: {})", value)
                 }
                 StdLibType::Option(opt) => opt.convert_ffi_expression_to_swift(value),
+                StdLibType::Array(array) => {
+                    if array.elements_need_conversion() {
+                        let elements: Vec<String> = (0..array.len)
+                            .map(|idx| {
+                                array.ty.convert_ffi_value_to_swift_value(
+                                    &format!("val.{}", idx),
+                                    type_pos,
+                                    types,
+                                )
+                            })
+                            .collect();
+
+                        format!(
+                            "{{ let val = {value}.array; return ({elements}) }}()",
+                            value = value,
+                            elements = elements.join(", ")
+                        )
+                    } else {
+                        format!("{}.array", value)
+                    }
+                }
+                // Swift needs to know the function's link name in order to call its generated
+                // `next` function, so this gets handled when generating the function.
+                StdLibType::Iterator(_) => {
+                    unreachable!("Use BuiltInIterator::convert_ffi_value_to_swift_value")
+                }
+                StdLibType::Stream(_) => {
+                    unreachable!("Use BuiltInStream::convert_ffi_value_to_swift_value")
+                }
+                StdLibType::Result(_) => {
+                    unreachable!(
+                        "Result<T, E> is only supported as the return type of async functions"
+                    )
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 format!("{}.intoSwiftRepr()", value)
@@ -1289,6 +2016,17 @@ impl BridgedType {
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(_shared_enum))) => {
                 format!("{}.intoSwiftRepr()", value)
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => match type_pos {
+                TypePosition::FnArg(_) | TypePosition::FnReturn(_) => {
+                    trait_object.convert_ffi_value_to_swift_value(value)
+                }
+                TypePosition::SharedStructField => {
+                    todo!("Trait objects in shared struct fields are not yet supported")
+                }
+                TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                    todo!("Async functions that return trait objects are not yet supported")
+                }
+            },
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let mut ty_name = opaque.ty.to_string();
 
@@ -1306,6 +2044,8 @@ impl BridgedType {
                             ty_name = ty_name,
                             value = value,
                         )
+                    } else if let TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy = type_pos {
+                        format!("{ty_name}(ptr: {value}!)", ty_name = ty_name, value = value,)
                     } else {
                         format!("{ty_name}(ptr: {value})", ty_name = ty_name, value = value,)
                     }
@@ -1347,7 +2087,19 @@ impl BridgedType {
                 | StdLibType::Isize
                 | StdLibType::F32
                 | StdLibType::F64
-                | StdLibType::Bool => value.to_string(),
+                | StdLibType::Bool
+                | StdLibType::U128
+                | StdLibType::I128 => value.to_string(),
+                StdLibType::NonZero(_) => format!(
+                    r#"{{ let val = {}; precondition(val != 0, "NonZero integers cannot be zero"); return val }}()"#,
+                    value
+                ),
+                StdLibType::Char => format!("{}.value", value),
+                StdLibType::Duration => format!("{}.toFfiDuration()", value),
+                StdLibType::SystemTime => format!("{}.toFfiSystemTime()", value),
+                StdLibType::Path(path) => path
+                    .string_equivalent()
+                    .convert_swift_expression_to_ffi_compatible(value, type_pos),
                 StdLibType::RefSlice(_) => {
                     format!("{}.toFfiSlice()", value)
                 }
@@ -1401,6 +2153,38 @@ impl BridgedType {
                 StdLibType::Option(option) => {
                     option.convert_swift_expression_to_ffi_compatible(value, type_pos)
                 }
+                StdLibType::Array(array) => {
+                    let ffi_name = array.ffi_name_string();
+
+                    if array.elements_need_conversion() {
+                        let elements: Vec<String> = (0..array.len)
+                            .map(|idx| {
+                                array.ty.convert_swift_expression_to_ffi_compatible(
+                                    &format!("val.{}", idx),
+                                    type_pos,
+                                )
+                            })
+                            .collect();
+
+                        format!(
+                            "{{ let val = {value}; return {ffi_name}(array: ({elements})) }}()",
+                            value = value,
+                            ffi_name = ffi_name,
+                            elements = elements.join(", ")
+                        )
+                    } else {
+                        format!("{}(array: {})", ffi_name, value)
+                    }
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Passing iterators from Swift to Rust is not yet supported")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Passing a Result from Swift to Rust is not yet supported")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Passing streams from Swift to Rust is not yet supported")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 format!("{}.intoFfiRepr()", value)
@@ -1408,6 +2192,17 @@ impl BridgedType {
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(_shared_enum))) => {
                 format!("{}.intoFfiRepr()", value)
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(trait_object)) => match type_pos {
+                TypePosition::FnArg(_) | TypePosition::FnReturn(_) => {
+                    trait_object.convert_swift_expression_to_ffi_value(value)
+                }
+                TypePosition::SharedStructField => {
+                    todo!("Trait objects in shared struct fields are not yet supported")
+                }
+                TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                    unimplemented!()
+                }
+            },
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let ty_name = &opaque.ty;
 
@@ -1482,14 +2277,21 @@ impl BridgedType {
                 | StdLibType::U64
                 | StdLibType::I64
                 | StdLibType::Usize
-                | StdLibType::Isize => Some("stdint.h"),
+                | StdLibType::Isize
+                | StdLibType::Char => Some("stdint.h"),
                 StdLibType::Bool => Some("stdbool.h"),
+                StdLibType::NonZero(non_zero) => non_zero.ty.c_include(),
                 StdLibType::Pointer(ptr) => match &ptr.pointee {
                     Pointee::BuiltIn(ty) => ty.c_include(),
                     Pointee::Void(_) => None,
                 },
                 StdLibType::RefSlice(slice) => slice.ty.c_include(),
                 StdLibType::Vec(_vec) => Some("stdint.h"),
+                StdLibType::Array(array) => array.ty.c_include(),
+                StdLibType::Option(opt) => match opt.ty.deref() {
+                    BridgedType::StdLib(StdLibType::NonZero(non_zero)) => non_zero.ty.c_include(),
+                    _ => None,
+                },
                 _ => None,
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
@@ -1501,6 +2303,7 @@ impl BridgedType {
                 None
             }
             BridgedType::Foreign(CustomBridgedType::Opaque(_opaque)) => None,
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => None,
         }
     }
 
@@ -1534,6 +2337,33 @@ impl BridgedType {
                     rust: quote! { bool },
                     swift: "bool".into(),
                 },
+                StdLibType::Char => UnusedOptionNoneValue {
+                    rust: quote! { 123 },
+                    swift: "123".into(),
+                },
+                StdLibType::U128 => UnusedOptionNoneValue {
+                    rust: quote! { #swift_bridge_path::int128::RustU128 { low: 123, high: 0 } },
+                    swift: "123".into(),
+                },
+                StdLibType::I128 => UnusedOptionNoneValue {
+                    rust: quote! { #swift_bridge_path::int128::RustI128 { low: 123, high: 0 } },
+                    swift: "123".into(),
+                },
+                StdLibType::NonZero(_) => UnusedOptionNoneValue {
+                    rust: quote! { 0 },
+                    swift: "0".into(),
+                },
+                StdLibType::Duration => UnusedOptionNoneValue {
+                    rust: quote! { #swift_bridge_path::time::FfiDuration { secs: 0, nanos: 0 } },
+                    swift: "__private__FfiDuration()".into(),
+                },
+                StdLibType::SystemTime => UnusedOptionNoneValue {
+                    rust: quote! { #swift_bridge_path::time::FfiSystemTime { secs: 0, nanos: 0 } },
+                    swift: "__private__FfiSystemTime()".into(),
+                },
+                StdLibType::Path(path) => path
+                    .string_equivalent()
+                    .rust_unused_option_none_val(swift_bridge_path),
                 StdLibType::Pointer(_) => {
                     todo!("Support Option<*const T> and Option<*mut T>")
                 }
@@ -1568,6 +2398,18 @@ impl BridgedType {
                 StdLibType::Option(_) => {
                     todo!("Support nested Option<Option<T>>")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Support Option<[T; N]>")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 let option_name = shared_struct.ffi_option_name_tokens();
@@ -1583,6 +2425,9 @@ impl BridgedType {
                     swift: "TODO..Support Swift Option<Enum>::None value".into(),
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 let ty_name = &opaque.ty;
 
@@ -1598,12 +2443,37 @@ impl BridgedType {
         }
     }
 
+    /// The Swift module that needs to be imported in order to use this type on the Swift side.
+    ///
+    /// Duration -> Some("Foundation") since we use `TimeInterval`
+    pub fn swift_import(&self) -> Option<&'static str> {
+        match self {
+            BridgedType::StdLib(stdlib_type) => match stdlib_type {
+                StdLibType::Duration | StdLibType::SystemTime => Some("Foundation"),
+                StdLibType::Option(opt) => opt.ty.swift_import(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether or not this type is passed from Swift to Rust as a `RustStr`.
+    /// i.e. `&str` or `&Path`.
+    pub fn is_passed_as_rust_str(&self) -> bool {
+        match self {
+            BridgedType::StdLib(StdLibType::Str) => true,
+            BridgedType::StdLib(StdLibType::Path(path)) => !path.is_owned(),
+            _ => false,
+        }
+    }
+
     /// Whether or not the type is a `String`, or a type that contains an owned String such as
     /// `Option<String>` or `struct Foo { field: String } `
     pub fn contains_owned_string_recursive(&self) -> bool {
         match self {
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::String => true,
+                StdLibType::Path(path) => path.is_owned(),
                 StdLibType::Vec(inner) => inner.ty.contains_owned_string_recursive(),
                 StdLibType::Option(inner) => inner.ty.contains_owned_string_recursive(),
                 _ => false,
@@ -1617,6 +2487,7 @@ impl BridgedType {
                 false
             }
             BridgedType::Foreign(CustomBridgedType::Opaque(_)) => false,
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => false,
         }
     }
 
@@ -1626,6 +2497,7 @@ impl BridgedType {
         match self {
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::Str => true,
+                StdLibType::Path(path) => !path.is_owned(),
                 StdLibType::Vec(inner) => inner.ty.contains_ref_string_recursive(),
                 StdLibType::Option(inner) => inner.ty.contains_ref_string_recursive(),
                 _ => false,
@@ -1639,6 +2511,29 @@ impl BridgedType {
                 false
             }
             BridgedType::Foreign(CustomBridgedType::Opaque(_)) => false,
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => false,
+        }
+    }
+
+    /// The types that this type wraps, such as the `T` in `Option<T>` or the `T` and `E` in
+    /// `Result<T, E>`.
+    pub fn inner_types(&self) -> Vec<&BridgedType> {
+        match self {
+            BridgedType::StdLib(stdlib_type) => match stdlib_type {
+                StdLibType::Vec(inner) => vec![&inner.ty],
+                StdLibType::Option(inner) => vec![&inner.ty],
+                StdLibType::RefSlice(inner) => vec![&inner.ty],
+                StdLibType::Array(inner) => vec![&inner.ty],
+                StdLibType::Iterator(inner) => vec![&inner.item],
+                StdLibType::Stream(inner) => vec![&inner.item],
+                StdLibType::Result(inner) => vec![&inner.ok_ty, &inner.err_ty],
+                StdLibType::Pointer(BuiltInPointer {
+                    pointee: Pointee::BuiltIn(pointee),
+                    ..
+                }) => vec![pointee],
+                _ => vec![],
+            },
+            BridgedType::Foreign(_) => vec![],
         }
     }
 
@@ -1702,7 +2597,37 @@ mod tests {
             (quote! {isize}, StdLibType::Isize),
             (quote! {f32}, StdLibType::F32),
             (quote! {f64}, StdLibType::F64),
+            (quote! {u128}, StdLibType::U128),
+            (quote! {i128}, StdLibType::I128),
+            (quote! {char}, StdLibType::Char),
+            (
+                quote! {NonZeroU32},
+                StdLibType::NonZero(BuiltInNonZero {
+                    ty: Box::new(BridgedType::StdLib(StdLibType::U32)),
+                }),
+            ),
+            (
+                quote! {std::num::NonZeroI64},
+                StdLibType::NonZero(BuiltInNonZero {
+                    ty: Box::new(BridgedType::StdLib(StdLibType::I64)),
+                }),
+            ),
+            (quote! {Duration}, StdLibType::Duration),
+            (quote! {std::time::Duration}, StdLibType::Duration),
+            (quote! {SystemTime}, StdLibType::SystemTime),
+            (quote! {std::time::SystemTime}, StdLibType::SystemTime),
             (quote! {&str}, StdLibType::Str),
+            (quote! {&Path}, StdLibType::Path(BuiltInPath::RefPath)),
+            (
+                quote! {&std::path::Path},
+                StdLibType::Path(BuiltInPath::RefPath),
+            ),
+            (quote! {PathBuf}, StdLibType::Path(BuiltInPath::PathBuf)),
+            (quote! {&OsStr}, StdLibType::Path(BuiltInPath::RefOsStr)),
+            (
+                quote! {std::ffi::OsString},
+                StdLibType::Path(BuiltInPath::OsString),
+            ),
             (quote! {String}, StdLibType::String),
             (
                 quote! { Vec<u32>},
@@ -1716,6 +2641,13 @@ mod tests {
                     ty: Box::new(BridgedType::StdLib(StdLibType::U32)),
                 }),
             ),
+            (
+                quote! { [u8; 32]},
+                StdLibType::Array(BuiltInArray {
+                    ty: Box::new(BridgedType::StdLib(StdLibType::U8)),
+                    len: 32,
+                }),
+            ),
             (
                 quote! {*const u8},
                 StdLibType::Pointer(BuiltInPointer {
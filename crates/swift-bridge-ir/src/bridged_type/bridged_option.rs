@@ -1,5 +1,5 @@
 use crate::bridged_type::{BridgedType, CustomBridgedType, SharedType, StdLibType, TypePosition};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::ops::Deref;
 use syn::Path;
@@ -11,6 +11,22 @@ pub(crate) struct BridgedOption {
 }
 
 impl BridgedOption {
+    /// Whether or not we support wrapping the inner type in an `Option`.
+    ///
+    /// `Option<[T; N]>`, `Option<impl Iterator<Item = T>>`, `Option<Result<T, E>>`,
+    /// `Option<impl Stream<Item = T>>` and `Option<Box<dyn Trait>>` are not yet supported.
+    pub(crate) fn supports_inner_type(&self) -> bool {
+        !matches!(
+            self.ty.deref(),
+            BridgedType::StdLib(
+                StdLibType::Array(_)
+                    | StdLibType::Iterator(_)
+                    | StdLibType::Result(_)
+                    | StdLibType::Stream(_)
+            ) | BridgedType::Foreign(CustomBridgedType::TraitObject(_))
+        )
+    }
+
     pub(super) fn convert_rust_value_to_ffi_value(
         &self,
         expression: &TokenStream,
@@ -73,6 +89,79 @@ impl BridgedOption {
                 StdLibType::Bool => {
                     option_rust_primitive_to_ffi_primitive(quote! {OptionBool}, quote! {false})
                 }
+                StdLibType::Char => {
+                    quote! {
+                        if let Some(val) = #expression {
+                            #swift_bridge_path::option::OptionU32 { val: u32::from(val), is_some: true }
+                        } else {
+                            #swift_bridge_path::option::OptionU32 { val: #unused_none_value, is_some: false }
+                        }
+                    }
+                }
+                StdLibType::U128 => {
+                    quote! {
+                        if let Some(val) = #expression {
+                            #swift_bridge_path::option::OptionU128 {
+                                val: #swift_bridge_path::int128::RustU128::from_u128(val),
+                                is_some: true
+                            }
+                        } else {
+                            #swift_bridge_path::option::OptionU128 { val: #unused_none_value, is_some: false }
+                        }
+                    }
+                }
+                StdLibType::I128 => {
+                    quote! {
+                        if let Some(val) = #expression {
+                            #swift_bridge_path::option::OptionI128 {
+                                val: #swift_bridge_path::int128::RustI128::from_i128(val),
+                                is_some: true
+                            }
+                        } else {
+                            #swift_bridge_path::option::OptionI128 { val: #unused_none_value, is_some: false }
+                        }
+                    }
+                }
+                StdLibType::NonZero(_) => {
+                    quote! {
+                        if let Some(val) = #expression { val.get() } else { #unused_none_value }
+                    }
+                }
+                StdLibType::Duration => {
+                    quote! {
+                        if let Some(val) = #expression {
+                            #swift_bridge_path::option::OptionDuration {
+                                val: #swift_bridge_path::time::FfiDuration::from_duration(val),
+                                is_some: true
+                            }
+                        } else {
+                            #swift_bridge_path::option::OptionDuration { val: #unused_none_value, is_some: false }
+                        }
+                    }
+                }
+                StdLibType::SystemTime => {
+                    quote! {
+                        if let Some(val) = #expression {
+                            #swift_bridge_path::option::OptionSystemTime {
+                                val: #swift_bridge_path::time::FfiSystemTime::from_system_time(val),
+                                is_some: true
+                            }
+                        } else {
+                            #swift_bridge_path::option::OptionSystemTime { val: #unused_none_value, is_some: false }
+                        }
+                    }
+                }
+                StdLibType::Path(path) => {
+                    let convert_path =
+                        path.convert_rust_value_to_ffi_value(&quote! { val }, swift_bridge_path);
+                    quote! {
+                        if let Some(val) = #expression {
+                            #convert_path
+                        } else {
+                            #unused_none_value
+                        }
+                    }
+                }
                 StdLibType::Pointer(_) => {
                     todo!("Support Option<*const T> and Option<*mut T>")
                 }
@@ -103,6 +192,18 @@ impl BridgedOption {
                 StdLibType::Option(_) => {
                     todo!("Support Option<Option<T>>")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Support Option<[T; N]>")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 let option_name = shared_struct.ffi_option_name_tokens();
@@ -116,6 +217,9 @@ impl BridgedOption {
                     #option_name::from_rust_repr(#expression)
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.has_swift_bridge_copy_annotation {
                     let copy_repr = opaque.copy_rust_repr_type();
@@ -168,6 +272,43 @@ impl BridgedOption {
                 | StdLibType::Bool => {
                     quote! { if #value.is_some { Some(#value.val) } else { None } }
                 }
+                StdLibType::Char => {
+                    quote! {
+                        if #value.is_some {
+                            Some(char::from_u32(#value.val).expect("Invalid Unicode scalar value"))
+                        } else {
+                            None
+                        }
+                    }
+                }
+                StdLibType::U128 => {
+                    quote! { if #value.is_some { Some(#value.val.into_u128()) } else { None } }
+                }
+                StdLibType::I128 => {
+                    quote! { if #value.is_some { Some(#value.val.into_i128()) } else { None } }
+                }
+                StdLibType::NonZero(non_zero) => {
+                    let ty = non_zero.to_rust_type_path();
+                    quote! { #ty::new(#value) }
+                }
+                StdLibType::Duration => {
+                    quote! { if #value.is_some { Some(#value.val.into_duration()) } else { None } }
+                }
+                StdLibType::SystemTime => {
+                    quote! { if #value.is_some { Some(#value.val.into_system_time()) } else { None } }
+                }
+                StdLibType::Path(path) => {
+                    let convert_path =
+                        path.convert_ffi_value_to_rust_value(value, Span::call_site());
+                    let is_none = if path.is_owned() {
+                        quote! { #value.is_null() }
+                    } else {
+                        quote! { #value.start.is_null() }
+                    };
+                    quote! {
+                        if #is_none { None } else { Some(#convert_path) }
+                    }
+                }
                 StdLibType::Pointer(_) => {
                     todo!("Option<*const T> and Option<*mut T> are not yet supported.")
                 }
@@ -194,6 +335,18 @@ impl BridgedOption {
                 StdLibType::Option(_) => {
                     todo!("Option<Option<T>> is not yet supported")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Option<[T; N]> is not yet supported")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 quote! {
@@ -205,6 +358,9 @@ impl BridgedOption {
                     #value.into_rust_repr()
                 }
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.has_swift_bridge_copy_annotation {
                     quote! {
@@ -245,9 +401,27 @@ impl BridgedOption {
                 | StdLibType::Isize
                 | StdLibType::F32
                 | StdLibType::F64
-                | StdLibType::Bool => {
+                | StdLibType::Bool
+                | StdLibType::U128
+                | StdLibType::I128 => {
                     format!("{{ let val = {expression}; if val.is_some {{ return val.val }} else {{ return nil }} }}()", expression = expression)
                 }
+                StdLibType::Char => {
+                    format!("{{ let val = {expression}; if val.is_some {{ return Unicode.Scalar(val.val)! }} else {{ return nil }} }}()", expression = expression)
+                }
+                StdLibType::NonZero(_) => {
+                    format!("{{ let val = {expression}; if val != 0 {{ return val }} else {{ return nil }} }}()", expression = expression)
+                }
+                StdLibType::Duration => {
+                    format!("{{ let val = {expression}; if val.is_some {{ return val.val.toTimeInterval() }} else {{ return nil }} }}()", expression = expression)
+                }
+                StdLibType::SystemTime => {
+                    format!("{{ let val = {expression}; if val.is_some {{ return val.val.toDate() }} else {{ return nil }} }}()", expression = expression)
+                }
+                StdLibType::Path(path) => BridgedOption {
+                    ty: Box::new(path.string_equivalent()),
+                }
+                .convert_ffi_expression_to_swift(expression),
                 StdLibType::Pointer(_) => {
                     todo!("Support Option<*const T> and Option<*mut T>")
                 }
@@ -269,6 +443,18 @@ impl BridgedOption {
                 StdLibType::Option(_) => {
                     todo!("Support Option<Option<T>>")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Support Option<[T; N]>")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 format!("{expression}.intoSwiftRepr()", expression = expression)
@@ -276,6 +462,9 @@ impl BridgedOption {
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(_shared_enum))) => {
                 format!("{expression}.intoSwiftRepr()", expression = expression)
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.has_swift_bridge_copy_annotation {
                     let type_name = opaque.swift_name();
@@ -328,6 +517,33 @@ impl BridgedOption {
                 StdLibType::F32 => convert_primitive("F32", "123.4"),
                 StdLibType::F64 => convert_primitive("F64", "123.4"),
                 StdLibType::Bool => convert_primitive("Bool", "false"),
+                StdLibType::Char => {
+                    format!(
+                        "{{ let val = {expression}; return __private__OptionU32(val: val?.value ?? 123, is_some: val != nil); }}()",
+                        expression = expression
+                    )
+                }
+                StdLibType::U128 => convert_primitive("U128", "123"),
+                StdLibType::I128 => convert_primitive("I128", "123"),
+                StdLibType::NonZero(_) => {
+                    format!("{expression} ?? 0", expression = expression)
+                }
+                StdLibType::Duration => {
+                    format!(
+                        "{{ let val = {expression}; return __private__OptionDuration(val: val?.toFfiDuration() ?? __private__FfiDuration(), is_some: val != nil); }}()",
+                        expression = expression
+                    )
+                }
+                StdLibType::SystemTime => {
+                    format!(
+                        "{{ let val = {expression}; return __private__OptionSystemTime(val: val?.toFfiSystemTime() ?? __private__FfiSystemTime(), is_some: val != nil); }}()",
+                        expression = expression
+                    )
+                }
+                StdLibType::Path(path) => BridgedOption {
+                    ty: Box::new(path.string_equivalent()),
+                }
+                .convert_swift_expression_to_ffi_compatible(expression, type_pos),
                 StdLibType::Pointer(_) => {
                     todo!("Option<*const T> and Option<*mut T> are not yet supported")
                 }
@@ -360,6 +576,18 @@ impl BridgedOption {
                 StdLibType::Option(_) => {
                     todo!("Option<Option<T> is not yet supported")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Option<[T; N]> is not yet supported")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 let ffi_name = shared_struct.ffi_option_name_string();
@@ -377,6 +605,9 @@ impl BridgedOption {
                     expression = expression
                 )
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.has_swift_bridge_copy_annotation {
                     let ffi_repr = opaque.copy_ffi_repr_type_string();
@@ -414,6 +645,16 @@ impl BridgedOption {
                 StdLibType::F32 => "struct __private__OptionF32".to_string(),
                 StdLibType::F64 => "struct __private__OptionF64".to_string(),
                 StdLibType::Bool => "struct __private__OptionBool".to_string(),
+                StdLibType::Char => "struct __private__OptionU32".to_string(),
+                StdLibType::U128 => "struct __private__OptionU128".to_string(),
+                StdLibType::I128 => "struct __private__OptionI128".to_string(),
+                StdLibType::NonZero(non_zero) => non_zero.ty.to_c(),
+                StdLibType::Duration => "struct __private__OptionDuration".to_string(),
+                StdLibType::SystemTime => "struct __private__OptionSystemTime".to_string(),
+                StdLibType::Path(path) => BridgedOption {
+                    ty: Box::new(path.string_equivalent()),
+                }
+                .to_c(),
                 StdLibType::Pointer(_) => {
                     todo!("Option<*const T> and Option<*mut T> are not yet supported")
                 }
@@ -428,6 +669,18 @@ impl BridgedOption {
                 StdLibType::Option(_) => {
                     todo!("Option<Option<T>> is not yet supported")
                 }
+                StdLibType::Array(_) => {
+                    todo!("Option<[T; N]> is not yet supported")
+                }
+                StdLibType::Iterator(_) => {
+                    todo!("Support Option<impl Iterator<Item = T>>")
+                }
+                StdLibType::Result(_) => {
+                    todo!("Support Option<Result<T, E>>")
+                }
+                StdLibType::Stream(_) => {
+                    todo!("Support Option<impl Stream<Item = T>>")
+                }
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
                 format!("struct {}", shared_struct.ffi_option_name_string())
@@ -435,6 +688,9 @@ impl BridgedOption {
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
                 format!("struct {}", shared_enum.ffi_option_name_string())
             }
+            BridgedType::Foreign(CustomBridgedType::TraitObject(_)) => {
+                todo!("Option<Box<dyn Trait>> is not yet supported")
+            }
             BridgedType::Foreign(CustomBridgedType::Opaque(opaque)) => {
                 if opaque.has_swift_bridge_copy_annotation {
                     opaque.option_copy_ffi_repr_type_string()
@@ -0,0 +1,91 @@
+use crate::parse::TypeDeclarations;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type, TypeParamBound, TypePath};
+
+/// `Box<dyn SomeTrait>`, where `SomeTrait` was declared in the bridge module.
+///
+/// The trait object gets boxed a second time, since `Box<dyn SomeTrait>` is a fat pointer, and
+/// is passed over FFI as a thin pointer. Swift wraps that pointer in a `RustSomeTrait` class that
+/// conforms to the `SomeTrait` protocol.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BridgedTraitObject {
+    pub trait_name: Ident,
+}
+
+impl BridgedTraitObject {
+    /// Box<dyn SomeTrait>
+    pub(super) fn new_with_type_path(path: &TypePath, types: &TypeDeclarations) -> Option<Self> {
+        let last = path.path.segments.last()?;
+        if last.ident != "Box" {
+            return None;
+        }
+
+        let trait_object = match &last.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(Type::TraitObject(trait_object)) => trait_object,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if trait_object.bounds.len() != 1 {
+            return None;
+        }
+
+        let trait_path = match &trait_object.bounds[0] {
+            TypeParamBound::Trait(trait_bound) => &trait_bound.path,
+            TypeParamBound::Lifetime(_) => return None,
+        };
+        let trait_name = trait_path.get_ident()?;
+
+        types.get_trait(&trait_name.to_string())?;
+
+        Some(BridgedTraitObject {
+            trait_name: trait_name.clone(),
+        })
+    }
+
+    pub(crate) fn to_rust_type_path(&self) -> TokenStream {
+        let trait_name = &self.trait_name;
+        quote! { Box<dyn #trait_name> }
+    }
+
+    pub(super) fn convert_rust_value_to_ffi_value(&self, expression: &TokenStream) -> TokenStream {
+        quote! {
+            Box::into_raw(Box::new( #expression )) as *mut std::ffi::c_void
+        }
+    }
+
+    pub(super) fn convert_ffi_value_to_rust_value(&self, value: &TokenStream) -> TokenStream {
+        let boxed_trait_object = self.to_rust_type_path();
+        quote! {
+            unsafe { *Box::from_raw( #value as *mut #boxed_trait_object ) }
+        }
+    }
+
+    /// The Swift class that wraps a pointer to a Rust trait object.
+    ///
+    /// For `trait SomeTrait` this would be "RustSomeTrait".
+    pub(crate) fn swift_class_name(&self) -> String {
+        format!("Rust{}", self.trait_name)
+    }
+
+    /// The existential that Swift functions use in place of `Box<dyn SomeTrait>`.
+    ///
+    /// For `trait SomeTrait` this would be "any SomeTrait".
+    pub(crate) fn swift_existential(&self) -> String {
+        format!("any {}", self.trait_name)
+    }
+
+    /// Wrap a pointer to a Rust trait object in the Swift class that conforms to the trait's
+    /// protocol.
+    pub(super) fn convert_ffi_value_to_swift_value(&self, value: &str) -> String {
+        format!("{}(ptr: {})", self.swift_class_name(), value)
+    }
+
+    /// Give up ownership of a value that conforms to the trait's protocol and get a pointer to a
+    /// Rust trait object.
+    pub(super) fn convert_swift_expression_to_ffi_value(&self, value: &str) -> String {
+        format!("{}.intoFfiRepr({})", self.swift_class_name(), value)
+    }
+}
@@ -0,0 +1,230 @@
+use crate::bridged_type::{BridgedOption, BridgedType, StdLibType, TypePosition};
+use crate::parse::{HostLang, TypeDeclarations};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{GenericArgument, Path, PathArguments, Token, Type, TypeParamBound, TypePath};
+
+/// `impl Iterator<Item = T>` or `Box<dyn Iterator<Item = T>>`
+///
+/// The iterator gets boxed up and passed to Swift as a pointer. Swift wraps that pointer in a
+/// `RustIterator<T>`, which pulls items one at a time by calling the function's generated
+/// `next` extern.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BuiltInIterator {
+    pub item: Box<BridgedType>,
+    /// `Box<dyn Iterator<Item = T>>` as opposed to `impl Iterator<Item = T>`
+    pub boxed: bool,
+}
+
+impl BuiltInIterator {
+    /// impl Iterator<Item = u8>
+    pub(super) fn new_with_impl_trait(
+        bounds: &Punctuated<TypeParamBound, Token![+]>,
+        types: &TypeDeclarations,
+    ) -> Option<Self> {
+        let item = iterator_item(bounds)?;
+
+        Some(BuiltInIterator {
+            item: Box::new(BridgedType::new_with_type(item, types)?),
+            boxed: false,
+        })
+    }
+
+    /// Box<dyn Iterator<Item = u8>>
+    pub(super) fn new_with_type_path(path: &TypePath, types: &TypeDeclarations) -> Option<Self> {
+        let last = path.path.segments.last()?;
+        if last.ident != "Box" {
+            return None;
+        }
+
+        let trait_object = match &last.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(Type::TraitObject(trait_object)) => trait_object,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let item = iterator_item(&trait_object.bounds)?;
+
+        Some(BuiltInIterator {
+            item: Box::new(BridgedType::new_with_type(item, types)?),
+            boxed: true,
+        })
+    }
+
+    pub(super) fn to_rust_type_path(&self) -> TokenStream {
+        let item = self.item.to_rust_type_path();
+
+        if self.boxed {
+            quote! { Box<dyn Iterator<Item = #item>> }
+        } else {
+            quote! { impl Iterator<Item = #item> }
+        }
+    }
+
+    /// The type that the iterator's pointer gets cast to in the `next` and `_free` functions.
+    fn boxed_iterator_type(&self) -> TokenStream {
+        let item = self.item.to_rust_type_path();
+        quote! { Box<dyn Iterator<Item = #item>> }
+    }
+
+    /// `next` returns an `Option<T>`.
+    fn next_return_type(&self) -> BridgedType {
+        BridgedType::StdLib(StdLibType::Option(BridgedOption {
+            ty: self.item.clone(),
+        }))
+    }
+
+    // We box the iterator a second time since `Box<dyn Iterator>` is a fat pointer.
+    pub(super) fn convert_rust_value_to_ffi_value(&self, expression: &TokenStream) -> TokenStream {
+        if self.boxed {
+            quote! {
+                Box::into_raw(Box::new( #expression )) as *mut std::ffi::c_void
+            }
+        } else {
+            let boxed_iterator = self.boxed_iterator_type();
+            quote! {
+                Box::into_raw(Box::new(Box::new( #expression ) as #boxed_iterator)) as *mut std::ffi::c_void
+            }
+        }
+    }
+
+    /// Generates the `next` and `_free` functions that Swift uses to iterate over and then free
+    /// the iterator that gets returned by the function with the given link name.
+    ///
+    /// ```no_rust,ignore
+    /// #[export_name = "__swift_bridge__$some_function$next"]
+    /// pub extern "C" fn __swift_bridge__some_function_next(
+    ///     iterator: *mut std::ffi::c_void
+    /// ) -> swift_bridge::option::OptionU8 { ... }
+    ///
+    /// #[export_name = "__swift_bridge__$some_function$_free"]
+    /// pub extern "C" fn __swift_bridge__some_function__free(
+    ///     iterator: *mut std::ffi::c_void
+    /// ) { ... }
+    /// ```
+    pub(crate) fn generate_next_and_free_functions(
+        &self,
+        link_name: &str,
+        prefixed_fn_name: &Ident,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let next_link_name = next_link_name(link_name);
+        let free_link_name = free_link_name(link_name);
+
+        let next_fn_name = Ident::new(
+            &format!("{}_next", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+        let free_fn_name = Ident::new(
+            &format!("{}__free", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+
+        let boxed_iterator = self.boxed_iterator_type();
+
+        let next_return_type = self.next_return_type();
+        let next_ret = next_return_type.to_ffi_compatible_rust_type(swift_bridge_path, types);
+        let next_val = next_return_type.convert_rust_value_to_ffi_compatible_value(
+            &quote! { iterator.next() },
+            swift_bridge_path,
+            types,
+        );
+
+        quote! {
+            #[export_name = #next_link_name]
+            pub extern "C" fn #next_fn_name (iterator: *mut std::ffi::c_void) -> #next_ret {
+                let iterator = unsafe { &mut *(iterator as *mut #boxed_iterator) };
+                #next_val
+            }
+
+            #[export_name = #free_link_name]
+            pub extern "C" fn #free_fn_name (iterator: *mut std::ffi::c_void) {
+                let iterator = unsafe { Box::from_raw(iterator as *mut #boxed_iterator) };
+                drop(iterator);
+            }
+        }
+    }
+
+    /// The C declarations for the function's `next` and `_free` functions.
+    pub(crate) fn c_declarations(&self, link_name: &str) -> String {
+        format!(
+            r#"{next_ret} {next}(void* iterator);
+void {free}(void* iterator);
+"#,
+            next_ret = self.next_return_type().to_c(),
+            next = next_link_name(link_name),
+            free = free_link_name(link_name),
+        )
+    }
+
+    /// Any C header that the `next` function's return type needs.
+    pub(crate) fn c_include(&self) -> Option<&'static str> {
+        self.next_return_type().c_include()
+    }
+
+    /// Wrap the pointer that a Rust function returned in a `RustIterator`.
+    ///
+    /// The `owners` are kept alive for as long as the iterator, since the iterator might be
+    /// borrowing from them. i.e. `fn rows(&self) -> impl Iterator<Item = Row> + '_`.
+    pub(crate) fn convert_ffi_value_to_swift_value(
+        &self,
+        value: &str,
+        link_name: &str,
+        owners: &str,
+        types: &TypeDeclarations,
+    ) -> String {
+        let next_val = self.next_return_type().convert_ffi_value_to_swift_value(
+            &format!("{}(iterator)", next_link_name(link_name)),
+            TypePosition::FnReturn(HostLang::Rust),
+            types,
+        );
+
+        format!(
+            "RustIterator(ptr: {value}, owners: {owners}, next: {{ iterator in {next_val} }}, free: {{ iterator in {free}(iterator) }})",
+            value = value,
+            owners = owners,
+            next_val = next_val,
+            free = free_link_name(link_name),
+        )
+    }
+}
+
+/// `Iterator<Item = u8> + '_` -> `u8`
+fn iterator_item(bounds: &Punctuated<TypeParamBound, Token![+]>) -> Option<&Type> {
+    for bound in bounds {
+        let trait_bound = match bound {
+            TypeParamBound::Trait(trait_bound) => trait_bound,
+            TypeParamBound::Lifetime(_) => continue,
+        };
+
+        let last = trait_bound.path.segments.last()?;
+        if last.ident != "Iterator" {
+            continue;
+        }
+
+        if let PathArguments::AngleBracketed(args) = &last.arguments {
+            for arg in args.args.iter() {
+                if let GenericArgument::Binding(binding) = arg {
+                    if binding.ident == "Item" {
+                        return Some(&binding.ty);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// __swift_bridge__$some_function -> __swift_bridge__$some_function$next
+fn next_link_name(link_name: &str) -> String {
+    format!("{}$next", link_name)
+}
+
+/// __swift_bridge__$some_function -> __swift_bridge__$some_function$_free
+fn free_link_name(link_name: &str) -> String {
+    format!("{}$_free", link_name)
+}
@@ -0,0 +1,47 @@
+use crate::bridged_type::BridgedType;
+use crate::parse::TypeDeclarations;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{GenericArgument, PathArguments, TypePath};
+
+/// `Result<T, E>`
+///
+/// Currently only supported as the return type of an async Rust function. The `Ok` and `Err`
+/// values get passed to separate Swift callbacks, and the Swift function throws the error.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BuiltInResult {
+    pub ok_ty: Box<BridgedType>,
+    pub err_ty: Box<BridgedType>,
+}
+
+impl BuiltInResult {
+    /// Result<u8, String>
+    pub(super) fn new_with_type_path(path: &TypePath, types: &TypeDeclarations) -> Option<Self> {
+        let last = path.path.segments.last()?;
+        if last.ident != "Result" {
+            return None;
+        }
+
+        let args = match &last.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 2 => &args.args,
+            _ => return None,
+        };
+
+        let (ok_ty, err_ty) = match (&args[0], &args[1]) {
+            (GenericArgument::Type(ok_ty), GenericArgument::Type(err_ty)) => (ok_ty, err_ty),
+            _ => return None,
+        };
+
+        Some(BuiltInResult {
+            ok_ty: Box::new(BridgedType::new_with_type(ok_ty, types)?),
+            err_ty: Box::new(BridgedType::new_with_type(err_ty, types)?),
+        })
+    }
+
+    pub(super) fn to_rust_type_path(&self) -> TokenStream {
+        let ok_ty = self.ok_ty.to_rust_type_path();
+        let err_ty = self.err_ty.to_rust_type_path();
+
+        quote! { Result<#ok_ty, #err_ty> }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, TypeDeclarations};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{GenericArgument, Path, PathArguments, Token, Type, TypeParamBound, TypePath};
+
+/// `impl Stream<Item = T>` or `Pin<Box<dyn Stream<Item = T> + Send>>`
+///
+/// The stream gets boxed up and passed to Swift as a pointer. Swift wraps that pointer in a
+/// `RustStream<T>`, which is an `AsyncSequence` that asks for one item at a time by calling the
+/// function's generated `next` extern.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BuiltInStream {
+    pub item: Box<BridgedType>,
+    /// `Pin<Box<dyn Stream<Item = T> + Send>>` as opposed to `impl Stream<Item = T>`
+    pub boxed: bool,
+}
+
+impl BuiltInStream {
+    /// impl Stream<Item = u8>
+    pub(super) fn new_with_impl_trait(
+        bounds: &Punctuated<TypeParamBound, Token![+]>,
+        types: &TypeDeclarations,
+    ) -> Option<Self> {
+        let item = stream_item(bounds)?;
+
+        Some(BuiltInStream {
+            item: Box::new(BridgedType::new_with_type(item, types)?),
+            boxed: false,
+        })
+    }
+
+    /// Pin<Box<dyn Stream<Item = u8> + Send>>
+    pub(super) fn new_with_type_path(path: &TypePath, types: &TypeDeclarations) -> Option<Self> {
+        let pin = path.path.segments.last()?;
+        if pin.ident != "Pin" {
+            return None;
+        }
+
+        let boxed = match single_type_arg(&pin.arguments)? {
+            Type::Path(boxed) => boxed.path.segments.last()?,
+            _ => return None,
+        };
+        if boxed.ident != "Box" {
+            return None;
+        }
+
+        let trait_object = match single_type_arg(&boxed.arguments)? {
+            Type::TraitObject(trait_object) => trait_object,
+            _ => return None,
+        };
+        let item = stream_item(&trait_object.bounds)?;
+
+        Some(BuiltInStream {
+            item: Box::new(BridgedType::new_with_type(item, types)?),
+            boxed: true,
+        })
+    }
+
+    pub(super) fn to_rust_type_path(&self) -> TokenStream {
+        let item = self.item.to_rust_type_path();
+
+        if self.boxed {
+            quote! { std::pin::Pin<Box<dyn swift_bridge::async_support::Stream<Item = #item> + Send>> }
+        } else {
+            quote! { impl swift_bridge::async_support::Stream<Item = #item> }
+        }
+    }
+
+    // A `Pin<Box<dyn Stream>>` is a fat pointer, so we box it a second time.
+    pub(super) fn convert_rust_value_to_ffi_value(&self, expression: &TokenStream) -> TokenStream {
+        if self.boxed {
+            quote! {
+                Box::into_raw(Box::new( #expression )) as *mut std::ffi::c_void
+            }
+        } else {
+            quote! {
+                swift_bridge::async_support::box_stream( #expression )
+            }
+        }
+    }
+
+    /// Generates the `next` and `_free` functions that Swift uses to iterate over and then free
+    /// the stream that gets returned by the function with the given link name.
+    ///
+    /// Each call to `next` spawns a task that waits for the stream's next item and then hands it
+    /// to `on_item`, or calls `on_end` once the stream is finished. If polling the stream panics
+    /// then `on_panic` gets called, and if Swift cancels the task before an item is ready, or the
+    /// task gets dropped because the runtime was shut down, then `on_cancelled` gets called
+    /// instead.
+    ///
+    /// ```no_rust,ignore
+    /// #[export_name = "__swift_bridge__$some_function$next"]
+    /// pub extern "C" fn __swift_bridge__some_function_next(
+    ///     stream: *mut std::ffi::c_void,
+    ///     cancellation_token: *mut std::ffi::c_void,
+    ///     callback_wrapper: *mut std::ffi::c_void,
+    ///     on_item: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+    ///     on_end: extern "C" fn(*mut std::ffi::c_void) -> (),
+    ///     on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+    ///     on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+    /// ) { ... }
+    ///
+    /// #[export_name = "__swift_bridge__$some_function$_free"]
+    /// pub extern "C" fn __swift_bridge__some_function__free(
+    ///     stream: *mut std::ffi::c_void
+    /// ) { ... }
+    /// ```
+    pub(crate) fn generate_next_and_free_functions(
+        &self,
+        link_name: &str,
+        prefixed_fn_name: &Ident,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let next_link_name = next_link_name(link_name);
+        let free_link_name = free_link_name(link_name);
+
+        let next_fn_name = Ident::new(
+            &format!("{}_next", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+        let free_fn_name = Ident::new(
+            &format!("{}__free", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+
+        let item = self.item.to_rust_type_path();
+        let item_ffi = self
+            .item
+            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+        let item_val = self.item.convert_rust_value_to_ffi_compatible_value(
+            &quote! { item },
+            swift_bridge_path,
+            types,
+        );
+
+        // Swift never calls `next` again until the previous call's callback has fired, which only
+        // happens after the `stream_next` future has been dropped. So the stream is never polled
+        // by two tasks at once.
+        quote! {
+            #[export_name = #next_link_name]
+            pub extern "C" fn #next_fn_name (
+                stream: *mut std::ffi::c_void,
+                cancellation_token: *mut std::ffi::c_void,
+                callback_wrapper: *mut std::ffi::c_void,
+                on_item: extern "C" fn(*mut std::ffi::c_void, #item_ffi) -> (),
+                on_end: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+            ) {
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
+                let next = unsafe { swift_bridge::async_support::stream_next::<#item>(stream) };
+                let task = async move {
+                    let item = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(next)).await;
+
+                    let callback_wrapper = callback_wrapper.into_raw();
+
+                    match item {
+                        Some(Ok(Some(item))) => (on_item)(callback_wrapper, #item_val),
+                        Some(Ok(None)) => (on_end)(callback_wrapper),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
+            }
+
+            #[export_name = #free_link_name]
+            pub extern "C" fn #free_fn_name (stream: *mut std::ffi::c_void) {
+                unsafe { swift_bridge::async_support::stream_free::<#item>(stream) }
+            }
+        }
+    }
+
+    /// The C declarations for the function's `next` and `_free` functions.
+    pub(crate) fn c_declarations(&self, link_name: &str) -> String {
+        let next = next_link_name(link_name);
+
+        format!(
+            r#"void {next}(void* stream, void* cancellation_token, void* callback_wrapper, void {next}$item(void* callback_wrapper, {item} item), void {next}$end(void* callback_wrapper), void {next}$panic(void* callback_wrapper, void* message), void {next}$cancelled(void* callback_wrapper, bool runtime_shut_down));
+void {free}(void* stream);
+"#,
+            next = next,
+            item = self.item.to_c(),
+            free = free_link_name(link_name),
+        )
+    }
+
+    /// Any C header that the stream's items need.
+    pub(crate) fn c_include(&self) -> Option<&'static str> {
+        self.item.c_include()
+    }
+
+    /// Wrap the pointer that a Rust function returned in a `RustStream`.
+    pub(crate) fn convert_ffi_value_to_swift_value(
+        &self,
+        value: &str,
+        link_name: &str,
+        types: &TypeDeclarations,
+    ) -> String {
+        let item_val = self.item.convert_ffi_value_to_swift_value(
+            "item",
+            TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
+            types,
+        );
+        let item_swift_ty = self
+            .item
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+        let wrapper = format!("RustStreamCbWrapper<{}>", item_swift_ty);
+
+        format!(
+            "RustStream(ptr: {value}, next: {{ stream, cancellationToken, cbWrapperPtr in {next}(stream, cancellationToken, cbWrapperPtr, {{ cbWrapperPtr, item in {wrapper}.complete(cbWrapperPtr, .success({item_val})) }}, {{ cbWrapperPtr in {wrapper}.complete(cbWrapperPtr, .success(nil)) }}, {{ cbWrapperPtr, message in {wrapper}.complete(cbWrapperPtr, .failure(RustPanic(message: RustString(ptr: message!).toString()))) }}, {{ cbWrapperPtr, runtimeShutDown in {wrapper}.complete(cbWrapperPtr, .failure(rustTaskDroppedError(runtimeShutDown))) }}) }}, free: {{ stream in {free}(stream) }})",
+            value = value,
+            next = next_link_name(link_name),
+            wrapper = wrapper,
+            item_val = item_val,
+            free = free_link_name(link_name),
+        )
+    }
+}
+
+/// `Pin<T>` -> `T`
+fn single_type_arg(arguments: &PathArguments) -> Option<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `Stream<Item = u8> + Send` -> `u8`
+fn stream_item(bounds: &Punctuated<TypeParamBound, Token![+]>) -> Option<&Type> {
+    for bound in bounds {
+        let trait_bound = match bound {
+            TypeParamBound::Trait(trait_bound) => trait_bound,
+            TypeParamBound::Lifetime(_) => continue,
+        };
+
+        let last = trait_bound.path.segments.last()?;
+        if last.ident != "Stream" {
+            continue;
+        }
+
+        if let PathArguments::AngleBracketed(args) = &last.arguments {
+            for arg in args.args.iter() {
+                if let GenericArgument::Binding(binding) = arg {
+                    if binding.ident == "Item" {
+                        return Some(&binding.ty);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// __swift_bridge__$some_function -> __swift_bridge__$some_function$next
+fn next_link_name(link_name: &str) -> String {
+    format!("{}$next", link_name)
+}
+
+/// __swift_bridge__$some_function -> __swift_bridge__$some_function$_free
+fn free_link_name(link_name: &str) -> String {
+    format!("{}$_free", link_name)
+}
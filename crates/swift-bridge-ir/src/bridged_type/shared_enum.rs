@@ -10,6 +10,9 @@ pub(crate) use self::enum_variant::EnumVariant;
 pub(crate) struct SharedEnum {
     pub name: Ident,
     pub variants: Vec<EnumVariant>,
+    /// `#[swift_bridge(Codable)]`
+    /// Derive serde's `Serialize` and `Deserialize` in Rust and conform to `Codable` in Swift.
+    pub codable: bool,
 }
 
 impl SharedEnum {
@@ -1,5 +1,5 @@
 use crate::bridged_type::{BridgedType, TypePosition};
-use crate::parse::TypeDeclarations;
+use crate::parse::{Operators, TypeDeclarations};
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
@@ -18,6 +18,15 @@ pub(crate) struct SharedStruct {
     pub fields: StructFields,
     pub swift_name: Option<LitStr>,
     pub already_declared: bool,
+    /// `#[swift_bridge(Codable)]`
+    /// Derive serde's `Serialize` and `Deserialize` in Rust and conform to `Codable` in Swift.
+    pub codable: bool,
+    /// `#[swift_bridge(operators(Add, Index(Output = f32)))]`
+    /// Generate Swift operators and subscripts that use the struct's `std::ops` implementations.
+    pub operators: Operators,
+    /// `#[swift_bridge(Default)]`
+    /// Generate a Swift `init()` that uses the struct's `Default` implementation.
+    pub default: bool,
 }
 
 impl SharedStruct {
@@ -45,6 +54,27 @@ impl SharedStruct {
         }
     }
 
+    /// The name of the Rust function that Swift calls to use one of this struct's operators or
+    /// its `Default` implementation.
+    ///
+    /// For `struct SomeStruct` and "_add" this would be:
+    /// "__swift_bridge__$SomeStruct$_add"
+    pub(crate) fn operator_fn_ffi_name(&self, fn_name: &str) -> String {
+        format!("{}${}", self.ffi_name_string(), fn_name)
+    }
+
+    /// The Rust function that Swift calls to use one of this struct's operators or its `Default`
+    /// implementation.
+    ///
+    /// For `struct SomeStruct` and "_add" this would be:
+    /// "__swift_bridge__SomeStruct__add"
+    pub(crate) fn operator_fn_ident(&self, fn_name: &str) -> Ident {
+        Ident::new(
+            &format!("{}{}_{}", SWIFT_BRIDGE_PREFIX, self.name, fn_name),
+            self.name.span(),
+        )
+    }
+
     /// __swift_bridge__Option_SomeStruct
     pub fn ffi_option_name_tokens(&self) -> TokenStream {
         let name = Ident::new(
@@ -28,19 +28,32 @@ use crate::test_utils::{
 };
 
 mod already_declared_attribute_codegen_tests;
+mod array_codegen_tests;
 mod async_function_codegen_tests;
+mod char_codegen_tests;
+mod codable_codegen_tests;
 mod conditional_compilation_codegen_tests;
+mod conversion_initializers_codegen_tests;
 mod extern_rust_function_opaque_rust_type_argument_codegen_tests;
 mod extern_rust_function_opaque_rust_type_return_codegen_tests;
 mod extern_rust_method_swift_class_placement_codegen_tests;
 mod function_attribute_codegen_tests;
 mod generic_opaque_rust_type_codegen_tests;
+mod int128_codegen_tests;
+mod iterator_codegen_tests;
+mod non_zero_codegen_tests;
 mod opaque_rust_type_codegen_tests;
 mod opaque_swift_type_codegen_tests;
+mod opaque_type_protocols_codegen_tests;
+mod operators_codegen_tests;
 mod option_codegen_tests;
+mod path_codegen_tests;
 mod shared_enum_codegen_tests;
 mod shared_struct_codegen_tests;
+mod stream_codegen_tests;
 mod string_codegen_tests;
+mod time_codegen_tests;
+mod trait_codegen_tests;
 mod vec_codegen_tests;
 
 struct CodegenTest {
@@ -0,0 +1,261 @@
+//! See also: crates/swift-integration-tests/src/array.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we can pass and return arrays of primitives to and from Rust functions.
+mod extern_rust_fn_array_of_primitives {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: [u8; 4]) -> [u8; 4];
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::FfiArray<u8, 4>
+            ) -> swift_bridge::FfiArray<u8, 4> {
+                swift_bridge::FfiArray::from_array(super::some_function(arg.into_array()))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: (UInt8, UInt8, UInt8, UInt8)) -> (UInt8, UInt8, UInt8, UInt8) {
+    __swift_bridge__$some_function(__swift_bridge__$Array$u8$4(array: arg)).array
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+#ifndef __swift_bridge__$Array$u8$4$Declared
+#define __swift_bridge__$Array$u8$4$Declared
+typedef struct __swift_bridge__$Array$u8$4 { uint8_t array[4]; } __swift_bridge__$Array$u8$4;
+#endif
+struct __swift_bridge__$Array$u8$4 __swift_bridge__$some_function(struct __swift_bridge__$Array$u8$4 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_array_of_primitives() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we convert each element of an array of shared structs to and from its FFI
+/// representation.
+mod extern_rust_fn_array_of_shared_structs {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Rust" {
+                    fn some_function (arg: [SomeStruct; 2]) -> [SomeStruct; 2];
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::FfiArray<__swift_bridge__SomeStruct, 2>
+            ) -> swift_bridge::FfiArray<__swift_bridge__SomeStruct, 2> {
+                swift_bridge::FfiArray::from_array(
+                    super::some_function(
+                        arg.into_array().map(|val| val.into_rust_repr())
+                    ).map(|val| val.into_ffi_repr())
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: (SomeStruct, SomeStruct)) -> (SomeStruct, SomeStruct) {
+    { let val = __swift_bridge__$some_function({ let val = arg; return __swift_bridge__$Array$SomeStruct$2(array: (val.0.intoFfiRepr(), val.1.intoFfiRepr())) }()).array; return (val.0.intoSwiftRepr(), val.1.intoSwiftRepr()) }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+#ifndef __swift_bridge__$Array$SomeStruct$2$Declared
+#define __swift_bridge__$Array$SomeStruct$2$Declared
+typedef struct __swift_bridge__$Array$SomeStruct$2 { struct __swift_bridge__$SomeStruct array[2]; } __swift_bridge__$Array$SomeStruct$2;
+#endif
+struct __swift_bridge__$Array$SomeStruct$2 __swift_bridge__$some_function(struct __swift_bridge__$Array$SomeStruct$2 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_array_of_shared_structs() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass and return arrays to and from Swift functions.
+mod extern_swift_fn_array_of_primitives {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function (arg: [u16; 2]) -> [u16; 2];
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_function(arg: [u16; 2]) -> [u16; 2] {
+                    unsafe {
+                        __swift_bridge__some_function(swift_bridge::FfiArray::from_array(arg))
+                    }.into_array()
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(
+                    arg: swift_bridge::FfiArray<u16, 2>
+                ) -> swift_bridge::FfiArray<u16, 2>;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: __swift_bridge__$Array$u16$2) -> __swift_bridge__$Array$u16$2 {
+    __swift_bridge__$Array$u16$2(array: some_function(arg: arg.array))
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+#ifndef __swift_bridge__$Array$u16$2$Declared
+#define __swift_bridge__$Array$u16$2$Declared
+typedef struct __swift_bridge__$Array$u16$2 { uint16_t array[2]; } __swift_bridge__$Array$u16$2;
+#endif
+    "#,
+    );
+
+    #[test]
+    fn extern_swift_fn_array_of_primitives() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use arrays as shared struct fields.
+mod shared_struct_with_array_field {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                struct SomeStruct {
+                    hash: [u8; 3]
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub struct __swift_bridge__SomeStruct {
+                    hash: swift_bridge::FfiArray<u8, 3>
+                }
+            },
+            quote! {
+                __swift_bridge__SomeStruct { hash: swift_bridge::FfiArray::from_array(val.hash) }
+            },
+            quote! {
+                SomeStruct { hash: val.hash.into_array() }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public struct SomeStruct {
+    var hash: (UInt8, UInt8, UInt8)
+"#,
+            r#"
+{ let val = self; return __swift_bridge__$SomeStruct(hash: __swift_bridge__$Array$u8$3(array: val.hash)); }()
+"#,
+            r#"
+{ let val = self; return SomeStruct(hash: val.hash.array); }()
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+#ifndef __swift_bridge__$Array$u8$3$Declared
+#define __swift_bridge__$Array$u8$3$Declared
+typedef struct __swift_bridge__$Array$u8$3 { uint8_t array[3]; } __swift_bridge__$Array$u8$3;
+#endif
+typedef struct __swift_bridge__$SomeStruct { struct __swift_bridge__$Array$u8$3 hash; } __swift_bridge__$SomeStruct;
+    "#,
+    );
+
+    #[test]
+    fn shared_struct_with_array_field() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
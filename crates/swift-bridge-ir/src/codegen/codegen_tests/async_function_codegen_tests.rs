@@ -3,7 +3,8 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 /// Verify that we generate the correct code for extern "Rust" async functions that do not return
-/// a value.
+/// a value. The Swift function throws, so that it can report panics, cancellation of the Swift task
+/// and the async runtime shutting down.
 mod extern_rust_async_function_no_return {
     use super::*;
 
@@ -21,20 +22,27 @@ mod extern_rust_async_function_no_return {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = super::some_function();
                 let task = async move {
-                    fut.await;
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper)
+                    match val {
+                        Some(Ok(())) => (callback)(callback_wrapper),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -42,27 +50,42 @@ mod extern_rust_async_function_no_return {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async {
+func some_function() async throws {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(()))
     }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<(), Never>) -> ()
+    var cb: (Result<(), Error>) -> ()
 
-    public init(cb: @escaping (Result<(), Never>) -> ()) {
+    public init(cb: @escaping (Result<(), Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -73,7 +96,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper));
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
     "#,
         )
     }
@@ -108,21 +131,28 @@ mod extern_rust_async_function_u32_arg {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
                 arg: u32
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = super::some_function(arg);
                 let task = async move {
-                    fut.await;
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper)
+                    match val {
+                        Some(Ok(())) => (callback)(callback_wrapper),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -130,27 +160,42 @@ mod extern_rust_async_function_u32_arg {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function(_ arg: UInt32) async {
+func some_function(_ arg: UInt32) async throws {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(()))
     }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete, arg)
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, arg)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<(), Never>) -> ()
+    var cb: (Result<(), Error>) -> ()
 
-    public init(cb: @escaping (Result<(), Never>) -> ()) {
+    public init(cb: @escaping (Result<(), Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -162,7 +207,7 @@ class CbWrapper$some_function {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
 #include <stdint.h>
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), uint32_t arg);
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down), uint32_t arg);
     "#,
         )
     }
@@ -197,20 +242,27 @@ mod extern_rust_async_function_returns_u8 {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = super::some_function();
                 let task = async move {
-                    let val = fut.await;
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper, val)
+                    match val {
+                        Some(Ok(val)) => (callback)(callback_wrapper, val),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -218,27 +270,42 @@ mod extern_rust_async_function_returns_u8 {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> UInt8 {
+func some_function() async throws -> UInt8 {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UInt8) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(rustFnRetVal))
     }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<UInt8, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<UInt8, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<UInt8, Never>) -> ()
+    var cb: (Result<UInt8, Error>) -> ()
 
-    public init(cb: @escaping (Result<UInt8, Never>) -> ()) {
+    public init(cb: @escaping (Result<UInt8, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -250,7 +317,7 @@ class CbWrapper$some_function {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
 #include <stdint.h>
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret));
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
     "#,
         )
     }
@@ -285,20 +352,27 @@ mod extern_rust_async_function_returns_string {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = super::some_function();
                 let task = async move {
-                    let val = swift_bridge::string::RustString(fut.await).box_into_raw();
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper, val)
+                    match val {
+                        Some(Ok(val)) => (callback)(callback_wrapper, swift_bridge::string::RustString(val).box_into_raw()),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -306,27 +380,42 @@ mod extern_rust_async_function_returns_string {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> RustString {
+func some_function() async throws -> RustString {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(RustString(ptr: rustFnRetVal!)))
     }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<RustString, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<RustString, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<RustString, Never>) -> ()
+    var cb: (Result<RustString, Error>) -> ()
 
-    public init(cb: @escaping (Result<RustString, Never>) -> ()) {
+    public init(cb: @escaping (Result<RustString, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -337,7 +426,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret));
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
     "#,
         )
     }
@@ -374,20 +463,27 @@ mod extern_rust_async_function_returns_struct {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, __swift_bridge__SomeStruct) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = super::some_function();
                 let task = async move {
-                    let val = fut.await.into_ffi_repr();
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper, val)
+                    match val {
+                        Some(Ok(val)) => (callback)(callback_wrapper, val.into_ffi_repr()),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -395,27 +491,42 @@ mod extern_rust_async_function_returns_struct {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> SomeStruct {
+func some_function() async throws -> SomeStruct {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: __swift_bridge__$SomeStruct) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(rustFnRetVal.intoSwiftRepr()))
     }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<SomeStruct, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<SomeStruct, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<SomeStruct, Never>) -> ()
+    var cb: (Result<SomeStruct, Error>) -> ()
 
-    public init(cb: @escaping (Result<SomeStruct, Never>) -> ()) {
+    public init(cb: @escaping (Result<SomeStruct, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -426,7 +537,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$SomeStruct ret));
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$SomeStruct ret), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
     "#,
         )
     }
@@ -443,7 +554,187 @@ void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge_
     }
 }
 
+/// Verify that an extern "Rust" async function that returns a `Result<T, E>` passes the `Err` to a
+/// separate callback, and that the Swift function throws the error.
+mod extern_rust_async_function_returns_result {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    async fn some_function() -> Result<u8, String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                cancellation_token: *mut std::ffi::c_void,
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_error: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+            ) {
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
+                let fut = super::some_function();
+                let task = async move {
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
+
+                    let callback_wrapper = callback_wrapper.into_raw();
+
+                    match val {
+                        Some(Ok(Ok(val))) => (callback)(callback_wrapper, val),
+                        Some(Ok(Err(err))) => (on_error)(callback_wrapper, swift_bridge::string::RustString(err).box_into_raw()),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() async throws -> UInt8 {
+    func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UInt8) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.success(rustFnRetVal))
+    }
+    func onError(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnErrVal: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustString(ptr: rustFnErrVal!)))
+    }
+    func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+    }
+
+    let cancellationToken = __swift_bridge__$CancellationToken$new()
+    defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<UInt8, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onError, onPanic, onCancelled)
+        })
+    }, onCancel: {
+        __swift_bridge__$CancellationToken$cancel(cancellationToken)
+    })
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+#include <stdint.h>
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret), void __swift_bridge__$some_function$err(void* callback_wrapper, void* err), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_function_returns_result() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that an extern "Rust" async function that returns a `Result<(), E>` where `E` is an
+/// opaque Rust type becomes an `async throws` Swift function with no return value.
+mod extern_rust_async_function_returns_result_null_opaque_err {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeError;
+                    async fn some_function() -> Result<(), SomeError>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_error: extern "C" fn(*mut std::ffi::c_void, *mut super::SomeError) -> (),
+            },
+            quote! {
+                match val {
+                    Some(Ok(Ok(()))) => (callback)(callback_wrapper),
+                    Some(Ok(Err(err))) => (on_error)(callback_wrapper, Box::into_raw(Box::new(err)) as *mut super::SomeError),
+                    Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                    None => (on_cancelled)(callback_wrapper, false),
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function() async throws {
+"#,
+            r#"
+    func onError(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnErrVal: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(SomeError(ptr: rustFnErrVal!)))
+    }
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$err(void* callback_wrapper, void* err), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down));
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_function_returns_result_null_opaque_err() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
 /// Verify that we generate the correct code for extern "Rust" async method.
+/// The Swift callback wrapper keeps `self` alive for as long as the Rust future might borrow it.
 mod extern_rust_async_method {
     use super::*;
 
@@ -462,21 +753,28 @@ mod extern_rust_async_method {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__SomeType_some_method(
+                cancellation_token: *mut std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
                 this: *mut super::SomeType
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
                 let fut = (unsafe {&*this}).some_method();
                 let task = async move {
-                    fut.await;
+                    let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                    let callback_wrapper = callback_wrapper;
-                    let callback_wrapper = callback_wrapper.0;
+                    let callback_wrapper = callback_wrapper.into_raw();
 
-                    (callback)(callback_wrapper)
+                    match val {
+                        Some(Ok(())) => (callback)(callback_wrapper),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
             }
         })
     }
@@ -485,28 +783,45 @@ mod extern_rust_async_method {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
 extension SomeTypeRef {
-    public func some_method() async {
+    public func some_method() async throws {
         func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
             let wrapper = Unmanaged<CbWrapper$SomeType$some_method>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
             wrapper.cb(.success(()))
         }
+        func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {
+            let wrapper = Unmanaged<CbWrapper$SomeType$some_method>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
+        }
+        func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {
+            let wrapper = Unmanaged<CbWrapper$SomeType$some_method>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+        }
 
-        return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-            let callback = { rustFnRetVal in
-                continuation.resume(with: rustFnRetVal)
-            }
+        let cancellationToken = __swift_bridge__$CancellationToken$new()
+        defer { __swift_bridge__$CancellationToken$_free(cancellationToken) }
 
-            let wrapper = CbWrapper$SomeType$some_method(cb: callback)
-            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+        return try await withTaskCancellationHandler(operation: {
+            try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+                let callback = { rustFnRetVal in
+                    continuation.resume(with: rustFnRetVal)
+                }
+
+                let wrapper = CbWrapper$SomeType$some_method(cb: callback, owner: self)
+                let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-            __swift_bridge__$SomeType$some_method(wrapperPtr, onComplete, ptr)
+                __swift_bridge__$SomeType$some_method(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, ptr)
+            })
+        }, onCancel: {
+            __swift_bridge__$CancellationToken$cancel(cancellationToken)
         })
     }
     class CbWrapper$SomeType$some_method {
-        var cb: (Result<(), Never>) -> ()
+        var cb: (Result<(), Error>) -> ()
+        let owner: AnyObject
     
-        public init(cb: @escaping (Result<(), Never>) -> ()) {
+        public init(cb: @escaping (Result<(), Error>) -> (), owner: AnyObject) {
             self.cb = cb
+            self.owner = owner
         }
     }
 }
@@ -517,7 +832,7 @@ extension SomeTypeRef {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$SomeType$some_method(void* callback_wrapper, void __swift_bridge__$SomeType$some_method$async(void* callback_wrapper), void* self);
+void __swift_bridge__$SomeType$some_method(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$SomeType$some_method$async(void* callback_wrapper), void __swift_bridge__$SomeType$some_method$panic(void* callback_wrapper, void* message), void __swift_bridge__$SomeType$some_method$cancelled(void* callback_wrapper, bool runtime_shut_down), void* self);
     "#,
         )
     }
@@ -533,3 +848,524 @@ void __swift_bridge__$SomeType$some_method(void* callback_wrapper, void __swift_
         .test();
     }
 }
+
+/// Verify that an extern "Rust" async method that takes an owned `self` moves `self` into the
+/// future, so Swift does not need to keep it alive.
+mod extern_rust_async_method_owned_self {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                    async fn some_method(self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            let fut = (* unsafe { Box::from_raw(this) }).some_method();
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+                let wrapper = CbWrapper$SomeType$some_method(cb: callback)
+                let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+                __swift_bridge__$SomeType$some_method(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, {isOwned = false; return ptr;}())
+"#,
+            r#"
+    class CbWrapper$SomeType$some_method {
+        var cb: (Result<(), Error>) -> ()
+    
+        public init(cb: @escaping (Result<(), Error>) -> ()) {
+            self.cb = cb
+        }
+    }
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn extern_rust_async_method_owned_self() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that an extern "Rust" async function that uses the `local` attribute gets polled on the
+/// calling thread instead of being spawned on the async runtime, and that Swift calls it on the
+/// main actor.
+mod extern_rust_async_local_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    async fn some_function(arg: u8);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![
+                quote! {
+                    on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+                    schedule: extern "C" fn(*mut std::ffi::c_void) -> (),
+                    arg: u8
+                },
+                quote! {
+                    swift_bridge::async_support::LocalTask::spawn(task, schedule)
+                },
+            ],
+            does_not_contain: vec![quote! {
+                ASYNC_RUNTIME
+            }],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@MainActor public func some_function(_ arg: UInt8) async throws {
+"#,
+            r#"
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, scheduleLocalRustTask, arg)
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down), void __swift_bridge__$some_function$schedule(void* task), uint8_t arg);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_local_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we copy `&str` and `&[T]` arguments before creating the future, since Swift only
+/// keeps them alive until the async function returns.
+mod extern_rust_async_function_copies_borrowed_args {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    async fn some_function(arg1: SomeType, arg2: &str, arg3: &[u8]);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            let arg1 = unsafe { *Box::from_raw(arg1) };
+            let arg2 = arg2.to_str().to_owned();
+            let arg3 = arg3.as_slice().to_owned();
+            let fut = async move { super::some_function(arg1, &arg2, &arg3).await };
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, {arg1.isOwned = false; return arg1.ptr;}(), arg2AsRustStr, arg3.toFfiSlice())
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down), void* arg1, struct RustStr arg2, struct __private__FfiSlice arg3);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_function_copies_borrowed_args() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that async methods that copy their arguments get `self` before creating the future.
+mod extern_rust_async_method_copies_borrowed_args {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    async fn some_method(&self, arg: &str);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            let arg = arg.to_str().to_owned();
+            let this = (unsafe { &*this });
+            let fut = async move { this.some_method(&arg).await };
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+            __swift_bridge__$SomeType$some_method(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, ptr, argAsRustStr)
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$SomeType$some_method(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$SomeType$some_method$async(void* callback_wrapper), void __swift_bridge__$SomeType$some_method$panic(void* callback_wrapper, void* message), void __swift_bridge__$SomeType$some_method$cancelled(void* callback_wrapper, bool runtime_shut_down), void* self, struct RustStr arg);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_method_copies_borrowed_args() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we pass owned Swift types to local async Rust functions. Swift retains the instance
+/// and Rust releases it when the future drops its handle.
+mod extern_rust_async_local_function_owned_swift_arg {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    type SomeSwiftType;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    async fn some_function(arg: SomeSwiftType);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            let fut = super::some_function(arg);
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+            __swift_bridge__$some_function(cancellationToken, wrapperPtr, onComplete, onPanic, onCancelled, scheduleLocalRustTask, __private__PointerToSwiftType(ptr: Unmanaged.passRetained(arg).toOpaque()))
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$cancelled(void* callback_wrapper, bool runtime_shut_down), void __swift_bridge__$some_function$schedule(void* task), struct __private__PointerToSwiftType arg);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_local_function_owned_swift_arg() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async functions that do not return
+/// a value, and that Swift hands its `Task` to Rust so that dropping the future cancels it.
+mod extern_swift_async_function_no_return {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub async fn some_function() {
+                    extern "C" fn on_complete(future_state: *mut std::ffi::c_void) {
+                        unsafe {
+                            swift_bridge::async_support::SwiftFuture::<()>::complete(future_state, ())
+                        }
+                    }
+
+                    let future = {
+                        let (future, future_state) =
+                            swift_bridge::async_support::SwiftFuture::<()>::new();
+                        let task = unsafe { __swift_bridge__some_function(future_state, on_complete) };
+                        unsafe { future.with_swift_task(task) }
+                    };
+                    future.await
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(
+                    callback_wrapper: *mut std::ffi::c_void,
+                    callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                ) -> *mut std::ffi::c_void;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @convention(c) (UnsafeMutableRawPointer) -> Void) -> UnsafeMutableRawPointer {
+    let task = Task {
+        await some_function()
+        callback(callbackWrapper)
+    }
+    return Unmanaged.passRetained(__private__SwiftTask(task)).toOpaque()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim("")
+    }
+
+    #[test]
+    fn extern_swift_async_function_no_return() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we convert the value that an extern "Swift" async function returns.
+mod extern_swift_async_function_returns_string {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function(arg: u8) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub async fn some_function(arg: u8) -> String {
+                    extern "C" fn on_complete(
+                        future_state: *mut std::ffi::c_void,
+                        ret: *mut swift_bridge::string::RustString
+                    ) {
+                        let val = unsafe { Box::from_raw(ret).0 };
+                        unsafe {
+                            swift_bridge::async_support::SwiftFuture::<String>::complete(future_state, val)
+                        }
+                    }
+
+                    let future = {
+                        let (future, future_state) =
+                            swift_bridge::async_support::SwiftFuture::<String>::new();
+                        let task = unsafe { __swift_bridge__some_function(future_state, on_complete, arg) };
+                        unsafe { future.with_swift_task(task) }
+                    };
+                    future.await
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(
+                    callback_wrapper: *mut std::ffi::c_void,
+                    callback: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                    arg: u8
+                ) -> *mut std::ffi::c_void;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @convention(c) (UnsafeMutableRawPointer, UnsafeMutableRawPointer) -> Void, _ arg: UInt8) -> UnsafeMutableRawPointer {
+    let task = Task {
+        let val = await some_function(arg: arg)
+        callback(callbackWrapper, { let rustString = val.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+    }
+    return Unmanaged.passRetained(__private__SwiftTask(task)).toOpaque()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim("")
+    }
+
+    #[test]
+    fn extern_swift_async_function_returns_string() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we retain the Swift instance for the duration of an extern "Swift" async method,
+/// since Rust might drop its handle to the instance before the method completes.
+mod extern_swift_async_method {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    type SomeType;
+
+                    async fn some_method(&self) -> u16;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            impl SomeType {
+                pub async fn some_method(&self) -> u16 {
+                    extern "C" fn on_complete(future_state: *mut std::ffi::c_void, ret: u16) {
+                        let val = ret;
+                        unsafe {
+                            swift_bridge::async_support::SwiftFuture::<u16>::complete(future_state, val)
+                        }
+                    }
+
+                    let future = {
+                        let (future, future_state) =
+                            swift_bridge::async_support::SwiftFuture::<u16>::new();
+                        let task = unsafe {
+                            __swift_bridge__SomeType_some_method(
+                                future_state,
+                                on_complete,
+                                swift_bridge::PointerToSwiftType(self.0)
+                            )
+                        };
+                        unsafe { future.with_swift_task(task) }
+                    };
+                    future.await
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$SomeType$some_method")
+func __swift_bridge__SomeType_some_method (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @convention(c) (UnsafeMutableRawPointer, UInt16) -> Void, _ this: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer {
+    let this = Unmanaged<SomeType>.fromOpaque(this).takeUnretainedValue()
+    let task = Task {
+        let val = await this.some_method()
+        callback(callbackWrapper, val)
+    }
+    return Unmanaged.passRetained(__private__SwiftTask(task)).toOpaque()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn extern_swift_async_method() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
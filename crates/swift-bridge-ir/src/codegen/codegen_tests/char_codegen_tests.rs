@@ -0,0 +1,171 @@
+//! See also: crates/swift-integration-tests/src/char.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we pass a `char` across the FFI boundary as its Unicode scalar value.
+mod extern_rust_fn_char {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: char) -> char;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: u32) -> u32 {
+                u32::from(
+                    super::some_function(
+                        char::from_u32(arg).expect("Invalid Unicode scalar value")
+                    )
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Unicode.Scalar) -> Unicode.Scalar {
+    Unicode.Scalar(__swift_bridge__$some_function(arg.value))!
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+uint32_t __swift_bridge__$some_function(uint32_t arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_char() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass and return an `Option<char>`.
+mod extern_rust_fn_option_char {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Option<char>) -> Option<char>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::option::OptionU32
+            ) -> swift_bridge::option::OptionU32 {
+                if let Some(val) = super::some_function(
+                    if arg.is_some {
+                        Some(char::from_u32(arg.val).expect("Invalid Unicode scalar value"))
+                    } else {
+                        None
+                    }
+                ) {
+                    swift_bridge::option::OptionU32 { val: u32::from(val), is_some: true }
+                } else {
+                    swift_bridge::option::OptionU32 { val: 123, is_some: false }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Optional<Unicode.Scalar>) -> Optional<Unicode.Scalar> {
+    { let val = __swift_bridge__$some_function({ let val = arg; return __private__OptionU32(val: val?.value ?? 123, is_some: val != nil); }()); if val.is_some { return Unicode.Scalar(val.val)! } else { return nil } }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct __private__OptionU32 __swift_bridge__$some_function(struct __private__OptionU32 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_option_char() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift functions that take and return a `char` receive a `Unicode.Scalar`.
+mod extern_swift_fn_char {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function (arg: char) -> char;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function(arg: char) -> char {
+                char::from_u32(
+                    unsafe { __swift_bridge__some_function(u32::from(arg)) }
+                ).expect("Invalid Unicode scalar value")
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: UInt32) -> UInt32 {
+    some_function(arg: Unicode.Scalar(arg)!).value
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_char() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,319 @@
+//! See also: crates/swift-integration-tests/src/shared_types/codable.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a struct with named fields derives serde's traits and gets a `Codable`
+/// conformance that is keyed by the Rust field names.
+mod codable_struct_named_fields {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct SomeStruct {
+                    id: u32,
+                    name: String,
+                    score: Option<f64>,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(swift_bridge::serde::Serialize, swift_bridge::serde::Deserialize)]
+            #[serde(crate = "swift_bridge :: serde")]
+            pub struct SomeStruct {
+                pub id: u32,
+                pub name: String,
+                pub score: Option<f64>
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Codable {
+    enum CodingKeys: CodingKey {
+        case id
+        case name
+        case score
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        self.id = try container.decode(UInt32.self, forKey: .id)
+        self.name = try container.decode(String.self, forKey: .name).intoRustString()
+        self.score = try container.decodeIfPresent(Double.self, forKey: .score)
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.container(keyedBy: CodingKeys.self)
+        try container.encode(self.id, forKey: .id)
+        try container.encode(self.name.toString(), forKey: .name)
+        try container.encode(self.score, forKey: .score)
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn codable_struct_named_fields() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a newtype struct is encoded as its single field, following serde's conventions.
+mod codable_newtype_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct SomeStruct(Option<u8>);
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Codable {
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        self._0 = try container.decode(Optional<UInt8>.self)
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encode(self._0)
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn codable_newtype_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[serde(crate = "swift_bridge :: serde")]
+                pub struct SomeStruct(pub Option<u8>);
+            }),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a tuple struct is encoded as an array of its fields, following serde's
+/// conventions.
+mod codable_tuple_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct SomeStruct(u8, String);
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Codable {
+    public init(from decoder: Decoder) throws {
+        var container = try decoder.unkeyedContainer()
+        self._0 = try container.decode(UInt8.self)
+        self._1 = try container.decode(String.self).intoRustString()
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.unkeyedContainer()
+        try container.encode(self._0)
+        try container.encode(self._1.toString())
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn codable_tuple_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[serde(crate = "swift_bridge :: serde")]
+                pub struct SomeStruct(pub u8, pub String);
+            }),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a unit struct is encoded as `nil`, following serde's conventions.
+mod codable_unit_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(Codable)]
+                struct SomeStruct;
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Codable {
+    public init(from decoder: Decoder) throws {
+        _ = try decoder.singleValueContainer()
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encodeNil()
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn codable_unit_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[serde(crate = "swift_bridge :: serde")]
+                pub struct SomeStruct;
+            }),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an enum's variants are encoded as strings, following serde's conventions.
+mod codable_enum {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(Codable)]
+                enum SomeEnum {
+                    Variant1,
+                    Variant2,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(swift_bridge::serde::Serialize, swift_bridge::serde::Deserialize)]
+            #[serde(crate = "swift_bridge :: serde")]
+            pub enum SomeEnum {
+                Variant1,
+                Variant2
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Codable {
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        let variant = try container.decode(String.self)
+        switch variant {
+        case "Variant1":
+            self = .Variant1
+        case "Variant2":
+            self = .Variant2
+        default:
+            throw DecodingError.dataCorruptedError(in: container, debugDescription: "Unknown variant \(variant) of SomeEnum")
+        }
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        switch self {
+        case .Variant1:
+            try container.encode("Variant1")
+        case .Variant2:
+            try container.encode("Variant2")
+        }
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn codable_enum() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we do not generate serde derives or a `Codable` conformance when the attribute
+/// is not present.
+mod not_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                enum SomeEnum {
+                    Variant
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn not_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::DoesNotContain(quote! {
+                serde
+            }),
+            expected_swift_code: ExpectedSwiftCode::DoesNotContainAfterTrim("Codable"),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,246 @@
+//! See also: crates/swift-integration-tests/src/opaque_type_attributes/conversions.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate Swift initializers that call the type's `From` implementations.
+mod from_initializers {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(From(u8), From(&str))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_from_u8"]
+                pub extern "C" fn __swift_bridge__SomeType__from_u8 (value: u8) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(<super::SomeType as From<u8>>::from(value)))
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_from_str"]
+                pub extern "C" fn __swift_bridge__SomeType__from_str (
+                    value: swift_bridge::string::RustStr
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(<super::SomeType as From<&str>>::from(value.to_str())))
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType {
+    public convenience init(_ value: UInt8) {
+        self.init(ptr: __swift_bridge__$SomeType$_from_u8(value))
+    }
+
+    public convenience init<GenericToRustStr: ToRustStr>(_ value: GenericToRustStr) {
+        self.init(ptr: value.toRustStr({ valueAsRustStr -> UnsafeMutableRawPointer in
+            __swift_bridge__$SomeType$_from_str(valueAsRustStr)
+        }))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_from_u8(uint8_t value);
+void* __swift_bridge__$SomeType$_from_str(struct RustStr value);
+"#,
+        )
+    }
+
+    #[test]
+    fn from_initializers() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate failable Swift initializers for `TryFrom` implementations, and
+/// throwing initializers when the `TryFrom::Error` is bridged.
+mod try_from_initializers {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(TryFrom(i64), TryFrom(String, Error = String))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_try_from_i64"]
+                pub extern "C" fn __swift_bridge__SomeType__try_from_i64 (value: i64) -> *mut super::SomeType {
+                    match <super::SomeType as TryFrom<i64>>::try_from(value) {
+                        Ok(v) => Box::into_raw(Box::new(v)),
+                        Err(_) => std::ptr::null_mut()
+                    }
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_try_from_String"]
+                pub extern "C" fn __swift_bridge__SomeType__try_from_String (
+                    value: *mut swift_bridge::string::RustString,
+                    err: *mut *mut std::ffi::c_void
+                ) -> *mut super::SomeType {
+                    match <super::SomeType as TryFrom<String>>::try_from(unsafe { Box::from_raw(value).0 }) {
+                        Ok(v) => Box::into_raw(Box::new(v)),
+                        Err(e) => {
+                            unsafe { *err = swift_bridge::string::RustString(e).box_into_raw() as *mut std::ffi::c_void };
+                            std::ptr::null_mut()
+                        }
+                    }
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType {
+    public convenience init?(_ value: Int64) {
+        guard let ptr = __swift_bridge__$SomeType$_try_from_i64(value) else {
+            return nil
+        }
+        self.init(ptr: ptr)
+    }
+
+    public convenience init<GenericIntoRustString: IntoRustString>(_ value: GenericIntoRustString) throws {
+        var err: UnsafeMutableRawPointer? = nil
+        let ptr = __swift_bridge__$SomeType$_try_from_String({ let rustString = value.intoRustString(); rustString.isOwned = false; return rustString.ptr }(), &err)
+        if let err = err {
+            throw RustString(ptr: err)
+        }
+        self.init(ptr: ptr!)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_try_from_i64(int64_t value);
+void* __swift_bridge__$SomeType$_try_from_String(void* value, void** err);
+"#,
+        )
+    }
+
+    #[test]
+    fn try_from_initializers() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a required `init?(_ description: String)` that calls the type's
+/// `FromStr` implementation, and that a type that is also `CustomStringConvertible` conforms to
+/// `LosslessStringConvertible`.
+mod from_str_initializer {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(CustomStringConvertible, FromStr)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_parse"]
+            pub extern "C" fn __swift_bridge__SomeType__parse (
+                description: swift_bridge::string::RustStr
+            ) -> *mut super::SomeType {
+                match <super::SomeType as std::str::FromStr>::from_str(description.to_str()) {
+                    Ok(v) => Box::into_raw(Box::new(v)),
+                    Err(_) => std::ptr::null_mut()
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class SomeType: SomeTypeRefMut {
+    var isOwned: Bool = true
+
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    public required convenience init?(_ description: String) {
+        guard let ptr = description.toRustStr({ descriptionAsRustStr -> UnsafeMutableRawPointer? in
+            __swift_bridge__$SomeType$_parse(descriptionAsRustStr)
+        }) else {
+            return nil
+        }
+        self.init(ptr: ptr)
+    }
+
+    deinit {
+"#,
+            r#"
+extension SomeType: LosslessStringConvertible {}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_parse(struct RustStr description);
+"#,
+        )
+    }
+
+    #[test]
+    fn from_str_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
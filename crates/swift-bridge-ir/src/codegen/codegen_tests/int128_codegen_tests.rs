@@ -0,0 +1,123 @@
+//! See also: crates/swift-integration-tests/src/int128.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we pass 128 bit integers across the FFI boundary as a pair of 64 bit halves.
+mod extern_rust_fn_u128_and_i128 {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: u128) -> i128;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::int128::RustU128
+            ) -> swift_bridge::int128::RustI128 {
+                swift_bridge::int128::RustI128::from_i128(super::some_function(arg.into_u128()))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: RustU128) -> RustI128 {
+    __swift_bridge__$some_function(arg)
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct RustI128 __swift_bridge__$some_function(struct RustU128 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_u128_and_i128() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass and return an `Option<u128>`.
+mod extern_rust_fn_option_u128 {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Option<u128>) -> Option<u128>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::option::OptionU128
+            ) -> swift_bridge::option::OptionU128 {
+                if let Some(val) = super::some_function(
+                    if arg.is_some { Some(arg.val.into_u128()) } else { None }
+                ) {
+                    swift_bridge::option::OptionU128 {
+                        val: swift_bridge::int128::RustU128::from_u128(val),
+                        is_some: true
+                    }
+                } else {
+                    swift_bridge::option::OptionU128 {
+                        val: swift_bridge::int128::RustU128 { low: 123, high: 0 },
+                        is_some: false
+                    }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Optional<RustU128>) -> Optional<RustU128> {
+    { let val = __swift_bridge__$some_function({ let val = arg; return __private__OptionU128(val: val ?? 123, is_some: val != nil); }()); if val.is_some { return val.val } else { return nil } }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct __private__OptionU128 __swift_bridge__$some_function(struct __private__OptionU128 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_option_u128() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,213 @@
+//! See also: crates/swift-integration-tests/src/iterator.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate `next` and `_free` functions for a function that returns an
+/// `impl Iterator<Item = T>`.
+mod extern_rust_fn_return_impl_iterator {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function () -> impl Iterator<Item = u32>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> *mut std::ffi::c_void {
+                Box::into_raw(Box::new(
+                    Box::new(super::some_function()) as Box<dyn Iterator<Item = u32>>
+                )) as *mut std::ffi::c_void
+            }
+
+            #[export_name = "__swift_bridge__$some_function$next"]
+            pub extern "C" fn __swift_bridge__some_function_next(
+                iterator: *mut std::ffi::c_void
+            ) -> swift_bridge::option::OptionU32 {
+                let iterator = unsafe { &mut *(iterator as *mut Box<dyn Iterator<Item = u32>>) };
+                if let Some(val) = iterator.next() {
+                    swift_bridge::option::OptionU32 { val, is_some: true }
+                } else {
+                    swift_bridge::option::OptionU32 { val: 123, is_some: false }
+                }
+            }
+
+            #[export_name = "__swift_bridge__$some_function$_free"]
+            pub extern "C" fn __swift_bridge__some_function__free(
+                iterator: *mut std::ffi::c_void
+            ) {
+                let iterator = unsafe { Box::from_raw(iterator as *mut Box<dyn Iterator<Item = u32>>) };
+                drop(iterator);
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustIterator<UInt32> {
+    RustIterator(ptr: __swift_bridge__$some_function(), owners: [], next: { iterator in { let val = __swift_bridge__$some_function$next(iterator); if val.is_some { return val.val } else { return nil } }() }, free: { iterator in __swift_bridge__$some_function$_free(iterator) })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(void);
+struct __private__OptionU32 __swift_bridge__$some_function$next(void* iterator);
+void __swift_bridge__$some_function$_free(void* iterator);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_impl_iterator() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that a `Box<dyn Iterator<Item = T>>` does not get boxed up as a trait object a second
+/// time.
+mod extern_rust_fn_return_boxed_dyn_iterator {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function () -> Box<dyn Iterator<Item = String>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> *mut std::ffi::c_void {
+                Box::into_raw(Box::new(super::some_function())) as *mut std::ffi::c_void
+            }
+
+            #[export_name = "__swift_bridge__$some_function$next"]
+            pub extern "C" fn __swift_bridge__some_function_next(
+                iterator: *mut std::ffi::c_void
+            ) -> *mut swift_bridge::string::RustString {
+                let iterator = unsafe { &mut *(iterator as *mut Box<dyn Iterator<Item = String>>) };
+                if let Some(val) = iterator.next() {
+                    swift_bridge::string::RustString(val).box_into_raw()
+                } else {
+                    std::ptr::null::<swift_bridge::string::RustString>() as *mut swift_bridge::string::RustString
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustIterator<RustString> {
+    RustIterator(ptr: __swift_bridge__$some_function(), owners: [], next: { iterator in { let val = __swift_bridge__$some_function$next(iterator); if val != nil { return RustString(ptr: val!) } else { return nil } }() }, free: { iterator in __swift_bridge__$some_function$_free(iterator) })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(void);
+void* __swift_bridge__$some_function$next(void* iterator);
+void __swift_bridge__$some_function$_free(void* iterator);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_boxed_dyn_iterator() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an iterator that is returned from a method keeps the methods's `self` alive,
+/// along with any other opaque Rust types that it might be borrowing from.
+mod extern_rust_method_return_iterator_of_opaque_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Table;
+                    type Row;
+
+                    fn rows(self: &Table, filter: &Row) -> impl Iterator<Item = Row> + '_;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$Table$rows$next"]
+            pub extern "C" fn __swift_bridge__Table_rows_next(
+                iterator: *mut std::ffi::c_void
+            ) -> *mut super::Row {
+                let iterator = unsafe { &mut *(iterator as *mut Box<dyn Iterator<Item = super::Row>>) };
+                if let Some(val) = iterator.next() {
+                    Box::into_raw(Box::new(val))
+                } else {
+                    std::ptr::null_mut()
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension TableRef {
+    public func rows(_ filter: RowRef) -> RustIterator<Row> {
+        RustIterator(ptr: __swift_bridge__$Table$rows(ptr, filter.ptr), owners: [self, filter], next: { iterator in { let val = __swift_bridge__$Table$rows$next(iterator); if val != nil { return Row(ptr: val!) } else { return nil } }() }, free: { iterator in __swift_bridge__$Table$rows$_free(iterator) })
+    }
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void* __swift_bridge__$Table$rows(void* self, void* filter);
+void* __swift_bridge__$Table$rows$next(void* iterator);
+void __swift_bridge__$Table$rows$_free(void* iterator);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_method_return_iterator_of_opaque_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
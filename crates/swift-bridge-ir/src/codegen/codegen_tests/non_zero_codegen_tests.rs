@@ -0,0 +1,179 @@
+//! See also: crates/swift-integration-tests/src/non_zero.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we pass a NonZero integer across the FFI boundary as its underlying integer, and
+/// that Swift checks that the integer isn't zero before passing it to Rust.
+mod extern_rust_fn_non_zero {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: NonZeroU32) -> std::num::NonZeroI64;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: u32) -> i64 {
+                super::some_function(match std::num::NonZeroU32::new(arg) {
+                    Some(val) => val,
+                    None => std::process::abort(),
+                }).get()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: UInt32) -> Int64 {
+    __swift_bridge__$some_function({ let val = arg; precondition(val != 0, "NonZero integers cannot be zero"); return val }())
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+int64_t __swift_bridge__$some_function(uint32_t arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_non_zero() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an `Option<NonZero*>` uses zero to represent `None`, the same way that Rust does.
+mod extern_rust_fn_option_non_zero {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Option<NonZeroU8>) -> Option<NonZeroU8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: u8) -> u8 {
+                if let Some(val) = super::some_function(std::num::NonZeroU8::new(arg)) {
+                    val.get()
+                } else {
+                    0
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Optional<UInt8>) -> Optional<UInt8> {
+    { let val = __swift_bridge__$some_function(arg ?? 0); if val != 0 { return val } else { return nil } }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+uint8_t __swift_bridge__$some_function(uint8_t arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_option_non_zero() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that shared structs can have NonZero integer fields.
+mod shared_struct_non_zero_field {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                struct SomeStruct {
+                    field: std::num::NonZeroU16
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub struct __swift_bridge__SomeStruct {
+                    field: u16
+                }
+            },
+            quote! {
+                __swift_bridge__SomeStruct { field: val.field.get() }
+            },
+            quote! {
+                SomeStruct {
+                    field: match std::num::NonZeroU16::new(val.field) {
+                        Some(val) => val,
+                        None => std::process::abort(),
+                    }
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public struct SomeStruct {
+    var field: UInt16
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+typedef struct __swift_bridge__$SomeStruct { uint16_t field; } __swift_bridge__$SomeStruct;
+    "#,
+    );
+
+    #[test]
+    fn shared_struct_non_zero_field() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
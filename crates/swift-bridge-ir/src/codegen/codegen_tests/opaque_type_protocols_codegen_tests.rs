@@ -0,0 +1,419 @@
+//! See also: crates/swift-integration-tests/src/opaque_type_attributes/protocols.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate an `Equatable` conformance that calls the type's `PartialEq`
+/// implementation.
+mod equatable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Equatable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![quote! {
+                #[export_name = "__swift_bridge__$SomeType$_partial_eq"]
+                pub extern "C" fn __swift_bridge__SomeType__partial_eq (
+                    lhs: *mut super::SomeType,
+                    rhs: *mut super::SomeType
+                ) -> bool {
+                    unsafe { &*lhs == &*rhs }
+                }
+            }],
+            does_not_contain: vec![quote! {
+                __swift_bridge__SomeType__hash
+            }],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef: Equatable {
+    public static func == (lhs: SomeTypeRef, rhs: SomeTypeRef) -> Bool {
+        __swift_bridge__$SomeType$_partial_eq(lhs.ptr, rhs.ptr)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$SomeType$_free(void* self);
+bool __swift_bridge__$SomeType$_partial_eq(void* lhs, void* rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn equatable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a `Hashable` conformance that calls the type's `Hash` implementation,
+/// along with the `Equatable` conformance that `Hashable` requires.
+mod hashable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Hashable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_partial_eq"]
+                pub extern "C" fn __swift_bridge__SomeType__partial_eq (
+                    lhs: *mut super::SomeType,
+                    rhs: *mut super::SomeType
+                ) -> bool {
+                    unsafe { &*lhs == &*rhs }
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_hash"]
+                pub extern "C" fn __swift_bridge__SomeType__hash (
+                    this: *mut super::SomeType
+                ) -> u64 {
+                    use std::hash::{Hash, Hasher};
+
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    unsafe { &*this }.hash(&mut hasher);
+                    hasher.finish()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeTypeRef: Equatable {
+    public static func == (lhs: SomeTypeRef, rhs: SomeTypeRef) -> Bool {
+        __swift_bridge__$SomeType$_partial_eq(lhs.ptr, rhs.ptr)
+    }
+}
+"#,
+            r#"
+extension SomeTypeRef: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(__swift_bridge__$SomeType$_hash(ptr))
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+bool __swift_bridge__$SomeType$_partial_eq(void* lhs, void* rhs);
+uint64_t __swift_bridge__$SomeType$_hash(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn hashable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a `Comparable` conformance that calls the type's `Ord` implementation,
+/// along with the `Equatable` conformance that `Comparable` requires.
+mod comparable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__SomeType__partial_eq
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_cmp"]
+                pub extern "C" fn __swift_bridge__SomeType__cmp (
+                    lhs: *mut super::SomeType,
+                    rhs: *mut super::SomeType
+                ) -> i8 {
+                    std::cmp::Ord::cmp(unsafe { &*lhs }, unsafe { &*rhs }) as i8
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeTypeRef: Equatable {
+"#,
+            r#"
+extension SomeTypeRef: Comparable {
+    public static func < (lhs: SomeTypeRef, rhs: SomeTypeRef) -> Bool {
+        __swift_bridge__$SomeType$_cmp(lhs.ptr, rhs.ptr) < 0
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+bool __swift_bridge__$SomeType$_partial_eq(void* lhs, void* rhs);
+int8_t __swift_bridge__$SomeType$_cmp(void* lhs, void* rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn comparable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate `CustomStringConvertible` and `CustomDebugStringConvertible`
+/// conformances that call the type's `Display` and `Debug` implementations.
+mod custom_string_convertible {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![
+                quote! {
+                    #[export_name = "__swift_bridge__$SomeType$_description"]
+                    pub extern "C" fn __swift_bridge__SomeType__description (
+                        this: *mut super::SomeType
+                    ) -> *mut swift_bridge::string::RustString {
+                        swift_bridge::string::RustString(unsafe { &*this }.to_string()).box_into_raw()
+                    }
+                },
+                quote! {
+                    #[export_name = "__swift_bridge__$SomeType$_debug_description"]
+                    pub extern "C" fn __swift_bridge__SomeType__debug_description (
+                        this: *mut super::SomeType
+                    ) -> *mut swift_bridge::string::RustString {
+                        swift_bridge::string::RustString(format!("{:?}", unsafe { &*this })).box_into_raw()
+                    }
+                },
+            ],
+            does_not_contain: vec![quote! {
+                __swift_bridge__SomeType__partial_eq
+            }],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeTypeRef: CustomStringConvertible {
+    public var description: String {
+        RustString(ptr: __swift_bridge__$SomeType$_description(ptr)).toString()
+    }
+}
+"#,
+            r#"
+extension SomeTypeRef: CustomDebugStringConvertible {
+    public var debugDescription: String {
+        RustString(ptr: __swift_bridge__$SomeType$_debug_description(ptr)).toString()
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_description(void* self);
+void* __swift_bridge__$SomeType$_debug_description(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn custom_string_convertible() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Swift `clone()` method that calls the type's `Clone` implementation.
+mod clone {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Clone)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_clone"]
+            pub extern "C" fn __swift_bridge__SomeType__clone (
+                this: *mut super::SomeType
+            ) -> *mut super::SomeType {
+                Box::into_raw(Box::new(Clone::clone(unsafe { &*this })))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef {
+    public func clone() -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_clone(ptr))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_clone(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn clone() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Swift convenience initializer for an opaque Rust type that uses the
+/// `Default` attribute.
+mod default {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Default)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_default"]
+            pub extern "C" fn __swift_bridge__SomeType__default () -> *mut super::SomeType {
+                Box::into_raw(Box::new(<super::SomeType as Default>::default()))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType {
+    public convenience init() {
+        self.init(ptr: __swift_bridge__$SomeType$_default())
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_default(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn default() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -0,0 +1,256 @@
+//! See also: crates/swift-integration-tests/src/operators.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate operators for an opaque Rust type that call the type's `std::ops`
+/// implementations on references.
+mod opaque_type_operators {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(operators(Add, Neg, Index(Output = f32)))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_add"]
+                pub extern "C" fn __swift_bridge__SomeType__add (
+                    lhs: *mut super::SomeType,
+                    rhs: *mut super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(std::ops::Add::add(unsafe { &*lhs }, unsafe { &*rhs })))
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_neg"]
+                pub extern "C" fn __swift_bridge__SomeType__neg (
+                    this: *mut super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(std::ops::Neg::neg(unsafe { &*this })))
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_index"]
+                pub extern "C" fn __swift_bridge__SomeType__index (
+                    this: *mut super::SomeType,
+                    index: usize
+                ) -> f32 {
+                    *std::ops::Index::index(unsafe { &*this }, index)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef {
+    public static func + (lhs: SomeTypeRef, rhs: SomeTypeRef) -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_add(lhs.ptr, rhs.ptr))
+    }
+
+    public static prefix func - (operand: SomeTypeRef) -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_neg(operand.ptr))
+    }
+
+    public subscript(index: UInt) -> Float {
+        __swift_bridge__$SomeType$_index(self.ptr, index)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_add(void* lhs, void* rhs);
+void* __swift_bridge__$SomeType$_neg(void* self);
+float __swift_bridge__$SomeType$_index(void* self, uintptr_t index);
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_type_operators() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate operators for a `Clone` opaque Rust type that call the type's
+/// `std::ops` implementations on cloned, owned values.
+mod clone_opaque_type_operators {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Clone, operators(Mul, Neg))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_mul"]
+                pub extern "C" fn __swift_bridge__SomeType__mul (
+                    lhs: *mut super::SomeType,
+                    rhs: *mut super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(std::ops::Mul::mul(
+                        Clone::clone(unsafe { &*lhs }),
+                        Clone::clone(unsafe { &*rhs })
+                    )))
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_neg"]
+                pub extern "C" fn __swift_bridge__SomeType__neg (
+                    this: *mut super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new(std::ops::Neg::neg(Clone::clone(unsafe { &*this }))))
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+    public static func * (lhs: SomeTypeRef, rhs: SomeTypeRef) -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_mul(lhs.ptr, rhs.ptr))
+    }
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_mul(void* lhs, void* rhs);
+void* __swift_bridge__$SomeType$_neg(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn clone_opaque_type_operators() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate operators for a shared struct that call the struct's `std::ops`
+/// implementations on owned values.
+mod shared_struct_operators {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", operators(Sub, Mul, Index(Output = u8)))]
+                struct SomeStruct {
+                    field: u8
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$_sub"]
+                pub extern "C" fn __swift_bridge__SomeStruct__sub (
+                    lhs: __swift_bridge__SomeStruct,
+                    rhs: __swift_bridge__SomeStruct
+                ) -> __swift_bridge__SomeStruct {
+                    std::ops::Sub::sub(lhs.into_rust_repr(), rhs.into_rust_repr()).into_ffi_repr()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$_mul"]
+                pub extern "C" fn __swift_bridge__SomeStruct__mul (
+                    lhs: __swift_bridge__SomeStruct,
+                    rhs: __swift_bridge__SomeStruct
+                ) -> __swift_bridge__SomeStruct {
+                    std::ops::Mul::mul(lhs.into_rust_repr(), rhs.into_rust_repr()).into_ffi_repr()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$_index"]
+                pub extern "C" fn __swift_bridge__SomeStruct__index (
+                    this: __swift_bridge__SomeStruct,
+                    index: usize
+                ) -> u8 {
+                    *std::ops::Index::index(&this.into_rust_repr(), index)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct {
+    public static func - (lhs: SomeStruct, rhs: SomeStruct) -> SomeStruct {
+        __swift_bridge__$SomeStruct$_sub(lhs.intoFfiRepr(), rhs.intoFfiRepr()).intoSwiftRepr()
+    }
+
+    public static func * (lhs: SomeStruct, rhs: SomeStruct) -> SomeStruct {
+        __swift_bridge__$SomeStruct$_mul(lhs.intoFfiRepr(), rhs.intoFfiRepr()).intoSwiftRepr()
+    }
+
+    public subscript(index: UInt) -> UInt8 {
+        __swift_bridge__$SomeStruct$_index(self.intoFfiRepr(), index)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+struct __swift_bridge__$SomeStruct __swift_bridge__$SomeStruct$_sub(struct __swift_bridge__$SomeStruct lhs, struct __swift_bridge__$SomeStruct rhs);
+struct __swift_bridge__$SomeStruct __swift_bridge__$SomeStruct$_mul(struct __swift_bridge__$SomeStruct lhs, struct __swift_bridge__$SomeStruct rhs);
+uint8_t __swift_bridge__$SomeStruct$_index(struct __swift_bridge__$SomeStruct self, uintptr_t index);
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_operators() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -0,0 +1,188 @@
+//! See also: crates/swift-integration-tests/src/path.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a `&Path` argument is passed the same way as a `&str` and that a `PathBuf` is
+/// returned the same way as a `String`.
+mod extern_rust_fn_ref_path_arg_path_buf_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: &Path) -> PathBuf;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::string::RustStr
+            ) -> *mut swift_bridge::string::RustString {
+                swift_bridge::string::RustString::from_os_string(
+                    super::some_function(std::path::Path::new(arg.to_os_str()))
+                ).box_into_raw()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function<GenericToRustStr: ToRustStr>(_ arg: GenericToRustStr) -> RustString {
+    return arg.toRustStr({ argAsRustStr in
+        RustString(ptr: __swift_bridge__$some_function(argAsRustStr))
+    })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(struct RustStr arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_ref_path_arg_path_buf_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an owned `OsString` argument is passed the same way as a `String` and that a
+/// returned `&OsStr` points to the raw bytes of the `OsStr`.
+mod extern_rust_fn_os_string_arg_ref_os_str_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: std::ffi::OsString) -> &'static std::ffi::OsStr;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: *mut swift_bridge::string::RustString
+            ) -> swift_bridge::string::RustStr {
+                swift_bridge::string::RustStr::from_os_str(
+                    AsRef::<std::ffi::OsStr>::as_ref(
+                        super::some_function(
+                            std::ffi::OsString::from(unsafe { Box::from_raw(arg).0 })
+                        )
+                    )
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function<GenericIntoRustString: IntoRustString>(_ arg: GenericIntoRustString) -> RustStr {
+    __swift_bridge__$some_function({ let rustString = arg.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct RustStr __swift_bridge__$some_function(void* arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_os_string_arg_ref_os_str_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass an `Option<&Path>` and return an `Option<PathBuf>`.
+mod extern_rust_fn_option_paths {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Option<&Path>) -> Option<PathBuf>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::string::RustStr
+            ) -> *mut swift_bridge::string::RustString {
+                if let Some(val) = super::some_function(
+                    if arg.start.is_null() {
+                        None
+                    } else {
+                        Some(std::path::Path::new(arg.to_os_str()))
+                    }
+                ) {
+                    swift_bridge::string::RustString::from_os_string(val).box_into_raw()
+                } else {
+                    std::ptr::null::<swift_bridge::string::RustString>() as *mut swift_bridge::string::RustString
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function<GenericToRustStr: ToRustStr>(_ arg: Optional<GenericToRustStr>) -> Optional<RustString> {
+    return optionalRustStrToRustStr(arg, { argAsRustStr in
+        { let val = __swift_bridge__$some_function(argAsRustStr); if val != nil { return RustString(ptr: val!) } else { return nil } }()
+    })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(struct RustStr arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_option_paths() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
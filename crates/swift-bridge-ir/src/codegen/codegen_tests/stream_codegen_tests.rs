@@ -0,0 +1,209 @@
+//! See also: crates/swift-integration-tests/src/stream.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate `next` and `_free` functions for a function that returns an
+/// `impl Stream<Item = T>`.
+mod extern_rust_fn_return_impl_stream {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function () -> impl Stream<Item = u32>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> *mut std::ffi::c_void {
+                swift_bridge::async_support::box_stream(super::some_function())
+            }
+
+            #[export_name = "__swift_bridge__$some_function$next"]
+            pub extern "C" fn __swift_bridge__some_function_next(
+                stream: *mut std::ffi::c_void,
+                cancellation_token: *mut std::ffi::c_void,
+                callback_wrapper: *mut std::ffi::c_void,
+                on_item: extern "C" fn(*mut std::ffi::c_void, u32) -> (),
+                on_end: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+            ) {
+                let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
+                let next = unsafe { swift_bridge::async_support::stream_next::<u32>(stream) };
+                let task = async move {
+                    let item = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(next)).await;
+
+                    let callback_wrapper = callback_wrapper.into_raw();
+
+                    match item {
+                        Some(Ok(Some(item))) => (on_item)(callback_wrapper, item),
+                        Some(Ok(None)) => (on_end)(callback_wrapper),
+                        Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                        None => (on_cancelled)(callback_wrapper, false),
+                    }
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
+            }
+
+            #[export_name = "__swift_bridge__$some_function$_free"]
+            pub extern "C" fn __swift_bridge__some_function__free(stream: *mut std::ffi::c_void) {
+                unsafe { swift_bridge::async_support::stream_free::<u32>(stream) }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustStream<UInt32> {
+    RustStream(ptr: __swift_bridge__$some_function(), next: { stream, cancellationToken, cbWrapperPtr in __swift_bridge__$some_function$next(stream, cancellationToken, cbWrapperPtr, { cbWrapperPtr, item in RustStreamCbWrapper<UInt32>.complete(cbWrapperPtr, .success(item)) }, { cbWrapperPtr in RustStreamCbWrapper<UInt32>.complete(cbWrapperPtr, .success(nil)) }, { cbWrapperPtr, message in RustStreamCbWrapper<UInt32>.complete(cbWrapperPtr, .failure(RustPanic(message: RustString(ptr: message!).toString()))) }, { cbWrapperPtr, runtimeShutDown in RustStreamCbWrapper<UInt32>.complete(cbWrapperPtr, .failure(rustTaskDroppedError(runtimeShutDown))) }) }, free: { stream in __swift_bridge__$some_function$_free(stream) })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#include <stdint.h>
+void* __swift_bridge__$some_function(void);
+void __swift_bridge__$some_function$next(void* stream, void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$next$item(void* callback_wrapper, uint32_t item), void __swift_bridge__$some_function$next$end(void* callback_wrapper), void __swift_bridge__$some_function$next$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$next$cancelled(void* callback_wrapper, bool runtime_shut_down));
+void __swift_bridge__$some_function$_free(void* stream);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_impl_stream() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that a `Pin<Box<dyn Stream<Item = T> + Send>>` does not get boxed up as a trait object
+/// a second time.
+mod extern_rust_fn_return_pinned_boxed_dyn_stream {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function () -> Pin<Box<dyn Stream<Item = String> + Send>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function() -> *mut std::ffi::c_void {
+                    Box::into_raw(Box::new(super::some_function())) as *mut std::ffi::c_void
+                }
+            },
+            quote! {
+                on_item: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+            },
+            quote! {
+                Some(Ok(Some(item))) => (on_item)(callback_wrapper, swift_bridge::string::RustString(item).box_into_raw()),
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustStream<RustString> {
+    RustStream(ptr: __swift_bridge__$some_function(), next: { stream, cancellationToken, cbWrapperPtr in __swift_bridge__$some_function$next(stream, cancellationToken, cbWrapperPtr, { cbWrapperPtr, item in RustStreamCbWrapper<RustString>.complete(cbWrapperPtr, .success(RustString(ptr: item!))) }, { cbWrapperPtr in RustStreamCbWrapper<RustString>.complete(cbWrapperPtr, .success(nil)) }, { cbWrapperPtr, message in RustStreamCbWrapper<RustString>.complete(cbWrapperPtr, .failure(RustPanic(message: RustString(ptr: message!).toString()))) }, { cbWrapperPtr, runtimeShutDown in RustStreamCbWrapper<RustString>.complete(cbWrapperPtr, .failure(rustTaskDroppedError(runtimeShutDown))) }) }, free: { stream in __swift_bridge__$some_function$_free(stream) })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(void);
+void __swift_bridge__$some_function$next(void* stream, void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$next$item(void* callback_wrapper, void* item), void __swift_bridge__$some_function$next$end(void* callback_wrapper), void __swift_bridge__$some_function$next$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$next$cancelled(void* callback_wrapper, bool runtime_shut_down));
+void __swift_bridge__$some_function$_free(void* stream);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_pinned_boxed_dyn_stream() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that the `UInt32` that Rust hands to Swift for each `char` item gets converted into a
+/// `Unicode.Scalar`.
+mod extern_rust_fn_return_char_stream {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function () -> impl Stream<Item = char>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                on_item: extern "C" fn(*mut std::ffi::c_void, u32) -> (),
+            },
+            quote! {
+                Some(Ok(Some(item))) => (on_item)(callback_wrapper, u32::from(item)),
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustStream<Unicode.Scalar> {
+    RustStream(ptr: __swift_bridge__$some_function(), next: { stream, cancellationToken, cbWrapperPtr in __swift_bridge__$some_function$next(stream, cancellationToken, cbWrapperPtr, { cbWrapperPtr, item in RustStreamCbWrapper<Unicode.Scalar>.complete(cbWrapperPtr, .success(Unicode.Scalar(item)!)) }, { cbWrapperPtr in RustStreamCbWrapper<Unicode.Scalar>.complete(cbWrapperPtr, .success(nil)) }, { cbWrapperPtr, message in RustStreamCbWrapper<Unicode.Scalar>.complete(cbWrapperPtr, .failure(RustPanic(message: RustString(ptr: message!).toString()))) }, { cbWrapperPtr, runtimeShutDown in RustStreamCbWrapper<Unicode.Scalar>.complete(cbWrapperPtr, .failure(rustTaskDroppedError(runtimeShutDown))) }) }, free: { stream in __swift_bridge__$some_function$_free(stream) })
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void __swift_bridge__$some_function$next(void* stream, void* cancellation_token, void* callback_wrapper, void __swift_bridge__$some_function$next$item(void* callback_wrapper, uint32_t item), void __swift_bridge__$some_function$next$end(void* callback_wrapper), void __swift_bridge__$some_function$next$panic(void* callback_wrapper, void* message), void __swift_bridge__$some_function$next$cancelled(void* callback_wrapper, bool runtime_shut_down));
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_char_stream() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
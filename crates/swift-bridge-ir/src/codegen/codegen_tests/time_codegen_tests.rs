@@ -0,0 +1,241 @@
+//! See also: crates/swift-integration-tests/src/time.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we pass `Duration` and `SystemTime` across the FFI boundary as seconds plus
+/// nanoseconds, and that they become a `TimeInterval` and a `Date` on the Swift side.
+mod extern_rust_fn_duration_and_system_time {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Duration) -> std::time::SystemTime;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::time::FfiDuration
+            ) -> swift_bridge::time::FfiSystemTime {
+                swift_bridge::time::FfiSystemTime::from_system_time(
+                    super::some_function(arg.into_duration())
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ExactAfterTrim(
+            r#"
+import Foundation
+public func some_function(_ arg: TimeInterval) -> Date {
+    __swift_bridge__$some_function(arg.toFfiDuration()).toDate()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct __private__FfiSystemTime __swift_bridge__$some_function(struct __private__FfiDuration arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_duration_and_system_time() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass and return an `Option<Duration>` and an `Option<SystemTime>`.
+mod extern_rust_fn_option_duration_and_system_time {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Option<Duration>) -> Option<SystemTime>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::option::OptionDuration
+            ) -> swift_bridge::option::OptionSystemTime {
+                if let Some(val) = super::some_function(
+                    if arg.is_some { Some(arg.val.into_duration()) } else { None }
+                ) {
+                    swift_bridge::option::OptionSystemTime {
+                        val: swift_bridge::time::FfiSystemTime::from_system_time(val),
+                        is_some: true
+                    }
+                } else {
+                    swift_bridge::option::OptionSystemTime {
+                        val: swift_bridge::time::FfiSystemTime { secs: 0, nanos: 0 },
+                        is_some: false
+                    }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Optional<TimeInterval>) -> Optional<Date> {
+    { let val = __swift_bridge__$some_function({ let val = arg; return __private__OptionDuration(val: val?.toFfiDuration() ?? __private__FfiDuration(), is_some: val != nil); }()); if val.is_some { return val.val.toDate() } else { return nil } }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+struct __private__OptionSystemTime __swift_bridge__$some_function(struct __private__OptionDuration arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_option_duration_and_system_time() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift functions that take a `Duration` receive a `TimeInterval`.
+mod extern_swift_fn_duration {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function (arg: Duration) -> SystemTime;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function(arg: std::time::Duration) -> std::time::SystemTime {
+                unsafe {
+                    __swift_bridge__some_function(
+                        swift_bridge::time::FfiDuration::from_duration(arg)
+                    )
+                }.into_system_time()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: __private__FfiDuration) -> __private__FfiSystemTime {
+    some_function(arg: arg.toTimeInterval()).toFfiSystemTime()
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_duration() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that shared structs can have `Duration` and `SystemTime` fields, and that we import
+/// Foundation for them.
+mod shared_struct_time_fields {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                struct SomeStruct {
+                    timeout: std::time::Duration,
+                    created_at: std::time::SystemTime
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub struct __swift_bridge__SomeStruct {
+                    timeout: swift_bridge::time::FfiDuration,
+                    created_at: swift_bridge::time::FfiSystemTime
+                }
+            },
+            quote! {
+                SomeStruct {
+                    timeout: val.timeout.into_duration(),
+                    created_at: val.created_at.into_system_time()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+import Foundation
+public struct SomeStruct {
+    var timeout: TimeInterval
+    var created_at: Date
+"#,
+            r#"
+return __swift_bridge__$SomeStruct(timeout: val.timeout.toFfiDuration(), created_at: val.created_at.toFfiSystemTime());
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+typedef struct __swift_bridge__$SomeStruct { struct __private__FfiDuration timeout; struct __private__FfiSystemTime created_at; } __swift_bridge__$SomeStruct;
+    "#,
+    );
+
+    #[test]
+    fn shared_struct_time_fields() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,431 @@
+//! See also: crates/swift-integration-tests/src/traits.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a Rust trait and a Swift protocol for a trait that is declared in the
+/// bridge module, along with a class that wraps a `Box<dyn Trait>` and calls its vtable.
+mod trait_declaration {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                trait SomeTrait {
+                    fn some_method(&self, arg: u8) -> u16;
+                    fn another_method(&mut self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub trait SomeTrait {
+                    fn some_method(&self, arg: u8) -> u16;
+                    fn another_method(&mut self);
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeTrait$some_method"]
+                pub extern "C" fn __swift_bridge__SomeTrait_some_method (
+                    this: *mut std::ffi::c_void,
+                    arg: u8
+                ) -> u16 {
+                    SomeTrait::some_method(unsafe { &**(this as *mut Box<dyn SomeTrait>) }, arg)
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeTrait$another_method"]
+                pub extern "C" fn __swift_bridge__SomeTrait_another_method (
+                    this: *mut std::ffi::c_void
+                ) {
+                    SomeTrait::another_method(unsafe { &mut **(this as *mut Box<dyn SomeTrait>) })
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeTrait$_free"]
+                pub extern "C" fn __swift_bridge__SomeTrait__free (this: *mut std::ffi::c_void) {
+                    let this = unsafe { Box::from_raw(this as *mut Box<dyn SomeTrait>) };
+                    drop(this);
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public protocol SomeTrait {
+    func some_method(_ arg: UInt8) -> UInt16
+    func another_method()
+}
+public class RustSomeTrait: SomeTrait {
+    var ptr: UnsafeMutableRawPointer
+    var isOwned: Bool = true
+
+    public init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        if isOwned {
+            __swift_bridge__$SomeTrait$_free(ptr)
+        }
+    }
+}
+extension RustSomeTrait {
+    public func some_method(_ arg: UInt8) -> UInt16 {
+        __swift_bridge__$SomeTrait$some_method(ptr, arg)
+    }
+
+    public func another_method() {
+        __swift_bridge__$SomeTrait$another_method(ptr)
+    }
+}
+"#,
+            r#"
+extension RustSomeTrait {
+    static func intoFfiRepr(_ value: any SomeTrait) -> UnsafeMutableRawPointer {
+        if let value = value as? RustSomeTrait {
+            value.isOwned = false
+            return value.ptr
+        }
+        return __swift_bridge__$SomeTrait$_from_swift(Unmanaged.passRetained(__private__SwiftSomeTrait(value)).toOpaque())
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uint16_t __swift_bridge__$SomeTrait$some_method(void* self, uint8_t arg);
+void __swift_bridge__$SomeTrait$another_method(void* self);
+void __swift_bridge__$SomeTrait$_free(void* self);
+void* __swift_bridge__$SomeTrait$_from_swift(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn trait_declaration() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that an `impl SomeTrait for SomeType {}` marker makes the opaque type's Swift class
+/// conform to the trait's protocol, and lets the type be converted into a trait object.
+mod trait_implementor {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                }
+
+                trait SomeTrait {
+                    fn some_method(&self) -> String;
+                    fn another_method(&mut self, arg: String);
+                }
+
+                impl SomeTrait for SomeType {}
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$SomeTrait$some_method"]
+                pub extern "C" fn __swift_bridge__SomeType_SomeTrait_some_method (
+                    this: *mut super::SomeType
+                ) -> *mut swift_bridge::string::RustString {
+                    swift_bridge::string::RustString(
+                        <super::SomeType as SomeTrait>::some_method(unsafe { &*this })
+                    ).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$SomeTrait$another_method"]
+                pub extern "C" fn __swift_bridge__SomeType_SomeTrait_another_method (
+                    this: *mut super::SomeType,
+                    arg: *mut swift_bridge::string::RustString
+                ) {
+                    <super::SomeType as SomeTrait>::another_method(
+                        unsafe { &mut *this },
+                        unsafe { Box::from_raw(arg).0 }
+                    )
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$SomeTrait$_into_dyn"]
+                pub extern "C" fn __swift_bridge__SomeType_SomeTrait__into_dyn (
+                    this: *mut super::SomeType
+                ) -> *mut std::ffi::c_void {
+                    let this: Box<dyn SomeTrait> = unsafe { Box::from_raw(this) };
+                    Box::into_raw(Box::new(this)) as *mut std::ffi::c_void
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public protocol SomeTrait {
+    func some_method() -> RustString
+    func another_method(_ arg: RustString)
+}
+"#,
+            r#"
+extension RustSomeTrait {
+    public func some_method() -> RustString {
+        RustString(ptr: __swift_bridge__$SomeTrait$some_method(ptr))
+    }
+
+    public func another_method(_ arg: RustString) {
+        __swift_bridge__$SomeTrait$another_method(ptr, { let rustString = arg.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+    }
+
+    public func another_method<GenericIntoRustString: IntoRustString>(_ arg: GenericIntoRustString) {
+        __swift_bridge__$SomeTrait$another_method(ptr, { let rustString = arg.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+    }
+}
+"#,
+            r#"
+        if let value = value as? SomeType {
+            value.isOwned = false
+            return __swift_bridge__$SomeType$SomeTrait$_into_dyn(value.ptr)
+        }
+"#,
+            r#"
+extension SomeTypeRef {
+    public func some_method() -> RustString {
+        RustString(ptr: __swift_bridge__$SomeType$SomeTrait$some_method(ptr))
+    }
+}
+extension SomeTypeRefMut {
+    public func another_method(_ arg: RustString) {
+        __swift_bridge__$SomeType$SomeTrait$another_method(ptr, { let rustString = arg.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+    }
+}
+extension SomeType: SomeTrait {}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$SomeTrait$some_method(void* self);
+void __swift_bridge__$SomeType$SomeTrait$another_method(void* self, void* arg);
+void* __swift_bridge__$SomeType$SomeTrait$_into_dyn(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn trait_implementor() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that `Box<dyn SomeTrait>` arguments and returns become `any SomeTrait` in Swift.
+mod boxed_trait_object_argument_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                trait SomeTrait {
+                    fn some_method(&self);
+                }
+
+                extern "Rust" {
+                    fn some_function(arg: Box<dyn SomeTrait>) -> Box<dyn SomeTrait>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function (
+                arg: *mut std::ffi::c_void
+            ) -> *mut std::ffi::c_void {
+                Box::into_raw(Box::new(
+                    super::some_function(unsafe { *Box::from_raw(arg as *mut Box<dyn SomeTrait>) })
+                )) as *mut std::ffi::c_void
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: any SomeTrait) -> any SomeTrait {
+    RustSomeTrait(ptr: __swift_bridge__$some_function(RustSomeTrait.intoFfiRepr(arg)))
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(void* arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn boxed_trait_object_argument_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Rust struct that implements a trait by calling a retained Swift
+/// value through a vtable of `@_cdecl` Swift functions, so that Swift values can be passed to Rust
+/// as a `Box<dyn Trait>`.
+mod swift_implemented_trait_object {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                trait SomeTrait {
+                    fn some_method(&self, arg: u8) -> String;
+                    fn another_method(&mut self, arg: String);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                pub struct __swift_bridge__SwiftSomeTrait(*mut std::ffi::c_void);
+
+                impl SomeTrait for __swift_bridge__SwiftSomeTrait {
+                    fn some_method(&self, arg: u8) -> String {
+                        unsafe {
+                            Box::from_raw(unsafe { __swift_bridge__SwiftSomeTrait_some_method(self.0, arg) }).0
+                        }
+                    }
+
+                    fn another_method(&mut self, arg: String) {
+                        unsafe {
+                            __swift_bridge__SwiftSomeTrait_another_method(
+                                self.0,
+                                swift_bridge::string::RustString(arg).box_into_raw()
+                            )
+                        }
+                    }
+                }
+
+                impl Drop for __swift_bridge__SwiftSomeTrait {
+                    fn drop (&mut self) {
+                        unsafe { __swift_bridge__SwiftSomeTrait__free(self.0) }
+                    }
+                }
+
+                #[export_name = "__swift_bridge__$SomeTrait$_from_swift"]
+                pub extern "C" fn __swift_bridge__SomeTrait__from_swift (
+                    this: *mut std::ffi::c_void
+                ) -> *mut std::ffi::c_void {
+                    let this: Box<dyn SomeTrait> = Box::new(__swift_bridge__SwiftSomeTrait(this));
+                    Box::into_raw(Box::new(this)) as *mut std::ffi::c_void
+                }
+            },
+            quote! {
+                extern "C" {
+                    #[link_name = "__swift_bridge__$SwiftSomeTrait$some_method"]
+                    fn __swift_bridge__SwiftSomeTrait_some_method(
+                        this: *mut std::ffi::c_void,
+                        arg: u8
+                    ) -> *mut swift_bridge::string::RustString;
+
+                    #[link_name = "__swift_bridge__$SwiftSomeTrait$another_method"]
+                    fn __swift_bridge__SwiftSomeTrait_another_method(
+                        this: *mut std::ffi::c_void,
+                        arg: *mut swift_bridge::string::RustString
+                    );
+
+                    #[link_name = "__swift_bridge__$SwiftSomeTrait$_free"]
+                    fn __swift_bridge__SwiftSomeTrait__free(this: *mut std::ffi::c_void);
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+class __private__SwiftSomeTrait {
+    let value: any SomeTrait
+
+    init(_ value: any SomeTrait) {
+        self.value = value
+    }
+}
+@_cdecl("__swift_bridge__$SwiftSomeTrait$some_method")
+func __swift_bridge__SwiftSomeTrait_some_method (_ this: UnsafeMutableRawPointer, _ arg: UInt8) -> UnsafeMutableRawPointer {
+    { let rustString = Unmanaged<__private__SwiftSomeTrait>.fromOpaque(this).takeUnretainedValue().value.some_method(arg).intoRustString(); rustString.isOwned = false; return rustString.ptr }()
+}
+@_cdecl("__swift_bridge__$SwiftSomeTrait$another_method")
+func __swift_bridge__SwiftSomeTrait_another_method (_ this: UnsafeMutableRawPointer, _ arg: UnsafeMutableRawPointer) {
+    Unmanaged<__private__SwiftSomeTrait>.fromOpaque(this).takeUnretainedValue().value.another_method(RustString(ptr: arg))
+}
+@_cdecl("__swift_bridge__$SwiftSomeTrait$_free")
+func __swift_bridge__SwiftSomeTrait__free (ptr: UnsafeMutableRawPointer) {
+    let _ = Unmanaged<__private__SwiftSomeTrait>.fromOpaque(ptr).takeRetainedValue()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeTrait$_from_swift(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn swift_implemented_trait_object() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
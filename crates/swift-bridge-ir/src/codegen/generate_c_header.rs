@@ -2,17 +2,22 @@
 
 use crate::bridged_type::{BridgedType, StdLibType, StructFields};
 use crate::codegen::CodegenConfig;
-use crate::parse::{SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
+use crate::parse::{
+    OpaqueForeignTypeDeclaration, Operators, SharedTypeDeclaration, TraitDeclaration, TraitMethod,
+    TypeDeclaration, TypeDeclarations,
+};
 use crate::parsed_extern_fn::ParsedExternFn;
 use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
 use std::collections::{BTreeSet, HashSet};
-use syn::ReturnType;
+use syn::{ReturnType, Type};
 
 const NOTICE: &'static str = "// File automatically generated by swift-bridge.";
 
 struct Bookkeeping {
     includes: BTreeSet<&'static str>,
     slice_types: HashSet<String>,
+    /// The `[T; N]` FFI structs that have already been declared.
+    array_types: HashSet<String>,
 }
 
 impl SwiftBridgeModule {
@@ -38,6 +43,7 @@ impl SwiftBridgeModule {
             // TODO: Delete this.
             //  Don't think we're using it.
             slice_types: HashSet::new(),
+            array_types: HashSet::new(),
         };
 
         for ty in self.types.types() {
@@ -73,6 +79,7 @@ impl SwiftBridgeModule {
                                         if let Some(include) = ty.c_include() {
                                             bookkeeping.includes.insert(include);
                                         }
+                                        header += &declare_array_type(&ty, &mut bookkeeping);
 
                                         let name = field.swift_name_string();
 
@@ -86,6 +93,7 @@ impl SwiftBridgeModule {
                                         if let Some(include) = ty.c_include() {
                                             bookkeeping.includes.insert(include);
                                         }
+                                        header += &declare_array_type(&ty, &mut bookkeeping);
 
                                         let name = format!("_{}", idx);
 
@@ -123,6 +131,22 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
 
                         header += &ty_decl;
                         header += "\n";
+
+                        header += &declare_operator_functions(
+                            &ty_struct.operators,
+                            &format!("struct {}", ffi_name),
+                            |fn_name| ty_struct.operator_fn_ffi_name(fn_name),
+                            &self.types,
+                            &mut bookkeeping,
+                        );
+
+                        if ty_struct.default && !ty_struct.fields.is_empty() {
+                            header += &format!(
+                                "struct {} {}(void);\n",
+                                ffi_name,
+                                ty_struct.operator_fn_ffi_name("_default")
+                            );
+                        }
                     }
                     SharedTypeDeclaration::Enum(ty_enum) => {
                         let ffi_name = ty_enum.ffi_name_string();
@@ -205,6 +229,16 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
 
                         header += &drop_ty;
                         header += "\n";
+
+                        header += &declare_protocol_conformance_functions(ty, &mut bookkeeping);
+                        header += &declare_operator_functions(
+                            &ty.attributes.operators,
+                            "void*",
+                            |fn_name| ty.protocol_fn_ffi_name(fn_name),
+                            &self.types,
+                            &mut bookkeeping,
+                        );
+                        header += &declare_conversion_functions(ty, &self.types, &mut bookkeeping);
                     }
 
                     // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
@@ -221,6 +255,8 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
         }
 
         for function in self.functions.iter() {
+            header += &declare_func_array_types(function, &mut bookkeeping, &self.types);
+
             if function.host_lang.is_swift() {
                 continue;
             }
@@ -228,6 +264,10 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
             header += &declare_func(&function, &mut bookkeeping, &self.types);
         }
 
+        for trait_declaration in self.types.traits() {
+            header += &declare_trait_functions(trait_declaration, &self.types, &mut bookkeeping);
+        }
+
         for slice_ty in bookkeeping.slice_types.iter() {
             header = format!(
                 r#"typedef struct FfiSlice_{slice_ty} {{ {slice_ty}* start; uintptr_t len; }} FfiSlice_{slice_ty};
@@ -267,12 +307,272 @@ void* __swift_bridge__$Vec_{ty_name}$as_ptr(void* vec_ptr);
     )
 }
 
+/// Declare the struct that is used to pass a `[T; N]` over FFI, if it hasn't already been declared.
+fn declare_array_type(ty: &BridgedType, bookkeeping: &mut Bookkeeping) -> String {
+    match ty {
+        BridgedType::StdLib(StdLibType::Array(array)) => {
+            if !bookkeeping.array_types.insert(array.ffi_name_string()) {
+                return "".to_string();
+            }
+            if let Some(include) = array.ty.c_include() {
+                bookkeeping.includes.insert(include);
+            }
+
+            format!("{}\n", array.c_typedef())
+        }
+        _ => "".to_string(),
+    }
+}
+
+/// Declare the structs for any `[T; N]` arguments or return values.
+///
+/// Functions in both `extern "Rust"` and `extern "Swift"` blocks need these since Swift sees the
+/// C struct on both sides of the FFI boundary.
+fn declare_func_array_types(
+    func: &ParsedExternFn,
+    bookkeeping: &mut Bookkeeping,
+    types: &TypeDeclarations,
+) -> String {
+    let mut array_types = "".to_string();
+
+    if let Some(ret) = BridgedType::new_with_return_type(&func.sig.output, types) {
+        array_types += &declare_array_type(&ret, bookkeeping);
+    }
+    for arg in func.sig.inputs.iter() {
+        if let Some(arg) = BridgedType::new_with_fn_arg(arg, types) {
+            array_types += &declare_array_type(&arg, bookkeeping);
+        }
+    }
+
+    array_types
+}
+
+/// Declare the functions that the Swift class of an opaque Rust type uses to implement protocols
+/// such as `Equatable` and `Hashable`.
+fn declare_protocol_conformance_functions(
+    ty: &OpaqueForeignTypeDeclaration,
+    bookkeeping: &mut Bookkeeping,
+) -> String {
+    let mut declarations = "".to_string();
+
+    if ty.attributes.is_equatable() {
+        bookkeeping.includes.insert("stdbool.h");
+        declarations += &format!(
+            "bool {}(void* lhs, void* rhs);\n",
+            ty.protocol_fn_ffi_name("_partial_eq")
+        );
+    }
+
+    if ty.attributes.hashable {
+        bookkeeping.includes.insert("stdint.h");
+        declarations += &format!(
+            "uint64_t {}(void* self);\n",
+            ty.protocol_fn_ffi_name("_hash")
+        );
+    }
+
+    if ty.attributes.comparable {
+        bookkeeping.includes.insert("stdint.h");
+        declarations += &format!(
+            "int8_t {}(void* lhs, void* rhs);\n",
+            ty.protocol_fn_ffi_name("_cmp")
+        );
+    }
+
+    if ty.attributes.custom_string_convertible {
+        declarations += &format!(
+            "void* {}(void* self);\n",
+            ty.protocol_fn_ffi_name("_description")
+        );
+    }
+
+    if ty.attributes.custom_debug_string_convertible {
+        declarations += &format!(
+            "void* {}(void* self);\n",
+            ty.protocol_fn_ffi_name("_debug_description")
+        );
+    }
+
+    if ty.attributes.clone {
+        declarations += &format!("void* {}(void* self);\n", ty.protocol_fn_ffi_name("_clone"));
+    }
+
+    if ty.attributes.default {
+        declarations += &format!("void* {}(void);\n", ty.protocol_fn_ffi_name("_default"));
+    }
+
+    declarations
+}
+
+/// Declare the functions that a type's Swift representation uses to implement its operators.
+///
+/// `c_ty` is the C type that the operands and results are passed as.
+/// Declare the functions that the Swift class of an opaque Rust type uses to implement its
+/// `From`, `TryFrom` and `FromStr` initializers.
+fn declare_conversion_functions(
+    ty: &OpaqueForeignTypeDeclaration,
+    types: &TypeDeclarations,
+    bookkeeping: &mut Bookkeeping,
+) -> String {
+    let mut declarations = "".to_string();
+
+    let mut conversions: Vec<(String, &Type, &str)> = vec![];
+    for from_ty in ty.attributes.from.iter() {
+        conversions.push((ty.conversion_fn_name("_from", from_ty), from_ty, ""));
+    }
+    for try_from in ty.attributes.try_from.iter() {
+        let err_param = if try_from.error.is_some() {
+            ", void** err"
+        } else {
+            ""
+        };
+        conversions.push((
+            ty.conversion_fn_name("_try_from", &try_from.ty),
+            &try_from.ty,
+            err_param,
+        ));
+    }
+
+    for (fn_name, from_ty, err_param) in conversions {
+        let from_ty = BridgedType::new_with_type(from_ty, types).unwrap();
+        if let Some(include) = from_ty.c_include() {
+            bookkeeping.includes.insert(include);
+        }
+
+        declarations += &format!(
+            "void* {name}({from_ty} value{err_param});\n",
+            name = ty.protocol_fn_ffi_name(&fn_name),
+            from_ty = from_ty.to_c(),
+            err_param = err_param
+        );
+    }
+
+    if ty.attributes.from_str {
+        declarations += &format!(
+            "void* {name}(struct RustStr description);\n",
+            name = ty.protocol_fn_ffi_name("_parse")
+        );
+    }
+
+    declarations
+}
+
+fn declare_operator_functions(
+    operators: &Operators,
+    c_ty: &str,
+    ffi_name: impl Fn(&str) -> String,
+    types: &TypeDeclarations,
+    bookkeeping: &mut Bookkeeping,
+) -> String {
+    let mut declarations = "".to_string();
+
+    for operator in operators.binary_operators() {
+        declarations += &format!(
+            "{c_ty} {name}({c_ty} lhs, {c_ty} rhs);\n",
+            c_ty = c_ty,
+            name = ffi_name(&format!("_{}", operator.method))
+        );
+    }
+
+    if operators.neg {
+        declarations += &format!(
+            "{c_ty} {name}({c_ty} self);\n",
+            c_ty = c_ty,
+            name = ffi_name("_neg")
+        );
+    }
+
+    if let Some(output) = operators.index.as_ref() {
+        let output = BridgedType::new_with_type(output, types).unwrap();
+
+        bookkeeping.includes.insert("stdint.h");
+        if let Some(include) = output.c_include() {
+            bookkeeping.includes.insert(include);
+        }
+
+        declarations += &format!(
+            "{output} {name}({c_ty} self, uintptr_t index);\n",
+            output = output.to_c(),
+            name = ffi_name("_index"),
+            c_ty = c_ty
+        );
+    }
+
+    declarations
+}
+
+/// Declare the functions that make up a trait object's vtable, along with the functions that the
+/// Swift classes of the trait's implementors use to call their implementations.
+///
+/// The `@_cdecl` functions that Rust uses to call Swift values are not declared, since Swift
+/// already knows about them.
+fn declare_trait_functions(
+    trait_declaration: &TraitDeclaration,
+    types: &TypeDeclarations,
+    bookkeeping: &mut Bookkeeping,
+) -> String {
+    let mut declarations = "".to_string();
+
+    let mut declare_method = |method: &TraitMethod, name: String| {
+        let mut params = vec!["void* self".to_string()];
+        for (arg_name, ty) in method.args.iter() {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            if let Some(include) = ty.c_include() {
+                bookkeeping.includes.insert(include);
+            }
+            params.push(format!("{} {}", ty.to_c(), arg_name));
+        }
+
+        let ret = match &method.output {
+            ReturnType::Default => "void".to_string(),
+            ReturnType::Type(_, ty) => {
+                let ty = BridgedType::new_with_type(ty, types).unwrap();
+                if let Some(include) = ty.c_include() {
+                    bookkeeping.includes.insert(include);
+                }
+                ty.to_c()
+            }
+        };
+
+        format!("{} {}({});\n", ret, name, params.join(", "))
+    };
+
+    for method in trait_declaration.methods.iter() {
+        declarations += &declare_method(
+            method,
+            trait_declaration.vtable_fn_ffi_name(&method.name.to_string()),
+        );
+    }
+    declarations += &format!(
+        "void {}(void* self);\n",
+        trait_declaration.vtable_fn_ffi_name("_free")
+    );
+    declarations += &format!(
+        "void* {}(void* self);\n",
+        trait_declaration.vtable_fn_ffi_name("_from_swift")
+    );
+
+    for implementor in trait_declaration.implementors.iter() {
+        for method in trait_declaration.methods.iter() {
+            declarations += &declare_method(
+                method,
+                trait_declaration.implementor_fn_ffi_name(implementor, &method.name.to_string()),
+            );
+        }
+        declarations += &format!(
+            "void* {}(void* self);\n",
+            trait_declaration.implementor_fn_ffi_name(implementor, "_into_dyn")
+        );
+    }
+
+    declarations
+}
+
 fn declare_func(
     func: &ParsedExternFn,
     bookkeeping: &mut Bookkeeping,
     types: &TypeDeclarations,
 ) -> String {
-    let ret = func.to_c_header_return(types);
     let name = func.link_name();
     let params = func.to_c_header_params(types);
 
@@ -292,12 +592,32 @@ fn declare_func(
 
     let declaration = if func.sig.asyncness.is_some() {
         let maybe_ret = BridgedType::new_with_return_type(&func.sig.output, types).unwrap();
+
+        // The `Err` of a `Result<T, E>` gets passed to a separate callback.
+        let (maybe_ret, maybe_on_error) = match maybe_ret {
+            BridgedType::StdLib(StdLibType::Result(result)) => (
+                *result.ok_ty,
+                format!(
+                    ", void {name}$err(void* callback_wrapper, {err} err)",
+                    name = name,
+                    err = result.err_ty.to_c()
+                ),
+            ),
+            ret => (ret, "".to_string()),
+        };
+
         let maybe_ret = if maybe_ret == BridgedType::StdLib(StdLibType::Null) {
             "".to_string()
         } else {
             format!(", {} ret", maybe_ret.to_c())
         };
 
+        let maybe_schedule = if func.is_local {
+            format!(", void {name}$schedule(void* task)", name = name)
+        } else {
+            "".to_string()
+        };
+
         let maybe_params = if func.sig.inputs.is_empty() {
             "".to_string()
         } else {
@@ -305,17 +625,36 @@ fn declare_func(
         };
 
         format!(
-            "void {name}(void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}){maybe_params});\n",
+            "void {name}(void* cancellation_token, void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}){maybe_on_error}, void {name}$panic(void* callback_wrapper, void* message), void {name}$cancelled(void* callback_wrapper, bool runtime_shut_down){maybe_schedule}{maybe_params});\n",
             name = name,
-            maybe_ret = maybe_ret
+            maybe_schedule = maybe_schedule,
+            maybe_ret = maybe_ret,
+            maybe_on_error = maybe_on_error
         )
     } else {
-        format!(
+        let ret = func.to_c_header_return(types);
+
+        let mut declaration = format!(
             "{ret} {name}({params});\n",
             ret = ret,
             name = name,
             params = params
-        )
+        );
+
+        if let Some(iterator) = func.return_iterator(types) {
+            if let Some(include) = iterator.c_include() {
+                bookkeeping.includes.insert(include);
+            }
+            declaration += &iterator.c_declarations(&name);
+        }
+        if let Some(stream) = func.return_stream(types) {
+            if let Some(include) = stream.c_include() {
+                bookkeeping.includes.insert(include);
+            }
+            declaration += &stream.c_declarations(&name);
+        }
+
+        declaration
     };
 
     declaration
@@ -7,12 +7,23 @@ use quote::ToTokens;
 use quote::{quote, quote_spanned};
 
 use crate::bridge_module_attributes::CfgAttr;
+use crate::codegen::generate_rust_tokens::conversions::generate_conversion_functions;
+use crate::codegen::generate_rust_tokens::opaque_type_protocols::generate_protocol_conformance_functions;
+use crate::codegen::generate_rust_tokens::operators::generate_opaque_type_operator_functions;
+use crate::codegen::generate_rust_tokens::traits::{
+    generate_swift_trait_object, generate_trait_definition, generate_trait_functions,
+};
 use crate::codegen::generate_rust_tokens::vec::generate_vec_of_opaque_rust_type_functions;
 use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration};
 use crate::SwiftBridgeModule;
 
+mod codable;
+mod conversions;
+mod opaque_type_protocols;
+mod operators;
 mod shared_enum;
 mod shared_struct;
+mod traits;
 mod vec;
 
 impl ToTokens for SwiftBridgeModule {
@@ -157,6 +168,15 @@ impl ToTokens for SwiftBridgeModule {
                                     };
 
                                     extern_rust_fn_tokens.push(free);
+                                    extern_rust_fn_tokens
+                                        .push(generate_protocol_conformance_functions(ty));
+                                    extern_rust_fn_tokens
+                                        .push(generate_opaque_type_operator_functions(ty));
+                                    extern_rust_fn_tokens.push(generate_conversion_functions(
+                                        ty,
+                                        swift_bridge_path,
+                                        &self.types,
+                                    ));
 
                                     // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
                                     //  make them pass.
@@ -210,6 +230,24 @@ impl ToTokens for SwiftBridgeModule {
             }
         }
 
+        let mut trait_definitions = vec![];
+        for trait_declaration in self.types.traits() {
+            trait_definitions.push(generate_trait_definition(trait_declaration, &self.types));
+            extern_rust_fn_tokens.push(generate_trait_functions(
+                trait_declaration,
+                &self.swift_bridge_path,
+                &self.types,
+            ));
+
+            let (swift_trait_object, swift_vtable) = generate_swift_trait_object(
+                trait_declaration,
+                &self.swift_bridge_path,
+                &self.types,
+            );
+            structs_for_swift_classes.push(swift_trait_object);
+            extern_swift_fn_tokens.push(swift_vtable);
+        }
+
         let extern_swift_fn_tokens = if extern_swift_fn_tokens.len() > 0 {
             quote! {
                 extern "C" {
@@ -238,6 +276,8 @@ impl ToTokens for SwiftBridgeModule {
 
             #(#shared_enum_definitions)*
 
+            #(#trait_definitions)*
+
             #(#extern_rust_fn_tokens)*
 
             #(#freestanding_rust_call_swift_fn_tokens)*
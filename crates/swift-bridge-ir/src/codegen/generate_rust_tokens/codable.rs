@@ -0,0 +1,19 @@
+//! Tests can be found in src/codegen/codegen_tests/codable_codegen_tests.rs
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Path;
+
+/// Generate the serde derives for a shared struct or enum that has the
+/// `#[swift_bridge(Codable)]` attribute.
+///
+/// serde is re-exported by `swift_bridge` when its `serde` feature is enabled, so the crate
+/// that declares the bridge module does not need to depend on serde directly.
+pub(super) fn generate_serde_derive(swift_bridge_path: &Path) -> TokenStream {
+    let serde_path = quote! { #swift_bridge_path::serde }.to_string();
+
+    quote! {
+        #[derive(#swift_bridge_path::serde::Serialize, #swift_bridge_path::serde::Deserialize)]
+        #[serde(crate = #serde_path)]
+    }
+}
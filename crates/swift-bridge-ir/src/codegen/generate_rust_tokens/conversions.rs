@@ -0,0 +1,111 @@
+//! Tests can be found in src/codegen/codegen_tests/conversion_initializers_codegen_tests.rs
+
+use crate::bridged_type::BridgedType;
+use crate::parse::{OpaqueForeignTypeDeclaration, TypeDeclarations};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Path, Type};
+
+/// Generate the functions that the Swift class of an opaque Rust type uses to implement its
+/// conversion initializers, using the type's `From`, `TryFrom` and `FromStr` implementations.
+///
+/// Fallible conversions return a null pointer when they fail. If the `TryFrom::Error` is bridged,
+/// the error is written to the `err` out-parameter so that Swift can throw it. Unsupported
+/// conversion and error types have already been rejected while parsing.
+pub(super) fn generate_conversion_functions(
+    ty: &OpaqueForeignTypeDeclaration,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> TokenStream {
+    let ty_name = &ty.ty;
+    let attributes = &ty.attributes;
+    let mut functions = vec![];
+
+    for from_ty in attributes.from.iter() {
+        let fn_name = ty.conversion_fn_name("_from", from_ty);
+        let link_name = ty.protocol_fn_ffi_name(&fn_name);
+        let fn_name = ty.protocol_fn_ident(&fn_name);
+        let (ffi_ty, value) = ffi_arg(from_ty, swift_bridge_path, types);
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (value: #ffi_ty) -> *mut super::#ty_name {
+                Box::into_raw(Box::new(<super::#ty_name as From<#from_ty>>::from(#value)))
+            }
+        });
+    }
+
+    for try_from in attributes.try_from.iter() {
+        let from_ty = &try_from.ty;
+        let fn_name = ty.conversion_fn_name("_try_from", from_ty);
+        let link_name = ty.protocol_fn_ffi_name(&fn_name);
+        let fn_name = ty.protocol_fn_ident(&fn_name);
+        let (ffi_ty, value) = ffi_arg(from_ty, swift_bridge_path, types);
+
+        let (err_param, on_err) = match try_from.error.as_ref() {
+            Some(error) => {
+                let error = BridgedType::new_with_type(error, types).unwrap();
+                let error = error.convert_rust_value_to_ffi_compatible_value(
+                    &quote! { e },
+                    swift_bridge_path,
+                    types,
+                );
+
+                (
+                    quote! { , err: *mut *mut std::ffi::c_void },
+                    quote! {
+                        Err(e) => {
+                            unsafe { *err = #error as *mut std::ffi::c_void };
+                            std::ptr::null_mut()
+                        }
+                    },
+                )
+            }
+            None => (quote! {}, quote! { Err(_) => std::ptr::null_mut() }),
+        };
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (value: #ffi_ty #err_param) -> *mut super::#ty_name {
+                match <super::#ty_name as TryFrom<#from_ty>>::try_from(#value) {
+                    Ok(v) => Box::into_raw(Box::new(v)),
+                    #on_err
+                }
+            }
+        });
+    }
+
+    if attributes.from_str {
+        let link_name = ty.protocol_fn_ffi_name("_parse");
+        let fn_name = ty.protocol_fn_ident("_parse");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (description: #swift_bridge_path::string::RustStr) -> *mut super::#ty_name {
+                match <super::#ty_name as std::str::FromStr>::from_str(description.to_str()) {
+                    Ok(v) => Box::into_raw(Box::new(v)),
+                    Err(_) => std::ptr::null_mut()
+                }
+            }
+        });
+    }
+
+    quote! {
+        #(#functions)*
+    }
+}
+
+/// The FFI type of a conversion's argument, along with the expression that converts the
+/// argument's FFI representation into its Rust representation.
+fn ffi_arg(
+    from_ty: &Type,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> (TokenStream, TokenStream) {
+    let bridged_ty = BridgedType::new_with_type(from_ty, types).unwrap();
+    let ffi_ty = bridged_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+    let value = bridged_ty.convert_ffi_value_to_rust_value(&quote! { value }, from_ty.span());
+
+    (ffi_ty, value)
+}
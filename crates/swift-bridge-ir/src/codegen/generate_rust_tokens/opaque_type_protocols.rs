@@ -0,0 +1,106 @@
+//! Tests can be found in src/codegen/codegen_tests/opaque_type_protocols_codegen_tests.rs
+
+use crate::parse::OpaqueForeignTypeDeclaration;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generate the functions that the Swift class of an opaque Rust type uses to implement protocols
+/// such as `Equatable` and `Hashable`.
+pub(super) fn generate_protocol_conformance_functions(
+    ty: &OpaqueForeignTypeDeclaration,
+) -> TokenStream {
+    let ty_name = &ty.ty;
+    let mut functions = vec![];
+
+    if ty.attributes.is_equatable() {
+        let link_name = ty.protocol_fn_ffi_name("_partial_eq");
+        let fn_name = ty.protocol_fn_ident("_partial_eq");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (lhs: *mut super::#ty_name, rhs: *mut super::#ty_name) -> bool {
+                unsafe { &*lhs == &*rhs }
+            }
+        });
+    }
+
+    if ty.attributes.hashable {
+        let link_name = ty.protocol_fn_ffi_name("_hash");
+        let fn_name = ty.protocol_fn_ident("_hash");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name) -> u64 {
+                use std::hash::{Hash, Hasher};
+
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                unsafe { &*this }.hash(&mut hasher);
+                hasher.finish()
+            }
+        });
+    }
+
+    if ty.attributes.comparable {
+        let link_name = ty.protocol_fn_ffi_name("_cmp");
+        let fn_name = ty.protocol_fn_ident("_cmp");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (lhs: *mut super::#ty_name, rhs: *mut super::#ty_name) -> i8 {
+                std::cmp::Ord::cmp(unsafe { &*lhs }, unsafe { &*rhs }) as i8
+            }
+        });
+    }
+
+    if ty.attributes.custom_string_convertible {
+        let link_name = ty.protocol_fn_ffi_name("_description");
+        let fn_name = ty.protocol_fn_ident("_description");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name) -> *mut swift_bridge::string::RustString {
+                swift_bridge::string::RustString(unsafe { &*this }.to_string()).box_into_raw()
+            }
+        });
+    }
+
+    if ty.attributes.custom_debug_string_convertible {
+        let link_name = ty.protocol_fn_ffi_name("_debug_description");
+        let fn_name = ty.protocol_fn_ident("_debug_description");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name) -> *mut swift_bridge::string::RustString {
+                swift_bridge::string::RustString(format!("{:?}", unsafe { &*this })).box_into_raw()
+            }
+        });
+    }
+
+    if ty.attributes.clone {
+        let link_name = ty.protocol_fn_ffi_name("_clone");
+        let fn_name = ty.protocol_fn_ident("_clone");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name) -> *mut super::#ty_name {
+                Box::into_raw(Box::new(Clone::clone(unsafe { &*this })))
+            }
+        });
+    }
+
+    if ty.attributes.default {
+        let link_name = ty.protocol_fn_ffi_name("_default");
+        let fn_name = ty.protocol_fn_ident("_default");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name () -> *mut super::#ty_name {
+                Box::into_raw(Box::new(<super::#ty_name as Default>::default()))
+            }
+        });
+    }
+
+    quote! {
+        #(#functions)*
+    }
+}
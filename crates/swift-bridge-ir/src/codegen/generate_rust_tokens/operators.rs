@@ -0,0 +1,134 @@
+//! Tests can be found in src/codegen/codegen_tests/operators_codegen_tests.rs
+
+use crate::bridged_type::SharedStruct;
+use crate::parse::OpaqueForeignTypeDeclaration;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+/// Generate the functions that the Swift class of an opaque Rust type uses to implement its
+/// operators.
+///
+/// The Swift class still owns the operands, so they are passed by reference and the type's
+/// `std::ops` traits need to be implemented for references, such as
+/// `impl Add<&SomeType> for &SomeType`.
+///
+/// If the type uses `#[swift_bridge(Clone)]` the operands are cloned instead, so the traits are
+/// called on owned values, such as `impl Add for SomeType`.
+pub(super) fn generate_opaque_type_operator_functions(
+    ty: &OpaqueForeignTypeDeclaration,
+) -> TokenStream {
+    let ty_name = &ty.ty;
+    let operators = &ty.attributes.operators;
+    let mut functions = vec![];
+
+    let operand = |ptr: &str| {
+        let ptr = Ident::new(ptr, Span::call_site());
+        if ty.attributes.clone {
+            quote! { Clone::clone(unsafe { &*#ptr }) }
+        } else {
+            quote! { unsafe { &*#ptr } }
+        }
+    };
+
+    for operator in operators.binary_operators() {
+        let fn_name = format!("_{}", operator.method);
+        let link_name = ty.protocol_fn_ffi_name(&fn_name);
+        let fn_name = ty.protocol_fn_ident(&fn_name);
+        let trait_name = Ident::new(operator.trait_name, Span::call_site());
+        let method = Ident::new(operator.method, Span::call_site());
+        let lhs = operand("lhs");
+        let rhs = operand("rhs");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (lhs: *mut super::#ty_name, rhs: *mut super::#ty_name) -> *mut super::#ty_name {
+                Box::into_raw(Box::new(std::ops::#trait_name::#method(#lhs, #rhs)))
+            }
+        });
+    }
+
+    if operators.neg {
+        let link_name = ty.protocol_fn_ffi_name("_neg");
+        let fn_name = ty.protocol_fn_ident("_neg");
+        let this = operand("this");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name) -> *mut super::#ty_name {
+                Box::into_raw(Box::new(std::ops::Neg::neg(#this)))
+            }
+        });
+    }
+
+    if let Some(output) = operators.index.as_ref() {
+        let link_name = ty.protocol_fn_ffi_name("_index");
+        let fn_name = ty.protocol_fn_ident("_index");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#ty_name, index: usize) -> #output {
+                *std::ops::Index::index(unsafe { &*this }, index)
+            }
+        });
+    }
+
+    quote! {
+        #(#functions)*
+    }
+}
+
+/// Generate the functions that the Swift representation of a shared struct uses to implement
+/// its operators.
+///
+/// The operands are passed by value, so the struct's `std::ops` traits are called on owned
+/// values, such as `impl Add for SomeStruct`.
+pub(super) fn generate_shared_struct_operator_functions(
+    shared_struct: &SharedStruct,
+) -> TokenStream {
+    let struct_ffi_name = shared_struct.ffi_name_tokens();
+    let operators = &shared_struct.operators;
+    let mut functions = vec![];
+
+    for operator in operators.binary_operators() {
+        let fn_name = format!("_{}", operator.method);
+        let link_name = shared_struct.operator_fn_ffi_name(&fn_name);
+        let fn_name = shared_struct.operator_fn_ident(&fn_name);
+        let trait_name = Ident::new(operator.trait_name, Span::call_site());
+        let method = Ident::new(operator.method, Span::call_site());
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (lhs: #struct_ffi_name, rhs: #struct_ffi_name) -> #struct_ffi_name {
+                std::ops::#trait_name::#method(lhs.into_rust_repr(), rhs.into_rust_repr()).into_ffi_repr()
+            }
+        });
+    }
+
+    if operators.neg {
+        let link_name = shared_struct.operator_fn_ffi_name("_neg");
+        let fn_name = shared_struct.operator_fn_ident("_neg");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: #struct_ffi_name) -> #struct_ffi_name {
+                std::ops::Neg::neg(this.into_rust_repr()).into_ffi_repr()
+            }
+        });
+    }
+
+    if let Some(output) = operators.index.as_ref() {
+        let link_name = shared_struct.operator_fn_ffi_name("_index");
+        let fn_name = shared_struct.operator_fn_ident("_index");
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: #struct_ffi_name, index: usize) -> #output {
+                *std::ops::Index::index(&this.into_rust_repr(), index)
+            }
+        });
+    }
+
+    quote! {
+        #(#functions)*
+    }
+}
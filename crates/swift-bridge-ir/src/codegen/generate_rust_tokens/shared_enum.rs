@@ -2,6 +2,7 @@
 //! crates/swift-bridge-ir/src/codegen/codegen_tests/shared_enum_codegen_tests.rs
 
 use crate::bridged_type::SharedEnum;
+use crate::codegen::generate_rust_tokens::codable::generate_serde_derive;
 use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -59,7 +60,14 @@ impl SwiftBridgeModule {
             convert_ffi_variants_to_rust.push(v);
         }
 
+        let serde_derive = if shared_enum.codable {
+            generate_serde_derive(swift_bridge_path)
+        } else {
+            quote! {}
+        };
+
         let definition = quote! {
+            #serde_derive
             pub enum #enum_name {
                 #(#enum_variants),*
             }
@@ -2,6 +2,8 @@
 //! crates/swift-bridge-ir/src/codegen/codegen_tests/shared_struct_codegen_tests.rs
 
 use crate::bridged_type::{BridgedType, SharedStruct};
+use crate::codegen::generate_rust_tokens::codable::generate_serde_derive;
+use crate::codegen::generate_rust_tokens::operators::generate_shared_struct_operator_functions;
 use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -85,7 +87,31 @@ impl SwiftBridgeModule {
             }
         };
 
+        let serde_derive = if shared_struct.codable {
+            generate_serde_derive(swift_bridge_path)
+        } else {
+            quote! {}
+        };
+
+        let operator_functions = generate_shared_struct_operator_functions(shared_struct);
+
+        // Swift already synthesizes an `init()` for structs that have no fields.
+        let default_function = if shared_struct.default && !shared_struct.fields.is_empty() {
+            let link_name = shared_struct.operator_fn_ffi_name("_default");
+            let fn_name = shared_struct.operator_fn_ident("_default");
+
+            quote! {
+                #[export_name = #link_name]
+                pub extern "C" fn #fn_name () -> #struct_ffi_name {
+                    <#struct_name as Default>::default().into_ffi_repr()
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         let definition = quote! {
+            #serde_derive
             pub struct #struct_name #struct_fields
 
             #struct_ffi_repr
@@ -144,6 +170,10 @@ impl SwiftBridgeModule {
                     }
                 }
             }
+
+            #operator_functions
+
+            #default_function
         };
 
         Some(definition)
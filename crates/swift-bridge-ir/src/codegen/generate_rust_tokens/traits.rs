@@ -0,0 +1,284 @@
+//! Tests can be found in src/codegen/codegen_tests/trait_codegen_tests.rs
+
+use crate::bridged_type::BridgedType;
+use crate::parse::{TraitDeclaration, TraitMethod, TypeDeclarations};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Path, ReturnType};
+
+/// Generate the Rust definition of a trait that was declared in the bridge module.
+///
+/// Users implement the generated trait for their types, i.e. `impl ffi::Shape for Circle`.
+pub(super) fn generate_trait_definition(
+    trait_declaration: &TraitDeclaration,
+    types: &TypeDeclarations,
+) -> TokenStream {
+    let trait_name = &trait_declaration.name;
+
+    let methods = trait_declaration.methods.iter().map(|method| {
+        let signature = method_signature(method, types);
+        quote! {
+            #signature;
+        }
+    });
+
+    quote! {
+        pub trait #trait_name {
+            #(#methods)*
+        }
+    }
+}
+
+/// Generate the functions that Swift uses to work with a trait.
+///
+/// The trait object's vtable is made up of one exported function per trait method, along with a
+/// function to free the trait object.
+/// Every implementor of the trait gets one function per trait method, along with a function that
+/// converts an owned implementor into a trait object.
+pub(super) fn generate_trait_functions(
+    trait_declaration: &TraitDeclaration,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> TokenStream {
+    let trait_name = &trait_declaration.name;
+    let trait_object = trait_declaration.trait_object().to_rust_type_path();
+    let mut functions = vec![];
+
+    for method in trait_declaration.methods.iter() {
+        let method_name = &method.name;
+        let link_name = trait_declaration.vtable_fn_ffi_name(&method_name.to_string());
+        let fn_name = trait_declaration.vtable_fn_ident(&method_name.to_string());
+
+        let this = if method.mutable {
+            quote! { unsafe { &mut **(this as *mut #trait_object) } }
+        } else {
+            quote! { unsafe { &**(this as *mut #trait_object) } }
+        };
+        let (params, ret, body) = method_params_and_call(
+            method,
+            quote! { #trait_name::#method_name },
+            this,
+            swift_bridge_path,
+            types,
+        );
+
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut std::ffi::c_void #(, #params)*) #ret {
+                #body
+            }
+        });
+    }
+
+    let free_link_name = trait_declaration.vtable_fn_ffi_name("_free");
+    let free_fn_name = trait_declaration.vtable_fn_ident("_free");
+    functions.push(quote! {
+        #[export_name = #free_link_name]
+        pub extern "C" fn #free_fn_name (this: *mut std::ffi::c_void) {
+            let this = unsafe { Box::from_raw(this as *mut #trait_object) };
+            drop(this);
+        }
+    });
+
+    for implementor in trait_declaration.implementors.iter() {
+        for method in trait_declaration.methods.iter() {
+            let method_name = &method.name;
+            let link_name =
+                trait_declaration.implementor_fn_ffi_name(implementor, &method_name.to_string());
+            let fn_name =
+                trait_declaration.implementor_fn_ident(implementor, &method_name.to_string());
+
+            let this = if method.mutable {
+                quote! { unsafe { &mut *this } }
+            } else {
+                quote! { unsafe { &*this } }
+            };
+            let (params, ret, body) = method_params_and_call(
+                method,
+                quote! { <super::#implementor as #trait_name>::#method_name },
+                this,
+                swift_bridge_path,
+                types,
+            );
+
+            functions.push(quote! {
+                #[export_name = #link_name]
+                pub extern "C" fn #fn_name (this: *mut super::#implementor #(, #params)*) #ret {
+                    #body
+                }
+            });
+        }
+
+        let link_name = trait_declaration.implementor_fn_ffi_name(implementor, "_into_dyn");
+        let fn_name = trait_declaration.implementor_fn_ident(implementor, "_into_dyn");
+        functions.push(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name (this: *mut super::#implementor) -> *mut std::ffi::c_void {
+                let this: #trait_object = unsafe { Box::from_raw(this) };
+                Box::into_raw(Box::new(this)) as *mut std::ffi::c_void
+            }
+        });
+    }
+
+    quote! {
+        #(#functions)*
+    }
+}
+
+/// Generate the Rust struct that implements a trait by calling the methods of a Swift value that
+/// conforms to the trait's Swift protocol, along with the function that Swift uses to turn the
+/// Swift value into a `Box<dyn Trait>`.
+///
+/// Returns the Rust items along with the declarations of the `@_cdecl` Swift functions that make
+/// up the Swift value's vtable.
+pub(super) fn generate_swift_trait_object(
+    trait_declaration: &TraitDeclaration,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> (TokenStream, TokenStream) {
+    let trait_name = &trait_declaration.name;
+    let trait_object = trait_declaration.trait_object().to_rust_type_path();
+    let struct_name = trait_declaration.swift_impl_struct_ident();
+
+    let mut methods = vec![];
+    let mut extern_swift_fns = vec![];
+
+    for method in trait_declaration.methods.iter() {
+        let method_name = method.name.to_string();
+        let link_name = trait_declaration.swift_vtable_fn_ffi_name(&method_name);
+        let fn_name = trait_declaration.swift_vtable_fn_ident(&method_name);
+
+        let mut params = vec![];
+        let mut call_args = vec![];
+        for (arg_name, ty) in method.args.iter() {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+            params.push(quote! { #arg_name: #ffi_ty });
+            call_args.push(ty.convert_rust_value_to_ffi_compatible_value(
+                &quote! { #arg_name },
+                swift_bridge_path,
+                types,
+            ));
+        }
+
+        let call = quote! { unsafe { #fn_name(self.0 #(, #call_args)*) } };
+        let (ret, call) = match &method.output {
+            ReturnType::Default => (quote! {}, call),
+            ReturnType::Type(_, ty) => {
+                let ty = BridgedType::new_with_type(ty, types).unwrap();
+                let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+                let call = ty.convert_ffi_value_to_rust_value(&call, method.name.span());
+
+                (quote! { -> #ffi_ty }, call)
+            }
+        };
+
+        extern_swift_fns.push(quote! {
+            #[link_name = #link_name]
+            fn #fn_name(this: *mut std::ffi::c_void #(, #params)*) #ret;
+        });
+
+        let signature = method_signature(method, types);
+        methods.push(quote! {
+            #signature {
+                #call
+            }
+        });
+    }
+
+    let free_link_name = trait_declaration.swift_vtable_fn_ffi_name("_free");
+    let free_fn_name = trait_declaration.swift_vtable_fn_ident("_free");
+    extern_swift_fns.push(quote! {
+        #[link_name = #free_link_name]
+        fn #free_fn_name(this: *mut std::ffi::c_void);
+    });
+
+    let from_swift_link_name = trait_declaration.vtable_fn_ffi_name("_from_swift");
+    let from_swift_fn_name = trait_declaration.vtable_fn_ident("_from_swift");
+
+    let items = quote! {
+        #[doc(hidden)]
+        pub struct #struct_name(*mut std::ffi::c_void);
+
+        impl #trait_name for #struct_name {
+            #(#methods)*
+        }
+
+        impl Drop for #struct_name {
+            fn drop (&mut self) {
+                unsafe { #free_fn_name(self.0) }
+            }
+        }
+
+        #[export_name = #from_swift_link_name]
+        pub extern "C" fn #from_swift_fn_name (this: *mut std::ffi::c_void) -> *mut std::ffi::c_void {
+            let this: #trait_object = Box::new(#struct_name(this));
+            Box::into_raw(Box::new(this)) as *mut std::ffi::c_void
+        }
+    };
+
+    (items, quote! { #(#extern_swift_fns)* })
+}
+
+/// `fn some_method(&self, arg: u8) -> u16`
+fn method_signature(method: &TraitMethod, types: &TypeDeclarations) -> TokenStream {
+    let method_name = &method.name;
+    let receiver = if method.mutable {
+        quote! { &mut self }
+    } else {
+        quote! { &self }
+    };
+
+    let args = method.args.iter().map(|(arg_name, ty)| {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        let ty = ty.to_rust_type_path();
+        quote! { #arg_name: #ty }
+    });
+
+    let ret = match &method.output {
+        ReturnType::Default => quote! {},
+        ReturnType::Type(_, ty) => {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            let ty = ty.to_rust_type_path();
+            quote! { -> #ty }
+        }
+    };
+
+    quote! {
+        fn #method_name(#receiver #(, #args)*) #ret
+    }
+}
+
+/// The FFI parameters, FFI return type and body of a function that calls a trait method.
+fn method_params_and_call(
+    method: &TraitMethod,
+    method_path: TokenStream,
+    this: TokenStream,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> (Vec<TokenStream>, TokenStream, TokenStream) {
+    let mut params = vec![];
+    let mut call_args = vec![];
+    for (arg_name, ty) in method.args.iter() {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        params.push(quote! { #arg_name: #ffi_ty });
+        call_args.push(ty.convert_ffi_value_to_rust_value(&quote! { #arg_name }, arg_name.span()));
+    }
+
+    let call = quote! { #method_path(#this #(, #call_args)*) };
+
+    match &method.output {
+        ReturnType::Default => (params, quote! {}, call),
+        ReturnType::Type(_, ty) => {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+            let call =
+                ty.convert_rust_value_to_ffi_compatible_value(&call, swift_bridge_path, types);
+
+            (params, quote! { -> #ffi_ty }, call)
+        }
+    }
+}
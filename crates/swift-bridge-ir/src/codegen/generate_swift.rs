@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use syn::Path;
 
 use crate::bridged_type::{BridgedType, TypePosition};
+use crate::codegen::generate_swift::conversions::generate_conversion_initializers;
 use crate::codegen::generate_swift::generate_function_swift_calls_rust::gen_func_swift_calls_rust;
 use crate::codegen::generate_swift::opaque_copy_type::generate_opaque_copy_struct;
+use crate::codegen::generate_swift::opaque_type_protocols::generate_protocol_conformances;
+use crate::codegen::generate_swift::operators::generate_opaque_type_operators;
 use crate::codegen::generate_swift::swift_class::generate_swift_class;
+use crate::codegen::generate_swift::traits::generate_trait;
 use crate::codegen::generate_swift::vec::generate_vectorizable_extension;
 use crate::codegen::CodegenConfig;
 use crate::parse::{
@@ -17,11 +21,16 @@ use crate::SwiftBridgeModule;
 
 mod vec;
 
+mod codable;
+mod conversions;
 mod generate_function_swift_calls_rust;
 mod opaque_copy_type;
+mod opaque_type_protocols;
+mod operators;
 mod shared_enum;
 mod shared_struct;
 mod swift_class;
+mod traits;
 
 impl SwiftBridgeModule {
     /// Generate the corresponding Swift code for the bridging module.
@@ -35,8 +44,11 @@ impl SwiftBridgeModule {
         let mut associated_funcs_and_methods: HashMap<String, Vec<&ParsedExternFn>> =
             HashMap::new();
         let mut class_protocols: HashMap<String, ClassProtocols> = HashMap::new();
+        let mut imports: BTreeSet<&'static str> = BTreeSet::new();
 
         for function in &self.functions {
+            imports.extend(function.swift_imports(&self.types));
+
             if function.host_lang.is_rust() {
                 if let Some(ty) = function.associated_type.as_ref() {
                     match ty {
@@ -92,6 +104,12 @@ impl SwiftBridgeModule {
         for ty in self.types.types() {
             match ty {
                 TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                    for field in shared_struct.fields.normalized_fields() {
+                        if let Some(ty) = BridgedType::new_with_type(&field.ty, &self.types) {
+                            imports.extend(ty.swift_import());
+                        }
+                    }
+
                     if let Some(swift_struct) = self.generate_shared_struct_string(shared_struct) {
                         swift += &swift_struct;
                         swift += "\n";
@@ -136,6 +154,12 @@ impl SwiftBridgeModule {
                                 swift += &generate_vectorizable_extension(&ty);
                                 swift += "\n";
                             }
+
+                            if ty.attributes.has_protocol_conformances() {
+                                swift += &generate_protocol_conformances(ty);
+                                swift += &generate_opaque_type_operators(ty, &self.types);
+                                swift += "\n";
+                            }
                         }
                     }
                     HostLang::Swift => {
@@ -146,6 +170,33 @@ impl SwiftBridgeModule {
             };
         }
 
+        for trait_declaration in self.types.traits() {
+            for method in trait_declaration.methods.iter() {
+                let arg_types = method.args.iter().map(|(_, ty)| ty);
+                let ret_type = match &method.output {
+                    syn::ReturnType::Default => None,
+                    syn::ReturnType::Type(_, ty) => Some(ty.as_ref()),
+                };
+
+                for ty in arg_types.chain(ret_type) {
+                    if let Some(ty) = BridgedType::new_with_type(ty, &self.types) {
+                        imports.extend(ty.swift_import());
+                    }
+                }
+            }
+
+            swift += &generate_trait(trait_declaration, &self.types);
+            swift += "\n";
+        }
+
+        if !imports.is_empty() {
+            let imports: String = imports
+                .iter()
+                .map(|import| format!("import {}\n", import))
+                .collect();
+            swift = imports + &swift;
+        }
+
         swift
     }
 }
@@ -207,6 +258,10 @@ fn gen_function_exposes_swift_to_rust(
     types: &TypeDeclarations,
     swift_bridge_path: &Path,
 ) -> String {
+    if func.sig.asyncness.is_some() {
+        return gen_async_function_exposes_swift_to_rust(func, types, swift_bridge_path);
+    }
+
     let link_name = func.link_name();
     let prefixed_fn_name = func.prefixed_fn_name();
     let fn_name = if let Some(swift_name) = func.swift_name_override.as_ref() {
@@ -271,8 +326,123 @@ func {prefixed_fn_name} ({params}){ret} {{
     generated_func
 }
 
+// Generate a function that Rust calls in order to run a Swift async function.
+//
+// Rust passes in a pointer to its future's state along with a callback. We run the Swift async
+// function in a `Task` and then pass the result to the callback, which completes the Rust future.
+// The task is returned to Rust so that it can be cancelled if Rust drops the future.
+//
+// # Example
+//
+// ```
+// @_cdecl("__swift_bridge__$some_function")
+// func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @convention(c) (UnsafeMutableRawPointer, UInt8) -> Void, _ arg: UInt8) -> UnsafeMutableRawPointer {
+//     let task = Task {
+//         let val = await some_function(arg: arg)
+//         callback(callbackWrapper, val)
+//     }
+//     return Unmanaged.passRetained(__private__SwiftTask(task)).toOpaque()
+// }
+// ```
+fn gen_async_function_exposes_swift_to_rust(
+    func: &ParsedExternFn,
+    types: &TypeDeclarations,
+    swift_bridge_path: &Path,
+) -> String {
+    let link_name = func.link_name();
+    let prefixed_fn_name = func.prefixed_fn_name();
+    let fn_name = if let Some(swift_name) = func.swift_name_override.as_ref() {
+        swift_name.value()
+    } else {
+        func.sig.ident.to_string()
+    };
+
+    // Unresolved return types are reported while parsing.
+    let return_ty = BridgedType::new_with_return_type(&func.sig.output, types).unwrap();
+
+    let (maybe_ffi_ret, maybe_val) = if return_ty.is_null() {
+        ("".to_string(), "".to_string())
+    } else {
+        (
+            format!(
+                ", {}",
+                return_ty.to_swift_type(TypePosition::FnReturn(HostLang::Swift), types)
+            ),
+            format!(
+                ", {}",
+                return_ty.convert_swift_expression_to_ffi_compatible(
+                    "val",
+                    TypePosition::FnArg(HostLang::Rust)
+                )
+            ),
+        )
+    };
+
+    let mut params = format!(
+        "_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @convention(c) (UnsafeMutableRawPointer{}) -> Void",
+        maybe_ffi_ret
+    );
+    let fn_params = func.to_swift_param_names_and_types(true, types);
+    if !fn_params.is_empty() {
+        params += ", ";
+        params += &fn_params;
+    }
+
+    let args = func.to_swift_call_args(false, true, types, swift_bridge_path);
+
+    // Methods retain their Swift instance before spawning the task, since Rust might drop its
+    // handle to the instance before the task finishes.
+    let mut maybe_retain_this = "".to_string();
+    let call_fn = match func.associated_type.as_ref() {
+        None => format!("{}({})", fn_name, args),
+        Some(TypeDeclaration::Shared(_)) => {
+            unreachable!("Async Swift functions that are associated to a shared type are rejected while parsing")
+        }
+        Some(TypeDeclaration::Opaque(associated_type)) => {
+            let associated_type = associated_type.to_string();
+            // Async initializers are rejected while parsing.
+            if func.is_method() {
+                maybe_retain_this = format!(
+                    "    let this = Unmanaged<{}>.fromOpaque(this).takeUnretainedValue()\n",
+                    associated_type
+                );
+                format!("this.{}({})", fn_name, args)
+            } else {
+                format!("{}.{}({})", associated_type, fn_name, args)
+            }
+        }
+    };
+
+    let call_fn = if return_ty.is_null() {
+        format!("await {}", call_fn)
+    } else {
+        format!("let val = await {}", call_fn)
+    };
+
+    format!(
+        r#"@_cdecl("{link_name}")
+func {prefixed_fn_name} ({params}) -> UnsafeMutableRawPointer {{
+{maybe_retain_this}    let task = Task {{
+        {call_fn}
+        callback(callbackWrapper{maybe_val})
+    }}
+    return Unmanaged.passRetained(__private__SwiftTask(task)).toOpaque()
+}}
+"#,
+        link_name = link_name,
+        prefixed_fn_name = prefixed_fn_name,
+        params = params,
+        maybe_retain_this = maybe_retain_this,
+        call_fn = call_fn,
+        maybe_val = maybe_val
+    )
+}
+
 struct ClassMethods {
     initializers: Vec<String>,
+    /// Initializers that must be declared in the class itself, such as the initializers that
+    /// satisfy a protocol's requirements.
+    required_initializers: Vec<String>,
     owned_self_methods: Vec<String>,
     ref_self_methods: Vec<String>,
     ref_mut_self_methods: Vec<String>,
@@ -285,6 +455,7 @@ fn generate_swift_class_methods(
     swift_bridge_path: &Path,
 ) -> ClassMethods {
     let mut initializers = vec![];
+    let mut required_initializers = vec![];
     let mut owned_self_methods = vec![];
     let mut ref_self_methods = vec![];
     let mut ref_mut_self_methods = vec![];
@@ -313,8 +484,17 @@ fn generate_swift_class_methods(
         }
     }
 
+    if let Some(ty) = types.get(type_name).and_then(|ty| ty.as_opaque()) {
+        if ty.attributes.has_conversions() {
+            let conversions = generate_conversion_initializers(ty, types);
+            initializers.extend(conversions.initializers);
+            required_initializers.extend(conversions.required_initializers);
+        }
+    }
+
     ClassMethods {
         initializers,
+        required_initializers,
         owned_self_methods,
         ref_self_methods,
         ref_mut_self_methods,
@@ -0,0 +1,278 @@
+//! Tests can be found in src/codegen/codegen_tests/codable_codegen_tests.rs
+
+use crate::bridged_type::{
+    BridgedType, CustomBridgedType, SharedEnum, SharedStruct, StdLibType, StructFields,
+    TypePosition,
+};
+use crate::parse::TypeDeclarations;
+
+/// Generate the `Codable` conformance for a shared struct that has the
+/// `#[swift_bridge(Codable)]` attribute.
+///
+/// The encoding follows serde's conventions so that values serialized by the Rust type's derived
+/// `Serialize` implementation can be decoded in Swift and vice versa:
+/// - Structs with named fields are keyed by their Rust field names.
+/// - Newtype structs are encoded as their single field.
+/// - Tuple structs are encoded as an array of their fields.
+/// - Unit structs are encoded as `nil`.
+pub(super) fn generate_struct_codable_conformance(
+    shared_struct: &SharedStruct,
+    types: &TypeDeclarations,
+) -> String {
+    let struct_name = shared_struct.swift_name_string();
+
+    let (coding_keys, decode, encode) = match &shared_struct.fields {
+        StructFields::Named(named) => {
+            let mut coding_keys = "".to_string();
+            let mut decode = "".to_string();
+            let mut encode = "".to_string();
+
+            for field in named.iter() {
+                let name = field.swift_name_string();
+                let value = CodableValue::new(&field.ty, &format!("self.{}", name), types);
+
+                coding_keys += &format!("\n        case {}", name);
+                decode += &format!(
+                    "\n        self.{name} = {decode}",
+                    name = name,
+                    decode = value.decode(&format!(", forKey: .{}", name), ContainerKind::Keyed)
+                );
+                encode += &format!(
+                    "\n        try container.encode({encode}, forKey: .{name})",
+                    encode = value.encode,
+                    name = name
+                );
+            }
+
+            let coding_keys = format!(
+                r#"
+    enum CodingKeys: CodingKey {{{coding_keys}
+    }}
+"#,
+                coding_keys = coding_keys
+            );
+
+            if named.is_empty() {
+                (
+                    coding_keys,
+                    "\n        _ = try decoder.container(keyedBy: CodingKeys.self)".to_string(),
+                    "\n        _ = encoder.container(keyedBy: CodingKeys.self)".to_string(),
+                )
+            } else {
+                (
+                    coding_keys,
+                    format!(
+                        "\n        let container = try decoder.container(keyedBy: CodingKeys.self){}",
+                        decode
+                    ),
+                    format!(
+                        "\n        var container = encoder.container(keyedBy: CodingKeys.self){}",
+                        encode
+                    ),
+                )
+            }
+        }
+        StructFields::Unnamed(unnamed) if unnamed.len() == 1 => {
+            let name = unnamed[0].swift_name_string();
+            let value = CodableValue::new(&unnamed[0].ty, &format!("self.{}", name), types);
+
+            (
+                "".to_string(),
+                format!(
+                    r#"
+        let container = try decoder.singleValueContainer()
+        self.{name} = {decode}"#,
+                    name = name,
+                    decode = value.decode("", ContainerKind::SingleValue)
+                ),
+                format!(
+                    r#"
+        var container = encoder.singleValueContainer()
+        try container.encode({encode})"#,
+                    encode = value.encode
+                ),
+            )
+        }
+        StructFields::Unnamed(unnamed) => {
+            let mut decode = "\n        var container = try decoder.unkeyedContainer()".to_string();
+            let mut encode = "\n        var container = encoder.unkeyedContainer()".to_string();
+
+            for field in unnamed.iter() {
+                let name = field.swift_name_string();
+                let value = CodableValue::new(&field.ty, &format!("self.{}", name), types);
+
+                decode += &format!(
+                    "\n        self.{name} = {decode}",
+                    name = name,
+                    decode = value.decode("", ContainerKind::Unkeyed)
+                );
+                encode += &format!("\n        try container.encode({})", value.encode);
+            }
+
+            ("".to_string(), decode, encode)
+        }
+        StructFields::Unit => (
+            "".to_string(),
+            "\n        _ = try decoder.singleValueContainer()".to_string(),
+            r#"
+        var container = encoder.singleValueContainer()
+        try container.encodeNil()"#
+                .to_string(),
+        ),
+    };
+
+    format!(
+        r#"
+extension {struct_name}: Codable {{{coding_keys}
+    public init(from decoder: Decoder) throws {{{decode}
+    }}
+
+    public func encode(to encoder: Encoder) throws {{{encode}
+    }}
+}}"#,
+        struct_name = struct_name,
+        coding_keys = coding_keys,
+        decode = decode,
+        encode = encode
+    )
+}
+
+/// Generate the `Codable` conformance for a shared enum that has the
+/// `#[swift_bridge(Codable)]` attribute.
+///
+/// Following serde's conventions, each variant is encoded as a string containing its name.
+pub(super) fn generate_enum_codable_conformance(shared_enum: &SharedEnum) -> String {
+    let enum_name = shared_enum.swift_name_string();
+
+    let mut decode_cases = "".to_string();
+    let mut encode_cases = "".to_string();
+
+    for variant in shared_enum.variants.iter() {
+        if !variant.fields.is_empty() {
+            unreachable!("Codable enum variants with fields are rejected while parsing.")
+        }
+
+        decode_cases += &format!(
+            r#"
+        case "{variant}":
+            self = .{variant}"#,
+            variant = variant.name
+        );
+        encode_cases += &format!(
+            r#"
+        case .{variant}:
+            try container.encode("{variant}")"#,
+            variant = variant.name
+        );
+    }
+
+    format!(
+        r#"
+extension {enum_name}: Codable {{
+    public init(from decoder: Decoder) throws {{
+        let container = try decoder.singleValueContainer()
+        let variant = try container.decode(String.self)
+        switch variant {{{decode_cases}
+        default:
+            throw DecodingError.dataCorruptedError(in: container, debugDescription: "Unknown variant \(variant) of {enum_name}")
+        }}
+    }}
+
+    public func encode(to encoder: Encoder) throws {{
+        var container = encoder.singleValueContainer()
+        switch self {{{encode_cases}
+        }}
+    }}
+}}"#,
+        enum_name = enum_name,
+        decode_cases = decode_cases,
+        encode_cases = encode_cases
+    )
+}
+
+#[derive(Copy, Clone)]
+enum ContainerKind {
+    Keyed,
+    Unkeyed,
+    SingleValue,
+}
+
+/// How a shared struct field gets encoded to and decoded from an encoding container.
+struct CodableValue {
+    /// The expression that gets passed to the container's `encode` method.
+    encode: String,
+    /// The Swift type that the container decodes.
+    decoded_ty: String,
+    /// Whether or not the field is an `Option`.
+    optional: bool,
+    /// Converts the decoded value into the field's Swift type.
+    convert_decoded: &'static str,
+}
+
+impl CodableValue {
+    fn new(ty: &syn::Type, value: &str, types: &TypeDeclarations) -> Self {
+        let bridged_ty = BridgedType::new_with_type(ty, types).unwrap();
+
+        match &bridged_ty {
+            BridgedType::StdLib(StdLibType::Option(opt)) => match opt.ty.as_ref() {
+                BridgedType::StdLib(StdLibType::String) => CodableValue {
+                    encode: format!("{}?.toString()", value),
+                    decoded_ty: "String".to_string(),
+                    optional: true,
+                    convert_decoded: "?.intoRustString()",
+                },
+                inner => CodableValue {
+                    optional: true,
+                    ..Self::new_non_optional(inner, value, types)
+                },
+            },
+            _ => Self::new_non_optional(&bridged_ty, value, types),
+        }
+    }
+
+    fn new_non_optional(ty: &BridgedType, value: &str, types: &TypeDeclarations) -> Self {
+        match ty {
+            BridgedType::StdLib(StdLibType::String) => CodableValue {
+                encode: format!("{}.toString()", value),
+                decoded_ty: "String".to_string(),
+                optional: false,
+                convert_decoded: ".intoRustString()",
+            },
+            BridgedType::StdLib(stdlib) if stdlib.is_primitive() => CodableValue {
+                encode: value.to_string(),
+                decoded_ty: ty.to_swift_type(TypePosition::SharedStructField, types),
+                optional: false,
+                convert_decoded: "",
+            },
+            BridgedType::Foreign(CustomBridgedType::Shared(_)) => CodableValue {
+                encode: value.to_string(),
+                decoded_ty: ty.to_swift_type(TypePosition::SharedStructField, types),
+                optional: false,
+                convert_decoded: "",
+            },
+            _ => unreachable!("Unsupported Codable field types are rejected while parsing."),
+        }
+    }
+
+    fn decode(&self, key: &str, container: ContainerKind) -> String {
+        let method = match (self.optional, container) {
+            (false, _) => "decode",
+            (true, ContainerKind::Keyed) | (true, ContainerKind::Unkeyed) => "decodeIfPresent",
+            (true, ContainerKind::SingleValue) => {
+                return format!(
+                    "try container.decode(Optional<{ty}>.self){convert}",
+                    ty = self.decoded_ty,
+                    convert = self.convert_decoded
+                );
+            }
+        };
+
+        format!(
+            "try container.{method}({ty}.self{key}){convert}",
+            method = method,
+            ty = self.decoded_ty,
+            key = key,
+            convert = self.convert_decoded
+        )
+    }
+}
@@ -0,0 +1,170 @@
+//! Tests can be found in src/codegen/codegen_tests/conversion_initializers_codegen_tests.rs
+
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::codegen::generate_swift::SwiftFuncGenerics;
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration, TypeDeclarations};
+use syn::Type;
+
+/// The Swift initializers that an opaque Rust type gets from its `From`, `TryFrom` and `FromStr`
+/// implementations.
+pub(super) struct ConversionInitializers {
+    /// Initializers that can live in an extension of the class.
+    pub initializers: Vec<String>,
+    /// Initializers that satisfy a protocol requirement, so they must be `required` and live in
+    /// the class declaration itself.
+    pub required_initializers: Vec<String>,
+}
+
+/// Generate the Swift initializers that call the type's conversion functions.
+///
+/// `From(u8)` becomes `init(_ value: UInt8)`, `TryFrom(i64)` becomes `init?(_ value: Int64)` and
+/// `TryFrom(i64, Error = String)` becomes `init(_ value: Int64) throws`.
+/// `FromStr` becomes the `init?(_ description: String)` that `LosslessStringConvertible`
+/// requires.
+pub(super) fn generate_conversion_initializers(
+    ty: &OpaqueForeignTypeDeclaration,
+    types: &TypeDeclarations,
+) -> ConversionInitializers {
+    let attributes = &ty.attributes;
+    let mut initializers = vec![];
+    let mut required_initializers = vec![];
+
+    for from_ty in attributes.from.iter() {
+        let ffi_name = ty.protocol_fn_ffi_name(&ty.conversion_fn_name("_from", from_ty));
+        let (generics, param_ty) = param(from_ty, types);
+        let call = call_with_value(from_ty, &ffi_name, "", "UnsafeMutableRawPointer", types);
+
+        initializers.push(format!(
+            r#"    public convenience init{generics}(_ value: {param_ty}) {{
+        self.init(ptr: {call})
+    }}"#,
+            generics = generics,
+            param_ty = param_ty,
+            call = call
+        ));
+    }
+
+    for try_from in attributes.try_from.iter() {
+        let from_ty = &try_from.ty;
+        let ffi_name = ty.protocol_fn_ffi_name(&ty.conversion_fn_name("_try_from", from_ty));
+        let (generics, param_ty) = param(from_ty, types);
+
+        let initializer = match try_from.error.as_ref() {
+            Some(error) => {
+                let error = BridgedType::new_with_type(error, types).unwrap();
+                let error = error.convert_ffi_value_to_swift_value(
+                    "err",
+                    TypePosition::FnReturn(HostLang::Rust),
+                    types,
+                );
+                let call = call_with_value(
+                    from_ty,
+                    &ffi_name,
+                    ", &err",
+                    "UnsafeMutableRawPointer?",
+                    types,
+                );
+
+                format!(
+                    r#"    public convenience init{generics}(_ value: {param_ty}) throws {{
+        var err: UnsafeMutableRawPointer? = nil
+        let ptr = {call}
+        if let err = err {{
+            throw {error}
+        }}
+        self.init(ptr: ptr!)
+    }}"#,
+                    generics = generics,
+                    param_ty = param_ty,
+                    call = call,
+                    error = error
+                )
+            }
+            None => {
+                let call =
+                    call_with_value(from_ty, &ffi_name, "", "UnsafeMutableRawPointer?", types);
+
+                format!(
+                    r#"    public convenience init?{generics}(_ value: {param_ty}) {{
+        guard let ptr = {call} else {{
+            return nil
+        }}
+        self.init(ptr: ptr)
+    }}"#,
+                    generics = generics,
+                    param_ty = param_ty,
+                    call = call
+                )
+            }
+        };
+
+        initializers.push(initializer);
+    }
+
+    if attributes.from_str {
+        required_initializers.push(format!(
+            r#"    public required convenience init?(_ description: String) {{
+        guard let ptr = description.toRustStr({{ descriptionAsRustStr -> UnsafeMutableRawPointer? in
+            {parse}(descriptionAsRustStr)
+        }}) else {{
+            return nil
+        }}
+        self.init(ptr: ptr)
+    }}"#,
+            parse = ty.protocol_fn_ffi_name("_parse")
+        ));
+    }
+
+    ConversionInitializers {
+        initializers,
+        required_initializers,
+    }
+}
+
+/// The generic parameters of an initializer along with the type of its `value` parameter.
+fn param(from_ty: &Type, types: &TypeDeclarations) -> (String, String) {
+    let from_ty = BridgedType::new_with_type(from_ty, types).unwrap();
+
+    let generics = if from_ty.contains_owned_string_recursive() {
+        format!("<{}>", SwiftFuncGenerics::String.as_bound())
+    } else if from_ty.contains_ref_string_recursive() {
+        format!("<{}>", SwiftFuncGenerics::Str.as_bound())
+    } else {
+        "".to_string()
+    };
+
+    (
+        generics,
+        from_ty.to_swift_type(TypePosition::FnArg(HostLang::Rust), types),
+    )
+}
+
+/// Call a conversion function with the initializer's `value` parameter.
+///
+/// Strings that are passed to Rust as a `RustStr` are only valid within a `toRustStr` closure, so
+/// the call is made from within the closure. `closure_ret` is the closure's return type, since
+/// Swift would otherwise infer an optional pointer.
+fn call_with_value(
+    from_ty: &Type,
+    ffi_name: &str,
+    extra_args: &str,
+    closure_ret: &str,
+    types: &TypeDeclarations,
+) -> String {
+    let from_ty = BridgedType::new_with_type(from_ty, types).unwrap();
+    let value = from_ty
+        .convert_swift_expression_to_ffi_compatible("value", TypePosition::FnArg(HostLang::Rust));
+    let call = format!("{}({}{})", ffi_name, value, extra_args);
+
+    if from_ty.is_passed_as_rust_str() {
+        format!(
+            r#"value.toRustStr({{ valueAsRustStr -> {closure_ret} in
+            {call}
+        }})"#,
+            closure_ret = closure_ret,
+            call = call
+        )
+    } else {
+        call
+    }
+}
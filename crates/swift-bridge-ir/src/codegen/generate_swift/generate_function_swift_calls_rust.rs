@@ -1,11 +1,13 @@
-use crate::bridged_type::{fn_arg_name, BridgedType, StdLibType, TypePosition};
+use crate::bridged_type::{
+    fn_arg_name, pat_type_pat_is_self, BridgedType, CustomBridgedType, StdLibType, TypePosition,
+};
 use crate::codegen::generate_swift::SwiftFuncGenerics;
 use crate::parse::{HostLang, TypeDeclaration};
 use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use quote::ToTokens;
 use std::collections::HashSet;
 use std::ops::Deref;
-use syn::{Path, ReturnType, Type};
+use syn::{FnArg, Path, ReturnType, Type};
 
 pub(super) fn gen_func_swift_calls_rust(
     function: &ParsedExternFn,
@@ -23,7 +25,22 @@ pub(super) fn gen_func_swift_calls_rust(
             format!(", {}", call_args)
         };
 
-        format!("{}(wrapperPtr, onComplete{})", fn_name, maybe_args)
+        let maybe_on_error = if function.return_result(types).is_some() {
+            ", onError"
+        } else {
+            ""
+        };
+
+        let maybe_schedule = if function.is_local {
+            ", scheduleLocalRustTask"
+        } else {
+            ""
+        };
+
+        format!(
+            "{}(cancellationToken, wrapperPtr, onComplete{}, onPanic, onCancelled{}{})",
+            fn_name, maybe_on_error, maybe_schedule, maybe_args
+        )
     } else {
         format!("{}({})", fn_name, call_args)
     };
@@ -80,6 +97,11 @@ pub(super) fn gen_func_swift_calls_rust(
         call_rust
     } else if function.is_swift_initializer {
         call_rust
+    } else if let Some(iterator) = function.return_iterator(types) {
+        let owners = iterator_owners(function, types);
+        iterator.convert_ffi_value_to_swift_value(&call_rust, &function.link_name(), &owners, types)
+    } else if let Some(stream) = function.return_stream(types) {
+        stream.convert_ffi_value_to_swift_value(&call_rust, &function.link_name(), types)
     } else if let Some(built_in) = function.return_ty_built_in(types) {
         built_in.convert_ffi_value_to_swift_value(
             &call_rust,
@@ -155,7 +177,7 @@ pub(super) fn gen_func_swift_calls_rust(
 
         // TODO: Refactor to make less duplicative
         match bridged_arg {
-            BridgedType::StdLib(StdLibType::Str) => {
+            bridged_arg if bridged_arg.is_passed_as_rust_str() => {
                 call_rust = format!(
                     r#"{maybe_return}{arg}.toRustStr({{ {arg}AsRustStr in
 {indentation}        {call_rust}
@@ -167,7 +189,7 @@ pub(super) fn gen_func_swift_calls_rust(
                 );
             }
             BridgedType::StdLib(StdLibType::Option(briged_opt))
-                if briged_opt.ty.deref() == &BridgedType::StdLib(StdLibType::Str) =>
+                if briged_opt.ty.is_passed_as_rust_str() =>
             {
                 call_rust = format!(
                     r#"{maybe_return}optionalRustStrToRustStr({arg}, {{ {arg}AsRustStr in
@@ -213,6 +235,38 @@ pub(super) fn gen_func_swift_calls_rust(
 
     let func_definition = if function.sig.asyncness.is_some() {
         let func_ret_ty = function.return_ty_built_in(types).unwrap();
+
+        // The `Ok` of a `Result<T, E>` gets passed to `onComplete` and the `Err` gets passed to
+        // `onError`, which throws it.
+        let (func_ret_ty, maybe_on_error) = match func_ret_ty {
+            BridgedType::StdLib(StdLibType::Result(result)) => {
+                let err_ty = result
+                    .err_ty
+                    .to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types);
+                let err_val = result.err_ty.convert_ffi_value_to_swift_value(
+                    "rustFnErrVal",
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
+                    types,
+                );
+
+                (
+                    *result.ok_ty,
+                    format!(
+                        r#"
+func onError(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnErrVal: {err_ty}) {{
+    let wrapper = Unmanaged<CbWrapper{type_name_segment}${fn_name}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.failure({err_val}))
+}}"#,
+                        err_ty = err_ty,
+                        err_val = err_val,
+                        type_name_segment = maybe_type_name_segment,
+                        fn_name = fn_name
+                    ),
+                )
+            }
+            func_ret_ty => (func_ret_ty, "".to_string()),
+        };
+
         let rust_fn_ret_ty =
             func_ret_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
 
@@ -235,26 +289,59 @@ pub(super) fn gen_func_swift_calls_rust(
 
         let callback_wrapper_ty = format!("CbWrapper{}${}", maybe_type_name_segment, fn_name);
 
+        // The Rust future borrows from `self`, so the callback wrapper keeps `self` alive until
+        // Rust has either completed or dropped the future.
+        let retains_self =
+            function.self_reference().is_some() && !function.is_copy_method_on_opaque_type();
+        let (maybe_owner_arg, maybe_owner_field, maybe_owner_param, maybe_owner_init) =
+            if retains_self {
+                (
+                    ", owner: self",
+                    format!("\n{}    let owner: AnyObject", indentation),
+                    ", owner: AnyObject",
+                    format!("\n{}        self.owner = owner", indentation),
+                )
+            } else {
+                ("", "".to_string(), "", "".to_string())
+            };
+
         let fn_body = format!(
             r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
     let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
     wrapper.cb(.success({on_complete_ret_val}))
+}}{maybe_on_error}
+func onPanic(cbWrapperPtr: UnsafeMutableRawPointer?, message: UnsafeMutableRawPointer?) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.failure(RustPanic(message: RustString(ptr: message!).toString())))
 }}
+func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?, runtimeShutDown: Bool) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.failure(rustTaskDroppedError(runtimeShutDown)))
+}}
+
+let cancellationToken = __swift_bridge__$CancellationToken$new()
+defer {{ __swift_bridge__$CancellationToken$_free(cancellationToken) }}
 
-return await withCheckedContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Never>) in
-    let callback = {{ rustFnRetVal in
-        continuation.resume(with: rustFnRetVal)
-    }}
+return try await withTaskCancellationHandler(operation: {{
+    try await withCheckedThrowingContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Error>) in
+        let callback = {{ rustFnRetVal in
+            continuation.resume(with: rustFnRetVal)
+        }}
 
-    let wrapper = {cb_wrapper_ty}(cb: callback)
-    let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+        let wrapper = {cb_wrapper_ty}(cb: callback{maybe_owner_arg})
+        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-    {call_rust}
+        {call_rust}
+    }})
+}}, onCancel: {{
+    __swift_bridge__$CancellationToken$cancel(cancellationToken)
 }})"#,
             rust_fn_ret_ty = rust_fn_ret_ty,
             maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
             on_complete_ret_val = on_complete_ret_val,
+            maybe_on_error = maybe_on_error,
             cb_wrapper_ty = callback_wrapper_ty,
+            maybe_owner_arg = maybe_owner_arg,
             call_rust = call_rust,
         );
 
@@ -270,22 +357,29 @@ return await withCheckedContinuation({{ (continuation: CheckedContinuation<{rust
 
         let callback_wrapper = format!(
             r#"{indentation}class {cb_wrapper_ty} {{
-{indentation}    var cb: (Result<{rust_fn_ret_ty}, Never>) -> ()
+{indentation}    var cb: (Result<{rust_fn_ret_ty}, Error>) -> (){maybe_owner_field}
 {indentation}
-{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, Never>) -> ()) {{
-{indentation}        self.cb = cb
+{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, Error>) -> (){maybe_owner_param}) {{
+{indentation}        self.cb = cb{maybe_owner_init}
 {indentation}    }}
 {indentation}}}"#,
             indentation = indentation,
-            cb_wrapper_ty = callback_wrapper_ty
+            cb_wrapper_ty = callback_wrapper_ty,
+            maybe_owner_field = maybe_owner_field,
+            maybe_owner_param = maybe_owner_param,
+            maybe_owner_init = maybe_owner_init,
         );
 
+        // Local functions get called on the main thread, which is where their future gets polled.
+        let maybe_main_actor = if function.is_local { "@MainActor " } else { "" };
+
         format!(
-            r#"{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async{maybe_ret} {{
+            r#"{indentation}{maybe_main_actor}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async throws{maybe_ret} {{
 {fn_body_indented}
 {indentation}}}
 {callback_wrapper}"#,
             indentation = indentation,
+            maybe_main_actor = maybe_main_actor,
             maybe_static_class_func = maybe_static_class_func,
             swift_class_func_name = public_func_fn_name,
             maybe_generics = maybe_generics,
@@ -311,3 +405,39 @@ return await withCheckedContinuation({{ (continuation: CheckedContinuation<{rust
 
     func_definition
 }
+
+/// The Swift values that an iterator returned by this function might be borrowing from.
+/// These get kept alive for as long as the iterator.
+///
+/// fn rows(&self, other: &SomeType) -> impl Iterator<Item = Row> -> "[self, other]"
+fn iterator_owners(function: &ParsedExternFn, types: &TypeDeclarations) -> String {
+    let mut owners = vec![];
+
+    if function.self_reference().is_some() && !function.is_copy_method_on_opaque_type() {
+        owners.push("self".to_string());
+    }
+
+    for arg in function.func.sig.inputs.iter() {
+        if let FnArg::Typed(pat_ty) = arg {
+            // `self: &SomeType` was handled above.
+            if pat_type_pat_is_self(pat_ty) {
+                continue;
+            }
+        }
+
+        let is_borrowed_opaque_rust_type = match BridgedType::new_with_fn_arg(arg, types) {
+            Some(BridgedType::Foreign(CustomBridgedType::Opaque(opaque))) => {
+                opaque.host_lang.is_rust()
+                    && opaque.reference
+                    && !opaque.has_swift_bridge_copy_annotation
+            }
+            _ => false,
+        };
+
+        if is_borrowed_opaque_rust_type {
+            owners.push(fn_arg_name(arg).unwrap().to_string());
+        }
+    }
+
+    format!("[{}]", owners.join(", "))
+}
@@ -0,0 +1,114 @@
+use crate::parse::OpaqueForeignTypeDeclaration;
+
+/// Generate the protocol conformances, such as `Equatable` and `Hashable`, that the Swift class
+/// of an opaque Rust type gets from the type's Rust trait implementations.
+///
+/// The conformances are declared on the `TypeRef` class so that owned values and references can
+/// be used interchangeably.
+pub(super) fn generate_protocol_conformances(ty: &OpaqueForeignTypeDeclaration) -> String {
+    let type_name = ty.ty.to_string();
+    let mut conformances = "".to_string();
+
+    if ty.attributes.is_equatable() {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref: Equatable {{
+    public static func == (lhs: {type_name}Ref, rhs: {type_name}Ref) -> Bool {{
+        {partial_eq}(lhs.ptr, rhs.ptr)
+    }}
+}}"#,
+            type_name = type_name,
+            partial_eq = ty.protocol_fn_ffi_name("_partial_eq"),
+        );
+    }
+
+    if ty.attributes.hashable {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref: Hashable {{
+    public func hash(into hasher: inout Hasher) {{
+        hasher.combine({hash}(ptr))
+    }}
+}}"#,
+            type_name = type_name,
+            hash = ty.protocol_fn_ffi_name("_hash"),
+        );
+    }
+
+    if ty.attributes.comparable {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref: Comparable {{
+    public static func < (lhs: {type_name}Ref, rhs: {type_name}Ref) -> Bool {{
+        {cmp}(lhs.ptr, rhs.ptr) < 0
+    }}
+}}"#,
+            type_name = type_name,
+            cmp = ty.protocol_fn_ffi_name("_cmp"),
+        );
+    }
+
+    if ty.attributes.custom_string_convertible {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref: CustomStringConvertible {{
+    public var description: String {{
+        RustString(ptr: {description}(ptr)).toString()
+    }}
+}}"#,
+            type_name = type_name,
+            description = ty.protocol_fn_ffi_name("_description"),
+        );
+    }
+
+    if ty.attributes.custom_string_convertible && ty.attributes.from_str {
+        // The `init?(_ description: String)` requirement is declared in the class itself, since
+        // required initializers can't be declared in an extension.
+        conformances += &format!(
+            r#"
+extension {type_name}: LosslessStringConvertible {{}}"#,
+            type_name = type_name,
+        );
+    }
+
+    if ty.attributes.custom_debug_string_convertible {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref: CustomDebugStringConvertible {{
+    public var debugDescription: String {{
+        RustString(ptr: {debug_description}(ptr)).toString()
+    }}
+}}"#,
+            type_name = type_name,
+            debug_description = ty.protocol_fn_ffi_name("_debug_description"),
+        );
+    }
+
+    if ty.attributes.clone {
+        conformances += &format!(
+            r#"
+extension {type_name}Ref {{
+    public func clone() -> {type_name} {{
+        {type_name}(ptr: {clone}(ptr))
+    }}
+}}"#,
+            type_name = type_name,
+            clone = ty.protocol_fn_ffi_name("_clone"),
+        );
+    }
+
+    if ty.attributes.default {
+        conformances += &format!(
+            r#"
+extension {type_name} {{
+    public convenience init() {{
+        self.init(ptr: {default}())
+    }}
+}}"#,
+            type_name = type_name,
+            default = ty.protocol_fn_ffi_name("_default"),
+        );
+    }
+
+    conformances
+}
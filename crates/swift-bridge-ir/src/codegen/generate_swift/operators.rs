@@ -0,0 +1,115 @@
+//! Tests can be found in src/codegen/codegen_tests/operators_codegen_tests.rs
+
+use crate::bridged_type::{BridgedType, SharedStruct, TypePosition};
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration, Operators, TypeDeclarations};
+
+/// Generate the operators and subscripts that the Swift class of an opaque Rust type gets from
+/// the type's `std::ops` implementations.
+///
+/// They are declared on the `TypeRef` class so that owned values and references can be used as
+/// operands.
+pub(super) fn generate_opaque_type_operators(
+    ty: &OpaqueForeignTypeDeclaration,
+    types: &TypeDeclarations,
+) -> String {
+    let type_name = ty.ty.to_string();
+
+    generate_operators(
+        &ty.attributes.operators,
+        &format!("{}Ref", type_name),
+        &type_name,
+        |fn_name| ty.protocol_fn_ffi_name(fn_name),
+        |operand| format!("{}.ptr", operand),
+        |call| format!("{}(ptr: {})", type_name, call),
+        types,
+    )
+}
+
+/// Generate the operators and subscripts that the Swift representation of a shared struct gets
+/// from the struct's `std::ops` implementations.
+pub(super) fn generate_shared_struct_operators(
+    shared_struct: &SharedStruct,
+    types: &TypeDeclarations,
+) -> String {
+    let struct_name = shared_struct.swift_name_string();
+
+    generate_operators(
+        &shared_struct.operators,
+        &struct_name,
+        &struct_name,
+        |fn_name| shared_struct.operator_fn_ffi_name(fn_name),
+        |operand| format!("{}.intoFfiRepr()", operand),
+        |call| format!("{}.intoSwiftRepr()", call),
+        types,
+    )
+}
+
+fn generate_operators(
+    operators: &Operators,
+    operand_ty: &str,
+    output_ty: &str,
+    ffi_name: impl Fn(&str) -> String,
+    pass_operand: impl Fn(&str) -> String,
+    convert_output: impl Fn(&str) -> String,
+    types: &TypeDeclarations,
+) -> String {
+    if operators.is_empty() {
+        return "".to_string();
+    }
+
+    let mut members = vec![];
+
+    for operator in operators.binary_operators() {
+        let call = format!(
+            "{}({}, {})",
+            ffi_name(&format!("_{}", operator.method)),
+            pass_operand("lhs"),
+            pass_operand("rhs")
+        );
+
+        members.push(format!(
+            r#"    public static func {swift_operator} (lhs: {operand_ty}, rhs: {operand_ty}) -> {output_ty} {{
+        {output}
+    }}"#,
+            swift_operator = operator.swift_operator,
+            operand_ty = operand_ty,
+            output_ty = output_ty,
+            output = convert_output(&call)
+        ));
+    }
+
+    if operators.neg {
+        let call = format!("{}({})", ffi_name("_neg"), pass_operand("operand"));
+
+        members.push(format!(
+            r#"    public static prefix func - (operand: {operand_ty}) -> {output_ty} {{
+        {output}
+    }}"#,
+            operand_ty = operand_ty,
+            output_ty = output_ty,
+            output = convert_output(&call)
+        ));
+    }
+
+    if let Some(output) = operators.index.as_ref() {
+        let output = BridgedType::new_with_type(output, types).unwrap();
+
+        members.push(format!(
+            r#"    public subscript(index: UInt) -> {index_output} {{
+        {index}({this}, index)
+    }}"#,
+            index_output = output.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types),
+            index = ffi_name("_index"),
+            this = pass_operand("self"),
+        ));
+    }
+
+    format!(
+        r#"
+extension {operand_ty} {{
+{members}
+}}"#,
+        operand_ty = operand_ty,
+        members = members.join("\n\n")
+    )
+}
@@ -1,4 +1,5 @@
 use crate::bridged_type::SharedEnum;
+use crate::codegen::generate_swift::codable::generate_enum_codable_conformance;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -99,6 +100,12 @@ extension {option_ffi_name} {{
             convert_ffi_repr_to_swift = convert_ffi_repr_to_swift
         );
 
+        let swift_enum = if shared_enum.codable {
+            swift_enum + &generate_enum_codable_conformance(shared_enum)
+        } else {
+            swift_enum
+        };
+
         Some(swift_enum)
     }
 }
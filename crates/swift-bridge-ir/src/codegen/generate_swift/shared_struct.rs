@@ -1,4 +1,6 @@
 use crate::bridged_type::{BridgedType, SharedStruct, StructFields, StructSwiftRepr, TypePosition};
+use crate::codegen::generate_swift::codable::generate_struct_codable_conformance;
+use crate::codegen::generate_swift::operators::generate_shared_struct_operators;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -108,6 +110,32 @@ extension {option_ffi_name} {{
                     convert_ffi_repr_to_swift = convert_ffi_repr_to_swift
                 );
 
+                let swift_struct = if shared_struct.codable {
+                    swift_struct + &generate_struct_codable_conformance(shared_struct, &self.types)
+                } else {
+                    swift_struct
+                };
+
+                let swift_struct =
+                    swift_struct + &generate_shared_struct_operators(shared_struct, &self.types);
+
+                // Swift already synthesizes an `init()` for structs that have no fields.
+                let swift_struct = if shared_struct.default && !shared_struct.fields.is_empty() {
+                    swift_struct
+                        + &format!(
+                            r#"
+extension {struct_name} {{
+    public init() {{
+        self = {default}().intoSwiftRepr()
+    }}
+}}"#,
+                            struct_name = struct_name,
+                            default = shared_struct.operator_fn_ffi_name("_default"),
+                        )
+                } else {
+                    swift_struct
+                };
+
                 Some(swift_struct)
             }
         }
@@ -1,4 +1,4 @@
-use crate::codegen::generate_swift::{generate_swift_class_methods, ClassProtocols};
+use crate::codegen::generate_swift::{generate_swift_class_methods, ClassMethods, ClassProtocols};
 use crate::parse::OpaqueForeignTypeDeclaration;
 use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use std::collections::HashMap;
@@ -20,26 +20,23 @@ pub(super) fn generate_swift_class(
         swift_bridge_path,
     );
 
-    create_class_declaration(
-        ty,
-        class_protocols,
-        &class_methods.initializers,
-        &class_methods.owned_self_methods,
-        &class_methods.ref_self_methods,
-        &class_methods.ref_mut_self_methods,
-        types,
-    )
+    create_class_declaration(ty, class_protocols, &class_methods, types)
 }
 
 fn create_class_declaration(
     ty: &OpaqueForeignTypeDeclaration,
     class_protocols: &ClassProtocols,
-    initializers: &[String],
-    owned_self_methods: &[String],
-    ref_self_methods: &[String],
-    ref_mut_self_methods: &[String],
+    class_methods: &ClassMethods,
     types: &TypeDeclarations,
 ) -> String {
+    let ClassMethods {
+        initializers,
+        required_initializers,
+        owned_self_methods,
+        ref_self_methods,
+        ref_mut_self_methods,
+    } = class_methods;
+
     let type_name = &ty.ty_name_ident().to_string();
     let generics = ty.generics.angle_bracketed_generic_placeholders_string();
 
@@ -50,13 +47,18 @@ fn create_class_declaration(
             "(self as! SwiftBridgeGenericFreer).rust_free()".to_string()
         };
 
+        let required_initializers: String = required_initializers
+            .iter()
+            .map(|initializer| format!("\n\n{}", initializer))
+            .collect();
+
         format!(
             r#"public class {type_name}{generics}: {type_name}RefMut{generics} {{
     var isOwned: Bool = true
 
     public override init(ptr: UnsafeMutableRawPointer) {{
         super.init(ptr: ptr)
-    }}
+    }}{required_initializers}
 
     deinit {{
         if isOwned {{
@@ -66,6 +68,7 @@ fn create_class_declaration(
 }}"#,
             type_name = type_name,
             generics = generics,
+            required_initializers = required_initializers,
             free_func_call = free_func_call
         )
     };
@@ -0,0 +1,352 @@
+//! Tests can be found in src/codegen/codegen_tests/trait_codegen_tests.rs
+
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::codegen::generate_swift::SwiftFuncGenerics;
+use crate::parse::{HostLang, TraitDeclaration, TraitMethod, TypeDeclarations};
+use std::collections::BTreeSet;
+use syn::ReturnType;
+
+/// Generate the Swift protocol for a trait that was declared in the bridge module, along with
+/// the class that wraps a `Box<dyn Trait>` and the conformances of the trait's implementors.
+///
+/// # Example
+///
+/// ```no_run,ignore
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+/// impl Shape for Circle {}
+/// ```
+///
+/// becomes
+///
+/// ```no_run,ignore
+/// public protocol Shape {
+///     func area() -> Double
+/// }
+/// public class RustShape: Shape { ... }
+/// extension CircleRef {
+///     public func area() -> Double {
+///         __swift_bridge__$Circle$Shape$area(ptr)
+///     }
+/// }
+/// extension Circle: Shape {}
+/// ```
+pub(super) fn generate_trait(
+    trait_declaration: &TraitDeclaration,
+    types: &TypeDeclarations,
+) -> String {
+    let trait_name = trait_declaration.name.to_string();
+    let trait_object = trait_declaration.trait_object();
+    let class_name = trait_object.swift_class_name();
+
+    let requirements: Vec<String> = trait_declaration
+        .methods
+        .iter()
+        .map(|method| format!("    func {}", method_signature(method, false, types)))
+        .collect();
+
+    // The trait object class also gets generic overloads, so that Swift code can pass a `String`
+    // where the protocol requires a `RustString`.
+    let mut trait_object_methods = vec![];
+    for method in trait_declaration.methods.iter() {
+        let ffi_name = trait_declaration.vtable_fn_ffi_name(&method.name.to_string());
+        trait_object_methods.push(generate_method(method, &ffi_name, false, types));
+        if has_generic_params(method, types) {
+            trait_object_methods.push(generate_method(method, &ffi_name, true, types));
+        }
+    }
+
+    let implementor_casts: String = trait_declaration
+        .implementors
+        .iter()
+        .map(|implementor| {
+            format!(
+                r#"
+        if let value = value as? {implementor} {{
+            value.isOwned = false
+            return {into_dyn}(value.ptr)
+        }}"#,
+                implementor = implementor,
+                into_dyn = trait_declaration.implementor_fn_ffi_name(implementor, "_into_dyn")
+            )
+        })
+        .collect();
+
+    let mut swift = format!(
+        r#"
+public protocol {trait_name} {{
+{requirements}
+}}
+public class {class_name}: {trait_name} {{
+    var ptr: UnsafeMutableRawPointer
+    var isOwned: Bool = true
+
+    public init(ptr: UnsafeMutableRawPointer) {{
+        self.ptr = ptr
+    }}
+
+    deinit {{
+        if isOwned {{
+            {free}(ptr)
+        }}
+    }}
+}}
+extension {class_name} {{
+{trait_object_methods}
+}}
+extension {class_name} {{
+    static func intoFfiRepr(_ value: {existential}) -> UnsafeMutableRawPointer {{
+        if let value = value as? {class_name} {{
+            value.isOwned = false
+            return value.ptr
+        }}{implementor_casts}
+        return {from_swift}(Unmanaged.passRetained({box_class}(value)).toOpaque())
+    }}
+}}
+class {box_class} {{
+    let value: {existential}
+
+    init(_ value: {existential}) {{
+        self.value = value
+    }}
+}}{swift_vtable}"#,
+        trait_name = trait_name,
+        class_name = class_name,
+        requirements = requirements.join("\n"),
+        free = trait_declaration.vtable_fn_ffi_name("_free"),
+        trait_object_methods = trait_object_methods.join("\n\n"),
+        existential = trait_object.swift_existential(),
+        implementor_casts = implementor_casts,
+        from_swift = trait_declaration.vtable_fn_ffi_name("_from_swift"),
+        box_class = trait_declaration.swift_box_class_name(),
+        swift_vtable = generate_swift_vtable(trait_declaration, types),
+    );
+
+    for implementor in trait_declaration.implementors.iter() {
+        let mut ref_methods = vec![];
+        let mut ref_mut_methods = vec![];
+
+        for method in trait_declaration.methods.iter() {
+            let ffi_name =
+                trait_declaration.implementor_fn_ffi_name(implementor, &method.name.to_string());
+            let method_definition = generate_method(method, &ffi_name, false, types);
+
+            if method.mutable {
+                ref_mut_methods.push(method_definition);
+            } else {
+                ref_methods.push(method_definition);
+            }
+        }
+
+        for (class_suffix, methods) in [("Ref", ref_methods), ("RefMut", ref_mut_methods)] {
+            if methods.is_empty() {
+                continue;
+            }
+
+            swift += &format!(
+                r#"
+extension {implementor}{class_suffix} {{
+{methods}
+}}"#,
+                implementor = implementor,
+                class_suffix = class_suffix,
+                methods = methods.join("\n\n")
+            );
+        }
+
+        swift += &format!(
+            r#"
+extension {implementor}: {trait_name} {{}}"#,
+            implementor = implementor,
+            trait_name = trait_name
+        );
+    }
+
+    swift
+}
+
+/// Generate the `@_cdecl` functions that Rust uses to call the methods of a Swift value that was
+/// passed to Rust as a `Box<dyn Trait>`, along with the function that releases the value.
+fn generate_swift_vtable(trait_declaration: &TraitDeclaration, types: &TypeDeclarations) -> String {
+    let box_class = trait_declaration.swift_box_class_name();
+    let mut vtable = "".to_string();
+
+    for method in trait_declaration.methods.iter() {
+        let method_name = method.name.to_string();
+
+        let mut params = vec!["_ this: UnsafeMutableRawPointer".to_string()];
+        let mut args = vec![];
+        for (arg_name, ty) in method.args.iter() {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            params.push(format!(
+                "_ {}: {}",
+                arg_name,
+                ty.to_swift_type(TypePosition::FnArg(HostLang::Swift), types)
+            ));
+            args.push(ty.convert_ffi_value_to_swift_value(
+                &arg_name.to_string(),
+                TypePosition::FnArg(HostLang::Swift),
+                types,
+            ));
+        }
+
+        let mut call = format!(
+            "Unmanaged<{box_class}>.fromOpaque(this).takeUnretainedValue().value.{method_name}({args})",
+            box_class = box_class,
+            method_name = method_name,
+            args = args.join(", ")
+        );
+        let mut ret = "".to_string();
+        if let ReturnType::Type(_, ty) = &method.output {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            ret = format!(
+                " -> {}",
+                ty.to_swift_type(TypePosition::FnReturn(HostLang::Swift), types)
+            );
+            call = ty.convert_swift_expression_to_ffi_compatible(
+                &call,
+                TypePosition::FnReturn(HostLang::Swift),
+            );
+        }
+
+        vtable += &format!(
+            r#"
+@_cdecl("{link_name}")
+func {fn_name} ({params}){ret} {{
+    {call}
+}}"#,
+            link_name = trait_declaration.swift_vtable_fn_ffi_name(&method_name),
+            fn_name = trait_declaration.swift_vtable_fn_ident(&method_name),
+            params = params.join(", "),
+            ret = ret,
+            call = call
+        );
+    }
+
+    vtable += &format!(
+        r#"
+@_cdecl("{link_name}")
+func {fn_name} (ptr: UnsafeMutableRawPointer) {{
+    let _ = Unmanaged<{box_class}>.fromOpaque(ptr).takeRetainedValue()
+}}"#,
+        link_name = trait_declaration.swift_vtable_fn_ffi_name("_free"),
+        fn_name = trait_declaration.swift_vtable_fn_ident("_free"),
+        box_class = box_class
+    );
+
+    vtable
+}
+
+/// Whether the trait method takes a `String` or `&str`, which the trait object class also accepts
+/// through a generic overload.
+fn has_generic_params(method: &TraitMethod, types: &TypeDeclarations) -> bool {
+    method.args.iter().any(|(_, ty)| {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        ty.contains_owned_string_recursive() || ty.contains_ref_string_recursive()
+    })
+}
+
+/// The Swift signature of a trait method, without the leading `func`.
+///
+/// i.e. `describe(_ prefix: RustString) -> RustString`, or if `generic` is true
+/// `describe<GenericIntoRustString: IntoRustString>(_ prefix: GenericIntoRustString) -> RustString`
+///
+/// Protocol requirements use the concrete version, so that Swift types can conform to the protocol
+/// without declaring generic methods.
+fn method_signature(method: &TraitMethod, generic: bool, types: &TypeDeclarations) -> String {
+    let mut generics = BTreeSet::new();
+    let mut params = vec![];
+
+    for (arg_name, ty) in method.args.iter() {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+
+        if ty.contains_owned_string_recursive() {
+            generics.insert(SwiftFuncGenerics::String);
+        } else if ty.contains_ref_string_recursive() {
+            generics.insert(SwiftFuncGenerics::Str);
+        }
+
+        let mut swift_ty = ty.to_swift_type(TypePosition::FnArg(HostLang::Rust), types);
+        if !generic {
+            swift_ty = swift_ty
+                .replace("GenericIntoRustString", "RustString")
+                .replace("GenericToRustStr", "RustStr");
+        }
+        params.push(format!("_ {}: {}", arg_name, swift_ty));
+    }
+    if !generic {
+        generics.clear();
+    }
+
+    let generics = if generics.is_empty() {
+        "".to_string()
+    } else {
+        let bounds: Vec<&str> = generics.iter().map(|g| g.as_bound()).collect();
+        format!("<{}>", bounds.join(", "))
+    };
+
+    let ret = match &method.output {
+        ReturnType::Default => "".to_string(),
+        ReturnType::Type(_, ty) => {
+            let ty = BridgedType::new_with_type(ty, types).unwrap();
+            format!(
+                " -> {}",
+                ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+            )
+        }
+    };
+
+    format!("{}{}({}){}", method.name, generics, params.join(", "), ret)
+}
+
+/// A public Swift method that implements a trait method by calling a Rust function.
+///
+/// `RustString` and `RustStr` conform to `IntoRustString` and `ToRustStr`, so the generic and the
+/// concrete version have the same body.
+fn generate_method(
+    method: &TraitMethod,
+    ffi_name: &str,
+    generic: bool,
+    types: &TypeDeclarations,
+) -> String {
+    let mut args = vec!["ptr".to_string()];
+    for (arg_name, ty) in method.args.iter() {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        args.push(ty.convert_swift_expression_to_ffi_compatible(
+            &arg_name.to_string(),
+            TypePosition::FnArg(HostLang::Rust),
+        ));
+    }
+
+    let mut call = format!("{}({})", ffi_name, args.join(", "));
+    if let ReturnType::Type(_, ty) = &method.output {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        call = ty.convert_ffi_value_to_swift_value(
+            &call,
+            TypePosition::FnReturn(HostLang::Rust),
+            types,
+        );
+    }
+
+    for (arg_name, ty) in method.args.iter() {
+        let ty = BridgedType::new_with_type(ty, types).unwrap();
+        if ty.is_passed_as_rust_str() {
+            call = format!(
+                r#"return {arg}.toRustStr({{ {arg}AsRustStr in
+        {call}
+    }})"#,
+                arg = arg_name,
+                call = call
+            );
+        }
+    }
+
+    format!(
+        r#"    public func {signature} {{
+        {call}
+    }}"#,
+        signature = method_signature(method, generic, types),
+        call = call
+    )
+}
@@ -1,8 +1,8 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use syn::{Error, FnArg, Receiver};
-use syn::{ForeignItemType, LitStr};
-use syn::{Token, Type};
+use syn::{ForeignItemType, ItemImpl, LitStr, TraitItem};
+use syn::{Signature, Token, Type};
 
 pub(crate) enum ParseError {
     /// `extern {}`
@@ -59,11 +59,140 @@ pub(crate) enum ParseError {
     ArgCopyAndRefMut {
         arg: FnArg,
     },
+    /// `async fn foo (arg: &str)` in an `extern "Swift"` block.
+    /// The Swift task reads its arguments after the call has returned to Rust, so Rust can't lend
+    /// it anything.
+    AsyncSwiftFnArgBorrow {
+        arg: FnArg,
+    },
+    /// `#[swift_bridge(init)] async fn new () -> SomeType` in an `extern "Swift"` block.
+    AsyncSwiftInitializer {
+        fn_ident: Ident,
+    },
+    /// `#[swift_bridge(associated_to = SomeStruct)] async fn foo ()` in an `extern "Swift"` block,
+    /// where `SomeStruct` is a shared struct or enum.
+    AsyncSwiftFnAssociatedToSharedType {
+        fn_ident: Ident,
+    },
+    /// `fn foo () -> Option<[u8; 4]>`
+    /// Arrays, iterators, results, streams and trait objects can't be wrapped in an `Option`.
+    UnsupportedOptionType {
+        ty: Type,
+    },
+    /// `fn foo (arg: [String; 2])`, `fn foo (arg: [u8; 0])` or `fn foo (arg: Vec<[u8; 4]>)`
+    /// Arrays are passed as C structs, which only hold primitives or shared types and can't be
+    /// empty. Swift represents them as tuples, which can't be stored in a `RustVec`.
+    UnsupportedArrayType {
+        ty: Type,
+    },
+    /// `fn foo (arg: &str) -> impl Iterator<Item = u8> + '_`
+    /// Swift only keeps the borrowed opaque Rust types that the iterator might borrow from alive,
+    /// so other borrowed arguments would be freed while the iterator still points to them.
+    IteratorFnArgBorrow {
+        arg: FnArg,
+    },
+    /// `fn foo (self: Arc<Self>)`
+    /// Methods can only take `self`, `&self` or `&mut self`.
+    UnsupportedSelfType {
+        self_: FnArg,
+    },
+    /// `async fn foo (&self)` on an opaque Copy type.
+    /// The copy that gets passed to Rust only lives until the function returns, so the future
+    /// cannot borrow from it.
+    AsyncCopyMethodBorrowsSelf {
+        self_: FnArg,
+    },
+    /// `async fn foo (&mut self)` in an `extern "Rust"` block.
+    /// Swift can't stop other code from using the instance while the future is running, so the
+    /// future can't hold on to a mutable borrow of it.
+    AsyncMethodMutSelf {
+        self_: FnArg,
+    },
+    /// `fn foo () -> Result<u8, String>`, or a `Result` argument or struct field.
+    /// Only async Rust functions can return a `Result`, since they become `async throws` Swift
+    /// functions.
+    UnsupportedResultType {
+        ty: Type,
+    },
+    /// `async fn foo (arg: &SomeType)` in an `extern "Rust"` block.
+    /// The future would outlive the borrow, since Swift only keeps the argument alive until the
+    /// function returns. `&str` and `&[T]` are allowed since they get copied before the future is
+    /// created.
+    AsyncFnArgBorrow {
+        arg: FnArg,
+    },
+    /// `async fn foo (arg: SomeSwiftType)` in an `extern "Rust"` block without
+    /// `#[swift_bridge(local)]`.
+    /// Handles to Swift types are not `Send`, so the future can't be spawned on the executor.
+    AsyncFnOwnedSwiftArgNotLocal {
+        arg: FnArg,
+    },
+    /// `#[swift_bridge(Equatable)] type SomeType;` or `#[swift_bridge(From(u8))] type SomeType;`
+    /// in an `extern "Swift"` block, or on a generic or Copy opaque Rust type.
+    /// Protocol conformances and conversion initializers are only generated for the Swift classes
+    /// of opaque Rust types.
+    UnsupportedProtocolConformance {
+        ty: Ident,
+    },
+    /// `#[swift_bridge(Codable)] struct SomeStruct { field: [u8; 4] }`
+    /// Codable fields must be numbers, bools, Strings, shared types or Options of them.
+    UnsupportedCodableField {
+        /// The field's name, such as "field" or "_0".
+        field: String,
+        ty: Type,
+    },
+    /// `#[swift_bridge(Codable)] enum SomeEnum { Variant(u8) }`
+    /// Only enums whose variants don't have fields can be Codable.
+    CodableEnumVariantWithFields {
+        variant: Ident,
+    },
+    /// `#[swift_bridge(operators(Index(Output = String)))]`
+    /// The index output gets copied out of the Rust value, so it must be a number or a bool.
+    UnsupportedIndexOutput {
+        output: Type,
+    },
+    /// `#[swift_bridge(Default)] type SomeType;` along with
+    /// `#[swift_bridge(init)] fn new() -> SomeType;`
+    /// Both would generate a parameterless Swift initializer.
+    DefaultConflictsWithInit {
+        sig: Signature,
+    },
+    /// `trait SomeTrait { fn foo(self); }`
+    /// Trait methods must borrow `self` so that they can be called on a `Box<dyn SomeTrait>`.
+    TraitMethodMustBorrowSelf {
+        sig: Signature,
+    },
+    /// `trait SomeTrait { type Output; }`, or a trait method that is generic, async or has a
+    /// default implementation.
+    UnsupportedTraitItem {
+        item: Box<TraitItem>,
+    },
+    /// `impl SomeTrait for SomeType { ... }` where `SomeTrait` wasn't declared in the bridge
+    /// module, `SomeType` isn't an opaque Rust type or the impl block isn't empty.
+    InvalidTraitImpl {
+        item_impl: Box<ItemImpl>,
+    },
+    /// `#[swift_bridge(TryFrom(i64, Error = u8))]`
+    /// Swift can only throw errors that are passed to Swift as a class, so the error must be a
+    /// `String` or an owned opaque Rust type.
+    UnsupportedTryFromError {
+        ty: Type,
+    },
+    /// `#[swift_bridge(From(u8))] type SomeType;` along with
+    /// `#[swift_bridge(init)] fn new(value: u8) -> SomeType;`, or the same conversion twice.
+    /// Both would generate an `init(_: UInt8)`.
+    ConflictingInitializer {
+        /// The conversion's type or the initializer function's signature.
+        initializer: TokenStream,
+        /// The Swift initializer, such as "init(_: UInt8)".
+        swift_signature: String,
+    },
 }
 
 /// An error while parsing a function attribute.
 pub(crate) enum FunctionAttributeParseError {
     Identifiable(IdentifiableParseError),
+    Local(LocalParseError),
 }
 
 /// An error while parsing a function's `Identifiable` attribute.
@@ -74,6 +203,12 @@ pub(crate) enum IdentifiableParseError {
     MissingReturnType { fn_ident: Ident },
 }
 
+/// An error while parsing a function's `local` attribute.
+pub(crate) enum LocalParseError {
+    /// Only async functions in `extern "Rust"` blocks have a future that needs to be polled.
+    MustBeAsyncRustFunction { fn_ident: Ident },
+}
+
 impl Into<syn::Error> for ParseError {
     fn into(self) -> Error {
         match self {
@@ -185,12 +320,162 @@ struct {struct_name};
                         Error::new_spanned(fn_ident, message)
                     }
                 },
+                FunctionAttributeParseError::Local(local) => match local {
+                    LocalParseError::MustBeAsyncRustFunction { fn_ident } => {
+                        let message = format!(
+                            r#"The local attribute can only be used on async functions in extern "Rust" blocks, but {} is not one."#,
+                            fn_ident
+                        );
+                        Error::new_spanned(fn_ident, message)
+                    }
+                },
             },
             ParseError::ArgCopyAndRefMut { arg } => {
                 let message =
                     format!(r#"Mutable references to opaque Copy types are not yet supported."#);
                 Error::new_spanned(arg, message)
             }
+            ParseError::AsyncSwiftFnArgBorrow { arg } => Error::new_spanned(
+                arg,
+                r#"Async functions in extern "Swift" blocks can't take borrowed arguments, since the Swift
+task outlives the borrow. Consider taking an owned value instead.
+"#,
+            ),
+            ParseError::AsyncSwiftInitializer { fn_ident } => {
+                let message = format!(
+                    r#"Async initializers are not yet supported in extern "Swift" blocks, but {} is one."#,
+                    fn_ident
+                );
+                Error::new_spanned(fn_ident, message)
+            }
+            ParseError::AsyncSwiftFnAssociatedToSharedType { fn_ident } => {
+                let message = format!(
+                    r#"Async functions in extern "Swift" blocks can't yet be associated to a shared struct or enum, but {} is."#,
+                    fn_ident
+                );
+                Error::new_spanned(fn_ident, message)
+            }
+            ParseError::UnsupportedOptionType { ty } => Error::new_spanned(
+                ty,
+                r#"Option<[T; N]>, Option<impl Iterator>, Option<Result<T, E>>, Option<impl Stream> and
+Option<Box<dyn Trait>> are not yet supported.
+"#,
+            ),
+            ParseError::UnsupportedArrayType { ty } => Error::new_spanned(
+                ty,
+                r#"Arrays must have a non-zero integer literal length and elements that are primitives, shared
+structs or shared enums. Arrays can't be used as the element type of a Vec.
+"#,
+            ),
+            ParseError::IteratorFnArgBorrow { arg } => Error::new_spanned(
+                arg,
+                r#"Functions that return an iterator can only borrow opaque Rust types, since Swift keeps them
+alive for as long as the iterator. Consider taking an owned value instead.
+"#,
+            ),
+            ParseError::UnsupportedSelfType { self_ } => Error::new_spanned(
+                self_,
+                r#"Unsupported type for self. Methods must take one of:
+self
+&self
+&mut self
+"#,
+            ),
+            ParseError::AsyncCopyMethodBorrowsSelf { self_ } => Error::new_spanned(
+                self_,
+                r#"Async methods on Copy types must take `self` by value, since the returned future
+cannot borrow from the copy of the value that gets passed to Rust.
+"#,
+            ),
+            ParseError::AsyncMethodMutSelf { self_ } => Error::new_spanned(
+                self_,
+                r#"Async Rust methods can't take `&mut self`, since Swift code could use the instance while
+the future is running. Take `&self` and use interior mutability, or take `self` by value.
+"#,
+            ),
+            ParseError::UnsupportedResultType { ty } => Error::new_spanned(
+                ty,
+                r#"Result<T, E> is currently only supported as the return type of async functions in
+extern "Rust" blocks.
+"#,
+            ),
+            ParseError::AsyncFnArgBorrow { arg } => Error::new_spanned(
+                arg,
+                r#"Async Rust functions can only borrow `&str` and `&[T]` arguments, which get copied
+before the future is created. Other arguments must be passed by value, since the future would
+outlive the borrow.
+"#,
+            ),
+            ParseError::AsyncFnOwnedSwiftArgNotLocal { arg } => Error::new_spanned(
+                arg,
+                r#"Swift types are not `Send`, so they can only be passed to async Rust functions that
+use `#[swift_bridge(local)]`.
+"#,
+            ),
+            ParseError::UnsupportedProtocolConformance { ty } => Error::new_spanned(
+                ty,
+                r#"Swift protocol conformances such as `Equatable` and conversion initializers such as
+`From(u8)` can only be generated for opaque Rust types that are neither generic nor Copy.
+"#,
+            ),
+            ParseError::UnsupportedCodableField { field, ty } => Error::new_spanned(
+                ty,
+                format!(
+                    r#"The "{}" field's type is not supported by the Codable attribute. Codable fields currently
+must be numbers, bools, Strings, shared structs, shared enums or Options of them.
+"#,
+                    field
+                ),
+            ),
+            ParseError::CodableEnumVariantWithFields { variant } => Error::new_spanned(
+                variant,
+                r#"The Codable attribute is currently only supported for enums whose variants don't have fields.
+"#,
+            ),
+            ParseError::UnsupportedIndexOutput { output } => Error::new_spanned(
+                output,
+                r#"Index operators currently only support number and bool outputs.
+"#,
+            ),
+            ParseError::DefaultConflictsWithInit { sig } => Error::new_spanned(
+                sig,
+                r#"This initializer takes no arguments, so it conflicts with the init() that the type's
+Default attribute generates. Remove one of them.
+"#,
+            ),
+            ParseError::TraitMethodMustBorrowSelf { sig } => Error::new_spanned(
+                sig,
+                r#"Trait methods must take `&self` or `&mut self`.
+"#,
+            ),
+            ParseError::UnsupportedTraitItem { item } => Error::new_spanned(
+                item,
+                r#"Traits can only contain methods without generics or default implementations.
+"#,
+            ),
+            ParseError::InvalidTraitImpl { item_impl } => Error::new_spanned(
+                item_impl,
+                r#"Trait implementations must be empty `impl SomeTrait for SomeType {}` blocks, where
+`SomeTrait` is declared in the bridge module and `SomeType` is an opaque Rust type that is neither
+generic nor Copy.
+"#,
+            ),
+            ParseError::UnsupportedTryFromError { ty } => Error::new_spanned(
+                ty,
+                r#"TryFrom errors currently only support String and owned opaque Rust types.
+"#,
+            ),
+            ParseError::ConflictingInitializer {
+                initializer,
+                swift_signature,
+            } => Error::new_spanned(
+                initializer,
+                format!(
+                    r#"This generates a Swift `{}`, which conflicts with another initializer of the same type.
+"#,
+                    swift_signature
+                ),
+            ),
         }
     }
 }
@@ -4,14 +4,21 @@ use crate::errors::{ParseError, ParseErrors};
 use crate::parse::parse_enum::SharedEnumDeclarationParser;
 use crate::parse::parse_extern_mod::ForeignModParser;
 use crate::parse::parse_struct::SharedStructDeclarationParser;
+use crate::parse::parse_trait::{TraitDeclarationParser, TraitImplParser};
+use crate::parse::validate_types::{unsupported_array, validate_types};
 use crate::SwiftBridgeModule;
 use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::{Item, ItemMod};
 
+mod operators;
 mod parse_enum;
 mod parse_extern_mod;
 mod parse_struct;
+mod parse_trait;
+mod validate_types;
+
+pub(crate) use self::operators::Operators;
 
 mod type_declarations;
 pub(crate) use self::type_declarations::*;
@@ -60,6 +67,7 @@ impl Parse for SwiftBridgeModuleAndErrors {
             let mut functions = vec![];
             let mut type_declarations = TypeDeclarations::default();
             let mut unresolved_types = vec![];
+            let mut trait_impls = vec![];
             let mut cfg_attrs = vec![];
 
             for attr in item_mod.attrs {
@@ -105,27 +113,54 @@ impl Parse for SwiftBridgeModuleAndErrors {
                             TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)),
                         );
                     }
+                    Item::Trait(item_trait) => {
+                        let trait_declaration = TraitDeclarationParser {
+                            item_trait,
+                            errors: &mut errors,
+                            unresolved_types: &mut unresolved_types,
+                        }
+                        .parse();
+                        type_declarations.insert_trait(trait_declaration);
+                    }
+                    Item::Impl(item_impl) => {
+                        trait_impls.push(item_impl);
+                    }
                     _ => {
                         todo!(
                             r#"
-                        Push an error that the module may only contain `extern` blocks, structs
-                        and enums
+                        Push an error that the module may only contain `extern` blocks, structs,
+                        enums, traits and trait impls
                         "#
                         )
                     }
                 };
             }
 
+            for item_impl in trait_impls {
+                TraitImplParser {
+                    item_impl,
+                    errors: &mut errors,
+                    type_declarations: &mut type_declarations,
+                }
+                .parse();
+            }
+
             for unresolved_type in unresolved_types.into_iter() {
                 if BridgedType::new_with_type(&unresolved_type, &type_declarations).is_some() {
                     continue;
                 }
 
-                errors.push(ParseError::UndeclaredType {
-                    ty: unresolved_type.clone(),
-                });
+                if let Some(array) = unsupported_array(&unresolved_type, &type_declarations) {
+                    errors.push(ParseError::UnsupportedArrayType { ty: array.clone() });
+                } else {
+                    errors.push(ParseError::UndeclaredType {
+                        ty: unresolved_type.clone(),
+                    });
+                }
             }
 
+            validate_types(&functions, &type_declarations, &mut errors);
+
             let module = SwiftBridgeModule {
                 name: module_name,
                 types: type_declarations,
@@ -0,0 +1,164 @@
+use crate::bridged_type::BridgedType;
+use crate::errors::{ParseError, ParseErrors};
+use crate::parse::TypeDeclarations;
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{Token, Type};
+
+/// `#[swift_bridge(operators(Add, Mul, Index(Output = f32)))]`
+///
+/// The `std::ops` traits that a bridged type's Swift representation gets operators for.
+#[derive(Default, Clone)]
+pub(crate) struct Operators {
+    pub add: bool,
+    pub sub: bool,
+    pub mul: bool,
+    pub div: bool,
+    pub neg: bool,
+    /// The `Output` type of the type's `Index<usize>` implementation.
+    pub index: Option<Box<Type>>,
+}
+
+/// A binary operator such as `+`, backed by a trait such as `std::ops::Add`.
+pub(crate) struct BinaryOperator {
+    /// The trait, such as "Add".
+    pub trait_name: &'static str,
+    /// The trait's method, such as "add".
+    pub method: &'static str,
+    /// The Swift operator, such as "+".
+    pub swift_operator: &'static str,
+}
+
+impl Operators {
+    /// Whether or not any operators were requested.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.binary_operators().is_empty() && !self.neg && self.index.is_none()
+    }
+
+    /// The binary operators that were requested, in a consistent order.
+    pub(crate) fn binary_operators(&self) -> Vec<BinaryOperator> {
+        [
+            (self.add, "Add", "add", "+"),
+            (self.sub, "Sub", "sub", "-"),
+            (self.mul, "Mul", "mul", "*"),
+            (self.div, "Div", "div", "/"),
+        ]
+        .into_iter()
+        .filter(|(enabled, _, _, _)| *enabled)
+        .map(|(_, trait_name, method, swift_operator)| BinaryOperator {
+            trait_name,
+            method,
+            swift_operator,
+        })
+        .collect()
+    }
+
+    /// Push an error if the `Index` output can't be copied across the FFI boundary.
+    pub(super) fn validate(&self, types: &TypeDeclarations, errors: &mut ParseErrors) {
+        let output = match self.index.as_ref() {
+            Some(output) => output,
+            None => return,
+        };
+
+        match BridgedType::new_with_type(output, types) {
+            Some(BridgedType::StdLib(stdlib)) if stdlib.is_primitive() => {}
+            _ => errors.push(ParseError::UnsupportedIndexOutput {
+                output: output.as_ref().clone(),
+            }),
+        }
+    }
+}
+
+impl Parse for Operators {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut operators = Operators::default();
+
+        let content;
+        syn::parenthesized!(content in input);
+
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+
+            match key.to_string().as_str() {
+                "Add" => operators.add = true,
+                "Sub" => operators.sub = true,
+                "Mul" => operators.mul = true,
+                "Div" => operators.div = true,
+                "Neg" => operators.neg = true,
+                // Index(Output = f32)
+                "Index" => {
+                    let index;
+                    syn::parenthesized!(index in content);
+
+                    let output: Ident = index.parse()?;
+                    if output != "Output" {
+                        return Err(syn::Error::new_spanned(
+                            output,
+                            r#"Expected the index output type, such as "Index(Output = f32)"."#,
+                        ));
+                    }
+                    index.parse::<Token![=]>()?;
+
+                    operators.index = Some(Box::new(index.parse()?));
+                }
+                _ => {
+                    let operator = key.to_string();
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        format!(r#"Unsupported operator trait "{}"."#, operator),
+                    ));
+                }
+            };
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(operators)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::test_utils::parse_errors;
+    use quote::{quote, ToTokens};
+
+    /// Verify that we push an error for `Index` outputs that aren't numbers or bools.
+    #[test]
+    fn error_if_unsupported_index_output() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", operators(Index(Output = String)))]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(operators(Index(Output = f32)))]
+                    type Supported;
+
+                    #[swift_bridge(operators(Index(Output = Supported)))]
+                    type Unsupported;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        let mut outputs = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedIndexOutput { output } => {
+                    outputs.push(output.to_token_stream().to_string());
+                }
+                _ => panic!(),
+            }
+        }
+        assert!(outputs.contains(&"String".to_string()));
+        assert!(outputs.contains(&"Supported".to_string()));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::bridged_type::{EnumVariant, SharedEnum, StructFields};
 use crate::errors::ParseErrors;
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
 use syn::ItemEnum;
 
 pub(crate) struct SharedEnumDeclarationParser<'a> {
@@ -9,10 +11,65 @@ pub(crate) struct SharedEnumDeclarationParser<'a> {
     pub errors: &'a mut ParseErrors,
 }
 
+enum EnumAttr {
+    Codable,
+}
+
+#[derive(Default)]
+struct EnumAttribs {
+    codable: bool,
+}
+
+impl Parse for EnumAttribs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attribs = EnumAttribs::default();
+
+        let opts =
+            syn::punctuated::Punctuated::<EnumAttr, syn::token::Comma>::parse_terminated(input)?;
+
+        for attr in opts.into_iter() {
+            match attr {
+                EnumAttr::Codable => attribs.codable = true,
+            }
+        }
+
+        Ok(attribs)
+    }
+}
+
+impl Parse for EnumAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        let attr = match key.to_string().as_str() {
+            "Codable" => EnumAttr::Codable,
+            _ => {
+                let attrib = key.to_string();
+                Err(syn::Error::new_spanned(
+                    key,
+                    format!(r#"Unrecognized attribute "{}"."#, attrib),
+                ))?
+            }
+        };
+
+        Ok(attr)
+    }
+}
+
 impl<'a> SharedEnumDeclarationParser<'a> {
     pub fn parse(self) -> Result<SharedEnum, syn::Error> {
         let item_enum = self.item_enum;
 
+        let mut attribs = EnumAttribs::default();
+        for attr in item_enum.attrs.iter() {
+            if !attr.path.is_ident("swift_bridge") {
+                continue;
+            }
+
+            let parsed: EnumAttribs = attr.parse_args()?;
+            attribs.codable |= parsed.codable;
+        }
+
         let mut variants = vec![];
 
         for v in item_enum.variants {
@@ -26,6 +83,7 @@ impl<'a> SharedEnumDeclarationParser<'a> {
         let shared_enum = SharedEnum {
             name: item_enum.ident,
             variants,
+            codable: attribs.codable,
         };
 
         Ok(shared_enum)
@@ -105,4 +163,23 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    /// Verify that we can parse the `Codable` attribute.
+    #[test]
+    fn parse_enum_codable_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(Codable)]
+                enum SomeEnum {
+                    SomeVariant
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = &module.types.types()[0].unwrap_shared_enum();
+        assert!(ty.codable);
+    }
 }
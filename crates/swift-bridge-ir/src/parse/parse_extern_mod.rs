@@ -1,19 +1,27 @@
 pub(crate) use self::opaque_type_attributes::OpaqueTypeAllAttributes;
-use crate::bridged_type::{pat_type_pat_is_self, BridgedType};
-use crate::errors::{FunctionAttributeParseError, IdentifiableParseError, ParseError, ParseErrors};
+use crate::bridged_type::{pat_type_pat_is_self, BridgedType, CustomBridgedType};
+use crate::errors::{
+    FunctionAttributeParseError, IdentifiableParseError, LocalParseError, ParseError, ParseErrors,
+};
 use crate::parse::parse_extern_mod::function_attributes::FunctionAttributes;
 use crate::parse::parse_extern_mod::generics::GenericOpaqueType;
 use crate::parse::type_declarations::{
     OpaqueForeignTypeDeclaration, TypeDeclaration, TypeDeclarations,
 };
 use crate::parse::{HostLang, OpaqueRustTypeGenerics};
-use crate::parsed_extern_fn::{fn_arg_is_mutable_reference, fn_arg_is_opaque_copy_type};
+use crate::parsed_extern_fn::{
+    async_fn_arg_is_copied, fn_arg_is_mutable_reference, fn_arg_is_opaque_copy_type,
+    type_contains_reference,
+};
 use crate::ParsedExternFn;
 use quote::ToTokens;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::ops::Deref;
-use syn::{FnArg, ForeignItem, ForeignItemFn, GenericParam, ItemForeignMod, Pat, ReturnType, Type};
+use syn::{
+    FnArg, ForeignItem, ForeignItemFn, GenericParam, ItemForeignMod, Pat, Receiver, ReturnType,
+    Token, Type,
+};
 
 mod function_attributes;
 mod generics;
@@ -79,19 +87,38 @@ impl<'a> ForeignModParser<'a> {
                         attributes: OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?,
                         generics: OpaqueRustTypeGenerics::new(),
                     };
+                    self.validate_protocol_conformances(&foreign_type);
+                    for ty in foreign_type.attributes.conversion_types() {
+                        self.unresolved_types.push(ty.clone());
+                    }
                     self.type_declarations.insert(
                         ty_name.clone(),
                         TypeDeclaration::Opaque(foreign_type.clone()),
                     );
                     local_type_declarations.insert(ty_name, foreign_type);
                 }
-                ForeignItem::Fn(func) => {
+                ForeignItem::Fn(mut func) => {
                     let mut attributes = FunctionAttributes::default();
 
                     for attr in func.attrs.iter() {
                         attributes = attr.parse_args()?;
                     }
 
+                    if let Some(first) = func.sig.inputs.first_mut() {
+                        if let Some(receiver) = self_type_as_receiver(first) {
+                            *first = FnArg::Receiver(receiver);
+                        }
+                    }
+
+                    if let Some(self_) = func.sig.receiver() {
+                        if !self.is_supported_self_type(self_) {
+                            self.errors.push(ParseError::UnsupportedSelfType {
+                                self_: self_.clone(),
+                            });
+                            continue;
+                        }
+                    }
+
                     for arg in func.sig.inputs.iter() {
                         if let FnArg::Typed(pat_ty) = arg {
                             let ty = &pat_ty.ty;
@@ -157,6 +184,17 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
 
+                    if attributes.is_local && (func.sig.asyncness.is_none() || host_lang.is_swift())
+                    {
+                        self.errors.push(ParseError::FunctionAttribute(
+                            FunctionAttributeParseError::Local(
+                                LocalParseError::MustBeAsyncRustFunction {
+                                    fn_ident: func.sig.ident.clone(),
+                                },
+                            ),
+                        ));
+                    }
+
                     for arg in func.sig.inputs.iter() {
                         let is_mutable_ref = fn_arg_is_mutable_reference(arg);
 
@@ -172,6 +210,92 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
 
+                    if func.sig.asyncness.is_some() && host_lang.is_swift() {
+                        if attributes.is_swift_initializer {
+                            self.errors.push(ParseError::AsyncSwiftInitializer {
+                                fn_ident: func.sig.ident.clone(),
+                            });
+                        }
+                        if let Some(TypeDeclaration::Shared(_)) = associated_type {
+                            self.errors
+                                .push(ParseError::AsyncSwiftFnAssociatedToSharedType {
+                                    fn_ident: func.sig.ident.clone(),
+                                });
+                        }
+
+                        for arg in func.sig.inputs.iter() {
+                            let pat_ty = match arg {
+                                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+                                _ => continue,
+                            };
+
+                            if type_contains_reference(&pat_ty.ty) {
+                                self.errors
+                                    .push(ParseError::AsyncSwiftFnArgBorrow { arg: arg.clone() });
+                            }
+                        }
+                    }
+
+                    if let Some(self_) = func.sig.receiver() {
+                        let borrows_self = match self_ {
+                            FnArg::Receiver(receiver) => receiver.reference.is_some(),
+                            FnArg::Typed(pat_ty) => matches!(pat_ty.ty.deref(), Type::Reference(_)),
+                        };
+
+                        if func.sig.asyncness.is_some()
+                            && borrows_self
+                            && !fn_arg_is_mutable_reference(self_)
+                            && fn_arg_is_opaque_copy_type(
+                                &associated_type,
+                                self_,
+                                self.type_declarations,
+                            )
+                        {
+                            self.errors.push(ParseError::AsyncCopyMethodBorrowsSelf {
+                                self_: self_.clone(),
+                            });
+                        }
+
+                        if func.sig.asyncness.is_some()
+                            && host_lang.is_rust()
+                            && fn_arg_is_mutable_reference(self_)
+                        {
+                            self.errors.push(ParseError::AsyncMethodMutSelf {
+                                self_: self_.clone(),
+                            });
+                        }
+                    }
+
+                    if func.sig.asyncness.is_some() && host_lang.is_rust() {
+                        for arg in func.sig.inputs.iter() {
+                            let pat_ty = match arg {
+                                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+                                _ => continue,
+                            };
+
+                            if async_fn_arg_is_copied(&pat_ty.ty) {
+                                continue;
+                            }
+
+                            if type_contains_reference(&pat_ty.ty) {
+                                self.errors
+                                    .push(ParseError::AsyncFnArgBorrow { arg: arg.clone() });
+                                continue;
+                            }
+
+                            let is_owned_swift_type = matches!(
+                                BridgedType::new_with_fn_arg(arg, self.type_declarations),
+                                Some(BridgedType::Foreign(CustomBridgedType::Opaque(opaque)))
+                                    if opaque.host_lang.is_swift()
+                            );
+                            if is_owned_swift_type && !attributes.is_local {
+                                self.errors.push(ParseError::AsyncFnOwnedSwiftArgNotLocal {
+                                    arg: arg.clone(),
+                                });
+                            }
+                        }
+                    }
+
                     let func = ParsedExternFn {
                         func,
                         associated_type,
@@ -184,6 +308,7 @@ impl<'a> ForeignModParser<'a> {
                         return_with: attributes.return_with,
                         args_into: attributes.args_into,
                         get_field: attributes.get_field,
+                        is_local: attributes.is_local,
                     };
                     self.functions.push(func);
                 }
@@ -214,6 +339,7 @@ impl<'a> ForeignModParser<'a> {
                                     .collect(),
                             },
                         };
+                        self.validate_protocol_conformances(&foreign_ty);
                         let generics: Vec<String> = foreign_ty
                             .generics
                             .generics
@@ -234,6 +360,44 @@ impl<'a> ForeignModParser<'a> {
         Ok(())
     }
 
+    /// Protocol conformances such as `#[swift_bridge(Equatable)]` and conversion initializers
+    /// such as `#[swift_bridge(From(u8))]` are only supported on opaque Rust types that are
+    /// neither generic nor Copy.
+    fn validate_protocol_conformances(&mut self, ty: &OpaqueForeignTypeDeclaration) {
+        if !ty.attributes.has_protocol_conformances() && !ty.attributes.has_conversions() {
+            return;
+        }
+
+        if ty.host_lang.is_swift() || !ty.generics.is_empty() || ty.attributes.copy.is_some() {
+            self.errors
+                .push(ParseError::UnsupportedProtocolConformance { ty: ty.ty.clone() });
+        }
+    }
+
+    /// `self`, `&self` and `&mut self` are supported, as well as their explicitly typed forms such
+    /// as `self: &SomeType`. `self: &Self` has already been turned into `&self` by
+    /// [`self_type_as_receiver`].
+    ///
+    /// Smart pointers such as `self: Arc<Self>` are not supported.
+    fn is_supported_self_type(&self, self_: &FnArg) -> bool {
+        let pat_ty = match self_ {
+            FnArg::Receiver(_) => return true,
+            FnArg::Typed(pat_ty) => pat_ty,
+        };
+
+        let self_ty = match pat_ty.ty.deref() {
+            Type::Path(ty_path) => ty_path.path.segments.to_token_stream(),
+            Type::Reference(type_ref) => match type_ref.elem.deref() {
+                Type::Path(ty_path) => ty_path.path.segments.to_token_stream(),
+                _ => return false,
+            },
+            _ => return false,
+        };
+
+        let self_ty_string = self_ty.to_string().replace(" ", "");
+        self.type_declarations.get(&self_ty_string).is_some()
+    }
+
     fn get_associated_type(
         &mut self,
         first: Option<&FnArg>,
@@ -330,6 +494,40 @@ Otherwise we use a more general error that says that your argument is invalid.
     }
 }
 
+/// `self: Self`, `self: &Self` and `self: &mut Self` become `self`, `&self` and `&mut self`, since
+/// `Self` is the type that the method is associated with.
+fn self_type_as_receiver(self_: &FnArg) -> Option<Receiver> {
+    let pat_ty = match self_ {
+        FnArg::Typed(pat_ty) if pat_type_pat_is_self(pat_ty) => pat_ty,
+        _ => return None,
+    };
+    let self_token = match pat_ty.pat.deref() {
+        Pat::Ident(pat_ident) => Token![self](pat_ident.ident.span()),
+        _ => return None,
+    };
+
+    let (reference, mutability, elem) = match pat_ty.ty.deref() {
+        Type::Reference(type_ref) => (
+            Some((type_ref.and_token, type_ref.lifetime.clone())),
+            type_ref.mutability,
+            type_ref.elem.deref(),
+        ),
+        ty => (None, None, ty),
+    };
+
+    match elem {
+        Type::Path(ty_path) if ty_path.qself.is_none() && ty_path.path.is_ident("Self") => {
+            Some(Receiver {
+                attrs: pat_ty.attrs.clone(),
+                reference,
+                mutability,
+                self_token,
+            })
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::errors::ParseError;
@@ -758,6 +956,270 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the `Equatable` and `Hashable` attributes.
+    #[test]
+    fn parse_equatable_and_hashable_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Equatable)]
+                    type SomeType;
+
+                    #[swift_bridge(Hashable)]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let some_type = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(some_type.attributes.equatable);
+        assert!(!some_type.attributes.hashable);
+
+        let another_type = module.types.get("AnotherType").unwrap().unwrap_opaque();
+        assert!(another_type.attributes.hashable);
+        assert!(another_type.attributes.is_equatable());
+    }
+
+    /// Verify that we can parse the `Comparable` attribute.
+    #[test]
+    fn parse_comparable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let some_type = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(some_type.attributes.comparable);
+        assert!(some_type.attributes.is_equatable());
+    }
+
+    /// Verify that we can parse the `CustomStringConvertible` and `CustomDebugStringConvertible`
+    /// attributes.
+    #[test]
+    fn parse_custom_string_convertible_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let some_type = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(some_type.attributes.custom_string_convertible);
+        assert!(some_type.attributes.custom_debug_string_convertible);
+        assert!(!some_type.attributes.is_equatable());
+    }
+
+    /// Verify that we can parse the `operators` attribute.
+    #[test]
+    fn parse_operators_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(operators(Add, Sub, Neg, Index(Output = f32)))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let operators = &module
+            .types
+            .get("SomeType")
+            .unwrap()
+            .unwrap_opaque()
+            .attributes
+            .operators;
+        assert!(operators.add);
+        assert!(operators.sub);
+        assert!(!operators.mul);
+        assert!(!operators.div);
+        assert!(operators.neg);
+        assert_eq!(
+            operators
+                .index
+                .as_ref()
+                .unwrap()
+                .to_token_stream()
+                .to_string(),
+            "f32"
+        );
+    }
+
+    /// Verify that we can parse the `Default` attribute.
+    #[test]
+    fn parse_default_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Default)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let some_type = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(some_type.attributes.default);
+    }
+
+    /// Verify that we push an error for protocol conformances on types that we can't generate
+    /// them for.
+    #[test]
+    fn error_if_unsupported_protocol_conformance() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Equatable)]
+                    type SomeCopyType;
+
+                    #[swift_bridge(Hashable)]
+                    type SomeGenericType<u32>;
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(Equatable)]
+                    type SomeSwiftType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedProtocolConformance { ty: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we can parse the `From`, `TryFrom` and `FromStr` attributes.
+    #[test]
+    fn parse_conversion_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(From(u8), From(&str), TryFrom(i64), TryFrom(String, Error = String), FromStr)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let some_type = module.types.get("SomeType").unwrap().unwrap_opaque();
+        let attributes = &some_type.attributes;
+
+        assert_eq!(attributes.from.len(), 2);
+        assert_eq!(attributes.from[0].to_token_stream().to_string(), "u8");
+        assert_eq!(attributes.from[1].to_token_stream().to_string(), "& str");
+
+        assert_eq!(attributes.try_from.len(), 2);
+        assert_eq!(
+            attributes.try_from[0].ty.to_token_stream().to_string(),
+            "i64"
+        );
+        assert!(attributes.try_from[0].error.is_none());
+        assert_eq!(
+            attributes.try_from[1].error.to_token_stream().to_string(),
+            "String"
+        );
+
+        assert!(attributes.from_str);
+    }
+
+    /// Verify that we push an error for conversion initializers on types that we can't generate
+    /// them for.
+    #[test]
+    fn error_if_unsupported_conversion_initializer() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), From(u32))]
+                    type SomeCopyType;
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(FromStr)]
+                    type SomeSwiftType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedProtocolConformance { ty: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for conversions from, or to errors of, undeclared types.
+    #[test]
+    fn error_if_conversion_type_is_undeclared() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(From(UnknownType), TryFrom(AnotherUnknownType), TryFrom(u8, Error = UnknownError))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        let mut tys = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::UndeclaredType { ty } => tys.push(ty.to_token_stream().to_string()),
+                _ => panic!(),
+            }
+        }
+        assert_eq!(
+            tys,
+            vec!["UnknownType", "AnotherUnknownType", "UnknownError"]
+        );
+    }
+
+    /// Verify that conversions can use types that are declared later in the module.
+    #[test]
+    fn conversion_type_declared_later() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(From(AnotherType), TryFrom(u8, Error = AnotherType))]
+                    type SomeType;
+                }
+
+                extern "Rust" {
+                    type AnotherType;
+                }
+            }
+        };
+
+        parse_ok(tokens);
+    }
+
     /// Verify that we can parse multiple atributes from an opaque type.
     #[test]
     fn parse_multiple_attributes() {
@@ -833,4 +1295,263 @@ mod tests {
             }
         }
     }
+
+    /// Verify that we push errors for borrowed arguments to async Swift functions, along with async
+    /// Swift initializers.
+    #[test]
+    fn error_if_async_swift_fn_borrows_arg_or_is_initializer() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Swift" {
+                    type SomeType;
+
+                    async fn a(arg: &str);
+                    async fn b(arg: &[u8]);
+                    async fn c(&self, arg: String);
+                    fn d(arg: &str);
+                    #[swift_bridge(init)]
+                    async fn new() -> SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+
+        let mut borrowed_args = 0;
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncSwiftFnArgBorrow { arg: _ } => borrowed_args += 1,
+                ParseError::AsyncSwiftInitializer { fn_ident } => {
+                    assert_eq!(fn_ident.to_string(), "new")
+                }
+                _ => panic!(),
+            }
+        }
+        assert_eq!(borrowed_args, 2);
+    }
+
+    /// Verify that we push an error for async Swift functions that are associated to a shared
+    /// type, since we don't generate the Swift code to call them.
+    #[test]
+    fn error_if_async_swift_fn_associated_to_shared_type() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                struct SomeStruct;
+
+                extern "Swift" {
+                    #[swift_bridge(associated_to = SomeStruct)]
+                    async fn a();
+                    #[swift_bridge(associated_to = SomeStruct)]
+                    fn b();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::AsyncSwiftFnAssociatedToSharedType { fn_ident } => {
+                assert_eq!(fn_ident.to_string(), "a")
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push errors for methods that take `self` in a smart pointer.
+    #[test]
+    fn error_if_unsupported_self_type() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a(self: Arc<Self>);
+                    async fn b(self: Arc<SomeType>);
+                    fn c(self: Pin<&mut Self>);
+                    fn d(self: &Box<SomeType>);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 4);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedSelfType { self_: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that `Self` refers to the type that a method is associated with.
+    #[test]
+    fn parse_self_type_receivers() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a(self: &Self);
+                    fn b(self: &mut Self);
+                    fn c(self: Self);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+        let functions = &module.functions;
+
+        assert_eq!(functions.len(), 3);
+        for function in functions.iter() {
+            assert!(function.is_method());
+            assert_eq!(
+                function
+                    .associated_type
+                    .as_ref()
+                    .unwrap()
+                    .as_opaque()
+                    .unwrap()
+                    .ty,
+                "SomeType"
+            );
+        }
+
+        assert!(functions[0].self_reference().is_some());
+        assert!(functions[0].self_mutability().is_none());
+        assert!(functions[1].self_mutability().is_some());
+        assert!(functions[2].self_reference().is_none());
+    }
+
+    /// Verify that we push an error for async Rust methods that take `&mut self`.
+    #[test]
+    fn error_if_async_rust_method_borrows_self_mutably() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    async fn a(&mut self);
+                    async fn b(self: &mut SomeType);
+                    async fn c(self: &mut Self);
+                    async fn d(&self);
+                    fn e(&mut self);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncMethodMutSelf { self_: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push errors for async methods that borrow a Copy opaque type's `self`.
+    #[test]
+    fn error_if_async_method_borrows_copy_self() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(2))]
+                    type SomeType;
+
+                    async fn a(&self);
+                    async fn b(self: &SomeType);
+                    async fn c(self);
+                    fn d(&self);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncCopyMethodBorrowsSelf { self_: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push errors for borrowed arguments to async Rust functions, other than
+    /// `&str` and `&[T]` which get copied.
+    #[test]
+    fn error_if_async_fn_borrows_arg() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    async fn a(arg: &SomeType);
+                    async fn b(arg: &mut SomeType);
+                    async fn c(arg: Option<&str>);
+                    async fn d(arg: &mut [u8]);
+                    async fn e(arg1: &str, arg2: &[u8], arg3: SomeType);
+                    async fn f(&self, arg: &str);
+                    fn g(arg: &SomeType);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 4);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncFnArgBorrow { arg: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error if an owned Swift type is passed to an async Rust function
+    /// that does not use the `local` attribute.
+    #[test]
+    fn error_if_owned_swift_arg_to_non_local_async_fn() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Swift" {
+                    type SomeSwiftType;
+                }
+
+                extern "Rust" {
+                    async fn a(arg: SomeSwiftType);
+                    #[swift_bridge(local)]
+                    async fn b(arg: SomeSwiftType);
+                    fn c(arg: SomeSwiftType);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncFnOwnedSwiftArgNotLocal { arg: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
 }
@@ -14,6 +14,7 @@ pub(super) struct FunctionAttributes {
     pub return_with: Option<Path>,
     pub args_into: Option<Vec<Ident>>,
     pub get_field: Option<GetField>,
+    pub is_local: bool,
 }
 
 impl FunctionAttributes {
@@ -43,6 +44,7 @@ impl FunctionAttributes {
             FunctionAttr::GetFieldWith(get_field) => {
                 self.get_field = Some(GetField::With(get_field))
             }
+            FunctionAttr::Local => self.is_local = true,
         }
     }
 }
@@ -58,6 +60,7 @@ pub(super) enum FunctionAttr {
     ArgsInto(Vec<Ident>),
     GetField(GetFieldDirect),
     GetFieldWith(GetFieldWith),
+    Local,
 }
 
 impl Parse for FunctionAttributes {
@@ -93,6 +96,7 @@ impl Parse for FunctionAttr {
                 FunctionAttr::SwiftName(value)
             }
             "init" => FunctionAttr::Init,
+            "local" => FunctionAttr::Local,
             "Identifiable" => FunctionAttr::Identifiable,
             // TODO: Right before we release 0.2.0 we should remove this
             //  "into_return_type" variant since it is deprecated.
@@ -163,7 +167,9 @@ impl Parse for FunctionAttr {
 
 #[cfg(test)]
 mod tests {
-    use crate::errors::{FunctionAttributeParseError, IdentifiableParseError, ParseError};
+    use crate::errors::{
+        FunctionAttributeParseError, IdentifiableParseError, LocalParseError, ParseError,
+    };
     use crate::test_utils::{parse_errors, parse_ok};
     use quote::{quote, ToTokens};
 
@@ -212,6 +218,57 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the local attribute on an async extern "Rust" function.
+    #[test]
+    fn parse_extern_rust_local_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    async fn some_function ();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(module.functions[0].is_local);
+    }
+
+    /// Verify that we push a parse error if we use the local attribute on a function that isn't
+    /// an async extern "Rust" function.
+    #[test]
+    fn error_if_local_attribute_on_non_async_rust_fn() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    fn a ();
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(local)]
+                    async fn b ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+
+        for (idx, expected) in vec!["a", "b"].into_iter().enumerate() {
+            match &errors[idx] {
+                ParseError::FunctionAttribute(FunctionAttributeParseError::Local(
+                    LocalParseError::MustBeAsyncRustFunction { fn_ident },
+                )) => {
+                    assert_eq!(fn_ident, expected);
+                }
+                _ => panic!(),
+            };
+        }
+    }
+
     /// Verify that we can parse an associated function.
     #[test]
     fn parse_associated_function() {
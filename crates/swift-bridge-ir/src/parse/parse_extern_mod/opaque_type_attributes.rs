@@ -1,9 +1,9 @@
-use crate::parse::OpaqueCopy;
+use crate::parse::{OpaqueCopy, Operators};
 use proc_macro2::Ident;
 use quote::ToTokens;
 use std::ops::Deref;
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, LitInt, Meta};
+use syn::{Attribute, LitInt, Meta, Token, Type};
 
 #[derive(Default, Clone)]
 pub(crate) struct OpaqueTypeAllAttributes {
@@ -26,6 +26,54 @@ pub(crate) struct OpaqueTypeSwiftBridgeAttributes {
     /// `#[swift_bridge(declare_generic)]`
     /// Used to declare a generic type.
     pub declare_generic: bool,
+    /// `#[swift_bridge(Equatable)]`
+    /// Conform the Swift class to `Equatable` using the type's `PartialEq` implementation.
+    pub equatable: bool,
+    /// `#[swift_bridge(Hashable)]`
+    /// Conform the Swift class to `Hashable` using the type's `Hash` implementation.
+    /// Since `Hashable` inherits from `Equatable`, this also implies `Equatable`.
+    pub hashable: bool,
+    /// `#[swift_bridge(Comparable)]`
+    /// Conform the Swift class to `Comparable` using the type's `Ord` implementation.
+    /// Since `Comparable` inherits from `Equatable`, this also implies `Equatable`.
+    pub comparable: bool,
+    /// `#[swift_bridge(CustomStringConvertible)]`
+    /// Conform the Swift class to `CustomStringConvertible` using the type's `Display`
+    /// implementation.
+    pub custom_string_convertible: bool,
+    /// `#[swift_bridge(CustomDebugStringConvertible)]`
+    /// Conform the Swift class to `CustomDebugStringConvertible` using the type's `Debug`
+    /// implementation.
+    pub custom_debug_string_convertible: bool,
+    /// `#[swift_bridge(Clone)]`
+    /// Generate a Swift `clone()` method that uses the type's `Clone` implementation.
+    pub clone: bool,
+    /// `#[swift_bridge(Default)]`
+    /// Generate a parameterless Swift initializer that uses the type's `Default` implementation.
+    pub default: bool,
+    /// `#[swift_bridge(operators(Add, Index(Output = f32)))]`
+    /// Generate Swift operators and subscripts that use the type's `std::ops` implementations.
+    pub operators: Operators,
+    /// `#[swift_bridge(From(u8))]`
+    /// Generate a Swift `init(_:)` for each of the type's `From<T>` implementations.
+    pub from: Vec<Type>,
+    /// `#[swift_bridge(TryFrom(i64), TryFrom(String, Error = String))]`
+    /// Generate a Swift `init?(_:)`, or an `init(_:) throws` if the error type is bridged, for
+    /// each of the type's `TryFrom<T>` implementations.
+    pub try_from: Vec<TryFromConversion>,
+    /// `#[swift_bridge(FromStr)]`
+    /// Generate a Swift `init?(_ description: String)` that uses the type's `FromStr`
+    /// implementation. Along with `CustomStringConvertible`, this conforms the Swift class to
+    /// `LosslessStringConvertible`.
+    pub from_str: bool,
+}
+
+/// `TryFrom(i64)` or `TryFrom(String, Error = String)`
+#[derive(Clone)]
+pub(crate) struct TryFromConversion {
+    pub ty: Type,
+    /// The `TryFrom::Error` type, if it should be thrown in Swift.
+    pub error: Option<Type>,
 }
 
 impl OpaqueTypeAllAttributes {
@@ -69,14 +117,71 @@ impl OpaqueTypeSwiftBridgeAttributes {
             OpaqueTypeAttr::AlreadyDeclared => self.already_declared = true,
             OpaqueTypeAttr::Copy { size } => self.copy = Some(OpaqueCopy { size_bytes: size }),
             OpaqueTypeAttr::DeclareGeneric => self.declare_generic = true,
+            OpaqueTypeAttr::Equatable => self.equatable = true,
+            OpaqueTypeAttr::Hashable => self.hashable = true,
+            OpaqueTypeAttr::Comparable => self.comparable = true,
+            OpaqueTypeAttr::CustomStringConvertible => self.custom_string_convertible = true,
+            OpaqueTypeAttr::CustomDebugStringConvertible => {
+                self.custom_debug_string_convertible = true
+            }
+            OpaqueTypeAttr::Clone => self.clone = true,
+            OpaqueTypeAttr::Default => self.default = true,
+            OpaqueTypeAttr::Operators(operators) => self.operators = operators,
+            OpaqueTypeAttr::From(ty) => self.from.push(*ty),
+            OpaqueTypeAttr::TryFrom(try_from) => self.try_from.push(*try_from),
+            OpaqueTypeAttr::FromStr => self.from_str = true,
         }
     }
+
+    /// Whether or not the Swift class conforms to `Equatable`.
+    pub(crate) fn is_equatable(&self) -> bool {
+        self.equatable || self.hashable || self.comparable
+    }
+
+    /// Whether or not any Swift initializers get generated from the type's `From`, `TryFrom` or
+    /// `FromStr` implementations.
+    pub(crate) fn has_conversions(&self) -> bool {
+        !self.from.is_empty() || !self.try_from.is_empty() || self.from_str
+    }
+
+    /// The types that the conversion initializers convert from, along with their `TryFrom::Error`
+    /// types.
+    pub(crate) fn conversion_types(&self) -> Vec<&Type> {
+        let mut tys: Vec<&Type> = self.from.iter().collect();
+        for try_from in self.try_from.iter() {
+            tys.push(&try_from.ty);
+            tys.extend(try_from.error.as_ref());
+        }
+        tys
+    }
+
+    /// Whether or not any Swift protocol conformances get generated from the type's Rust trait
+    /// implementations.
+    pub(crate) fn has_protocol_conformances(&self) -> bool {
+        self.is_equatable()
+            || self.custom_string_convertible
+            || self.custom_debug_string_convertible
+            || self.clone
+            || self.default
+            || !self.operators.is_empty()
+    }
 }
 
 pub(crate) enum OpaqueTypeAttr {
     AlreadyDeclared,
     Copy { size: usize },
     DeclareGeneric,
+    Equatable,
+    Hashable,
+    Comparable,
+    CustomStringConvertible,
+    CustomDebugStringConvertible,
+    Clone,
+    Default,
+    Operators(Operators),
+    From(Box<Type>),
+    TryFrom(Box<TryFromConversion>),
+    FromStr,
 }
 
 impl Parse for OpaqueTypeSwiftBridgeAttributes {
@@ -112,6 +217,47 @@ impl Parse for OpaqueTypeAttr {
                 }
             }
             "declare_generic" => OpaqueTypeAttr::DeclareGeneric,
+            "Equatable" => OpaqueTypeAttr::Equatable,
+            "Hashable" => OpaqueTypeAttr::Hashable,
+            "Comparable" => OpaqueTypeAttr::Comparable,
+            "CustomStringConvertible" => OpaqueTypeAttr::CustomStringConvertible,
+            "CustomDebugStringConvertible" => OpaqueTypeAttr::CustomDebugStringConvertible,
+            "Clone" => OpaqueTypeAttr::Clone,
+            "Default" => OpaqueTypeAttr::Default,
+            "operators" => OpaqueTypeAttr::Operators(input.parse()?),
+            // From(u8)
+            "From" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                OpaqueTypeAttr::From(Box::new(content.parse()?))
+            }
+            // TryFrom(i64) or TryFrom(i64, Error = String)
+            "TryFrom" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let ty = content.parse()?;
+                let mut error = None;
+
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+
+                    let error_key: Ident = content.parse()?;
+                    if error_key != "Error" {
+                        return Err(syn::Error::new_spanned(
+                            error_key,
+                            r#"Expected the error type, such as "TryFrom(i64, Error = String)"."#,
+                        ));
+                    }
+                    content.parse::<Token![=]>()?;
+
+                    error = Some(content.parse()?);
+                }
+
+                OpaqueTypeAttr::TryFrom(Box::new(TryFromConversion { ty, error }))
+            }
+            "FromStr" => OpaqueTypeAttr::FromStr,
             _ => {
                 let attrib = key.to_string();
                 Err(syn::Error::new_spanned(
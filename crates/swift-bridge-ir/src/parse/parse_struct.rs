@@ -1,5 +1,6 @@
 use crate::bridged_type::{SharedStruct, StructFields, StructSwiftRepr};
 use crate::errors::{ParseError, ParseErrors};
+use crate::parse::Operators;
 use proc_macro2::{Ident, TokenTree};
 use syn::parse::{Parse, ParseStream};
 use syn::{ItemStruct, LitStr, Token};
@@ -14,6 +15,9 @@ enum StructAttr {
     SwiftName(LitStr),
     Error(StructAttrParseError),
     AlreadyDeclared,
+    Codable,
+    Operators(Operators),
+    Default,
 }
 
 enum StructAttrParseError {
@@ -26,6 +30,9 @@ struct StructAttribs {
     swift_repr: Option<(StructSwiftRepr, LitStr)>,
     swift_name: Option<LitStr>,
     already_declared: bool,
+    codable: bool,
+    operators: Operators,
+    default: bool,
 }
 
 struct ParsedAttribs(Vec<StructAttr>);
@@ -64,6 +71,9 @@ impl Parse for StructAttr {
                 StructAttr::SwiftName(name)
             }
             "already_declared" => StructAttr::AlreadyDeclared,
+            "Codable" => StructAttr::Codable,
+            "operators" => StructAttr::Operators(input.parse()?),
+            "Default" => StructAttr::Default,
             _ => {
                 move_input_cursor_to_next_comma(input);
 
@@ -107,6 +117,15 @@ impl<'a> SharedStructDeclarationParser<'a> {
                     StructAttr::AlreadyDeclared => {
                         attribs.already_declared = true;
                     }
+                    StructAttr::Codable => {
+                        attribs.codable = true;
+                    }
+                    StructAttr::Operators(operators) => {
+                        attribs.operators = operators;
+                    }
+                    StructAttr::Default => {
+                        attribs.default = true;
+                    }
                 };
             }
         }
@@ -138,6 +157,9 @@ impl<'a> SharedStructDeclarationParser<'a> {
             fields: StructFields::from_syn_fields(item_struct.fields),
             swift_name: attribs.swift_name,
             already_declared: attribs.already_declared,
+            codable: attribs.codable,
+            operators: attribs.operators,
+            default: attribs.default,
         };
 
         Ok(shared_struct)
@@ -361,6 +383,66 @@ mod tests {
         assert!(ty.already_declared);
     }
 
+    /// Verify that we can parse the `Codable` attribute.
+    #[test]
+    fn parses_struct_codable_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct SomeType {
+                    field: u8
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert!(ty.codable);
+    }
+
+    /// Verify that we can parse the `operators` attribute.
+    #[test]
+    fn parses_struct_operators_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", operators(Mul, Div))]
+                struct SomeType {
+                    field: u8
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert!(ty.operators.mul);
+        assert!(ty.operators.div);
+        assert!(!ty.operators.add);
+        assert!(ty.operators.index.is_none());
+    }
+
+    /// Verify that we can parse the `Default` attribute.
+    #[test]
+    fn parses_struct_default_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Default)]
+                struct SomeType {
+                    field: u8
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert!(ty.default);
+    }
+
     /// Verify that we return an error if an attribute isn't recognized.
     #[test]
     fn error_if_attribute_unrecognized() {
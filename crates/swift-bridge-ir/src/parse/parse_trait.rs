@@ -0,0 +1,289 @@
+use crate::bridged_type::fn_arg_name;
+use crate::errors::{ParseError, ParseErrors};
+use crate::parse::{HostLang, TraitDeclaration, TraitMethod, TypeDeclaration, TypeDeclarations};
+use std::ops::Deref;
+use syn::{FnArg, ItemImpl, ItemTrait, ReturnType, TraitItem, Type};
+
+pub(crate) struct TraitDeclarationParser<'a> {
+    pub item_trait: ItemTrait,
+    pub errors: &'a mut ParseErrors,
+    pub unresolved_types: &'a mut Vec<Type>,
+}
+
+impl<'a> TraitDeclarationParser<'a> {
+    pub fn parse(self) -> TraitDeclaration {
+        let mut methods = vec![];
+
+        for item in self.item_trait.items {
+            let method = match &item {
+                TraitItem::Method(method)
+                    if method.default.is_none()
+                        && method.sig.asyncness.is_none()
+                        && method.sig.generics.params.is_empty() =>
+                {
+                    method
+                }
+                _ => {
+                    self.errors.push(ParseError::UnsupportedTraitItem {
+                        item: Box::new(item),
+                    });
+                    continue;
+                }
+            };
+            let sig = &method.sig;
+
+            let mutable = match sig.inputs.first() {
+                Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+                    receiver.mutability.is_some()
+                }
+                _ => {
+                    self.errors
+                        .push(ParseError::TraitMethodMustBorrowSelf { sig: sig.clone() });
+                    continue;
+                }
+            };
+
+            // Arguments must be named so that we can forward them. i.e. `fn foo(&self, bar: u8)`
+            //  as opposed to `fn foo(&self, (a, b): (u8, u8))`.
+            let args: Option<Vec<_>> = sig
+                .inputs
+                .iter()
+                .skip(1)
+                .map(|arg| match (fn_arg_name(arg), arg) {
+                    (Some(name), FnArg::Typed(pat_ty)) => {
+                        Some((name.clone(), pat_ty.ty.deref().clone()))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let args = match args {
+                Some(args) => args,
+                None => {
+                    self.errors.push(ParseError::UnsupportedTraitItem {
+                        item: Box::new(item),
+                    });
+                    continue;
+                }
+            };
+
+            for (_, ty) in args.iter() {
+                self.unresolved_types.push(ty.clone());
+            }
+            if let ReturnType::Type(_, ty) = &sig.output {
+                self.unresolved_types.push(ty.deref().clone());
+            }
+
+            methods.push(TraitMethod {
+                name: sig.ident.clone(),
+                mutable,
+                args,
+                output: sig.output.clone(),
+            });
+        }
+
+        TraitDeclaration {
+            name: self.item_trait.ident,
+            methods,
+            implementors: vec![],
+        }
+    }
+}
+
+/// Parses an `impl SomeTrait for SomeType {}` marker, which makes the Swift class for `SomeType`
+/// conform to the `SomeTrait` protocol.
+///
+/// This runs after all of the module's types and traits have been parsed.
+pub(crate) struct TraitImplParser<'a> {
+    pub item_impl: ItemImpl,
+    pub errors: &'a mut ParseErrors,
+    pub type_declarations: &'a mut TypeDeclarations,
+}
+
+impl<'a> TraitImplParser<'a> {
+    pub fn parse(self) {
+        let trait_name = self
+            .item_impl
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.get_ident())
+            .map(|trait_name| trait_name.to_string());
+
+        let implementor = match self.item_impl.self_ty.deref() {
+            Type::Path(path) => match self.type_declarations.get_with_type_path(path) {
+                Some(TypeDeclaration::Opaque(opaque))
+                    if opaque.host_lang == HostLang::Rust
+                        && opaque.attributes.copy.is_none()
+                        && opaque.generics.is_empty() =>
+                {
+                    Some(opaque.ty.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let trait_declaration = trait_name
+            .as_ref()
+            .and_then(|trait_name| self.type_declarations.get_trait_mut(trait_name));
+
+        match (trait_declaration, implementor) {
+            (Some(trait_declaration), Some(implementor))
+                if self.item_impl.items.is_empty() && self.item_impl.generics.params.is_empty() =>
+            {
+                trait_declaration.implementors.push(implementor);
+            }
+            _ => {
+                self.errors.push(ParseError::InvalidTraitImpl {
+                    item_impl: Box::new(self.item_impl),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::test_utils::{parse_errors, parse_ok};
+    use quote::{quote, ToTokens};
+
+    /// Verify that we can parse a trait declaration and the types that implement it.
+    #[test]
+    fn parse_trait_with_implementors() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Circle;
+                    type Square;
+                }
+
+                trait Shape {
+                    fn area(&self) -> f64;
+                    fn scale(&mut self, factor: f64);
+                }
+
+                impl Shape for Circle {}
+                impl Shape for Square {}
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let shape = module.types.get_trait("Shape").unwrap();
+        assert_eq!(shape.methods.len(), 2);
+
+        assert_eq!(shape.methods[0].name, "area");
+        assert!(!shape.methods[0].mutable);
+        assert!(shape.methods[0].args.is_empty());
+
+        assert_eq!(shape.methods[1].name, "scale");
+        assert!(shape.methods[1].mutable);
+        assert_eq!(shape.methods[1].args[0].0, "factor");
+
+        assert_eq!(shape.implementors.len(), 2);
+        assert_eq!(shape.implementors[0], "Circle");
+        assert_eq!(shape.implementors[1], "Square");
+    }
+
+    /// Verify that we push an error for trait methods that do not borrow self.
+    #[test]
+    fn error_if_trait_method_does_not_borrow_self() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                trait Shape {
+                    fn a(self);
+                    fn b();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 2);
+
+        for (idx, expected) in vec!["a", "b"].into_iter().enumerate() {
+            match &errors[idx] {
+                ParseError::TraitMethodMustBorrowSelf { sig } => {
+                    assert_eq!(sig.ident, expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for trait items that we do not support.
+    #[test]
+    fn error_if_unsupported_trait_item() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                trait Shape {
+                    type Output;
+                    fn a(&self) {}
+                    fn b<T>(&self);
+                    async fn c(&self);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 4);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedTraitItem { .. } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for trait impls that are not an empty impl of a declared
+    /// trait for an opaque Rust type.
+    #[test]
+    fn error_if_invalid_trait_impl() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Circle;
+                }
+                extern "Swift" {
+                    type SwiftType;
+                }
+
+                trait Shape {
+                    fn area(&self) -> f64;
+                }
+
+                impl Circle {}
+                impl UndeclaredTrait for Circle {}
+                impl Shape for SwiftType {}
+                impl Shape for Circle {
+                    fn area(&self) -> f64 {
+                        1.
+                    }
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 4);
+
+        for (idx, expected) in vec![
+            "impl Circle { }",
+            "impl UndeclaredTrait for Circle { }",
+            "impl Shape for SwiftType { }",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            match &errors[idx] {
+                ParseError::InvalidTraitImpl { item_impl } => {
+                    assert_eq!(item_impl.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+}
@@ -13,10 +13,14 @@ use syn::{PatType, Type, TypePath};
 mod generics;
 pub(crate) use self::generics::*;
 
+mod trait_declaration;
+pub(crate) use self::trait_declaration::*;
+
 #[derive(Default)]
 pub(crate) struct TypeDeclarations {
     decls: HashMap<String, TypeDeclaration>,
     order: Vec<String>,
+    traits: Vec<TraitDeclaration>,
 }
 
 #[derive(Clone)]
@@ -42,6 +46,9 @@ impl TypeDeclaration {
                         fields: shared_struct.fields.clone(),
                         swift_name: shared_struct.swift_name.clone(),
                         already_declared: shared_struct.already_declared,
+                        codable: shared_struct.codable,
+                        operators: shared_struct.operators.clone(),
+                        default: shared_struct.default,
                     },
                 )))
             }
@@ -49,6 +56,7 @@ impl TypeDeclaration {
                 BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(SharedEnum {
                     name: shared_enum.name.clone(),
                     variants: shared_enum.variants.clone(),
+                    codable: shared_enum.codable,
                 })))
             }
             TypeDeclaration::Opaque(opaque) => {
@@ -125,6 +133,42 @@ impl OpaqueForeignTypeDeclaration {
         )
     }
 
+    /// The C FFI link name of a function that the Swift class uses to call one of this opaque
+    /// Rust type's trait implementations.
+    ///
+    /// For `type SomeType` and "_partial_eq" this would be:
+    /// "__swift_bridge__$SomeType$_partial_eq"
+    pub(crate) fn protocol_fn_ffi_name(&self, fn_name: &str) -> String {
+        format!("{}${}${}", SWIFT_BRIDGE_PREFIX, self.ty, fn_name)
+    }
+
+    /// The Rust function that the Swift class uses to call one of this opaque Rust type's trait
+    /// implementations.
+    ///
+    /// For `type SomeType` and "_partial_eq" this would be:
+    /// "__swift_bridge__SomeType__partial_eq"
+    pub(crate) fn protocol_fn_ident(&self, fn_name: &str) -> Ident {
+        Ident::new(
+            &format!("{}{}_{}", SWIFT_BRIDGE_PREFIX, self.ty, fn_name),
+            self.ty.span(),
+        )
+    }
+
+    /// The name of the function that the Swift class uses to create this opaque Rust type from a
+    /// value of another type, such as with the type's `From` or `TryFrom` implementations.
+    ///
+    /// For "_from" and `Vec<u8>` this would be:
+    /// "_from_Vec_u8"
+    pub(crate) fn conversion_fn_name(&self, prefix: &str, from_ty: &Type) -> String {
+        let from_ty = from_ty.to_token_stream().to_string();
+        let from_ty: Vec<&str> = from_ty
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        format!("{}_{}", prefix, from_ty.join("_"))
+    }
+
     /// The identifier for the `#[repr(C)] __swift_bridge__SomeStruct([u8; 123usize])`
     /// type that is generated to pass a Copy type over FFI.
     pub(crate) fn ffi_copy_repr_ident(&self) -> Ident {
@@ -249,6 +293,22 @@ impl TypeDeclarations {
             .map(|ty| self.decls.get(ty).unwrap())
             .collect()
     }
+
+    pub(crate) fn get_trait(&self, trait_name: &str) -> Option<&TraitDeclaration> {
+        self.traits.iter().find(|t| t.name == trait_name)
+    }
+
+    pub(crate) fn get_trait_mut(&mut self, trait_name: &str) -> Option<&mut TraitDeclaration> {
+        self.traits.iter_mut().find(|t| t.name == trait_name)
+    }
+
+    pub(crate) fn insert_trait(&mut self, trait_declaration: TraitDeclaration) {
+        self.traits.push(trait_declaration);
+    }
+
+    pub(crate) fn traits(&self) -> &[TraitDeclaration] {
+        &self.traits
+    }
 }
 
 impl TypeDeclaration {
@@ -0,0 +1,141 @@
+use crate::bridged_type::BridgedTraitObject;
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Ident, Span};
+use syn::{ReturnType, Type};
+
+/// A trait that was declared in the bridge module.
+///
+/// ```no_run,ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Rust" {
+///         type Circle;
+///     }
+///
+///     trait Shape {
+///         fn area(&self) -> f64;
+///     }
+///
+///     impl Shape for Circle {}
+/// }
+/// ```
+///
+/// The trait becomes a Swift protocol that the Swift classes of its implementors conform to.
+/// `Box<dyn Shape>` values are passed to Swift as a pointer, and Swift calls the trait object's
+/// methods through a vtable of exported Rust functions, one per method.
+///
+/// Any other Swift value that conforms to the protocol can be passed to Rust as a
+/// `Box<dyn Shape>`. Rust calls its methods through a vtable of `@_cdecl` Swift functions.
+#[derive(Clone)]
+pub(crate) struct TraitDeclaration {
+    pub name: Ident,
+    pub methods: Vec<TraitMethod>,
+    /// The opaque Rust types that have an `impl SomeTrait for SomeType {}` in the bridge module.
+    pub implementors: Vec<Ident>,
+}
+
+/// A method in a bridge module trait declaration.
+#[derive(Clone)]
+pub(crate) struct TraitMethod {
+    pub name: Ident,
+    /// `&mut self` as opposed to `&self`.
+    pub mutable: bool,
+    /// The method's arguments, not including `self`.
+    pub args: Vec<(Ident, Type)>,
+    pub output: ReturnType,
+}
+
+impl TraitDeclaration {
+    /// The `Box<dyn SomeTrait>` for this trait.
+    pub(crate) fn trait_object(&self) -> BridgedTraitObject {
+        BridgedTraitObject {
+            trait_name: self.name.clone(),
+        }
+    }
+
+    /// The name of a function in the trait's vtable.
+    ///
+    /// For `trait SomeTrait` and "some_method" this would be:
+    /// "__swift_bridge__$SomeTrait$some_method"
+    pub(crate) fn vtable_fn_ffi_name(&self, fn_name: &str) -> String {
+        format!("{}${}${}", SWIFT_BRIDGE_PREFIX, self.name, fn_name)
+    }
+
+    /// The Rust function for one of the functions in the trait's vtable.
+    ///
+    /// For `trait SomeTrait` and "some_method" this would be:
+    /// "__swift_bridge__SomeTrait_some_method"
+    pub(crate) fn vtable_fn_ident(&self, fn_name: &str) -> Ident {
+        Ident::new(
+            &format!("{}{}_{}", SWIFT_BRIDGE_PREFIX, self.name, fn_name),
+            self.name.span(),
+        )
+    }
+
+    /// The name of a function that the Swift class of one of the trait's implementors uses to
+    /// call the implementation.
+    ///
+    /// For `impl SomeTrait for SomeType {}` and "some_method" this would be:
+    /// "__swift_bridge__$SomeType$SomeTrait$some_method"
+    pub(crate) fn implementor_fn_ffi_name(&self, implementor: &Ident, fn_name: &str) -> String {
+        format!(
+            "{}${}${}${}",
+            SWIFT_BRIDGE_PREFIX, implementor, self.name, fn_name
+        )
+    }
+
+    /// The Rust function that the Swift class of one of the trait's implementors uses to call
+    /// the implementation.
+    ///
+    /// For `impl SomeTrait for SomeType {}` and "some_method" this would be:
+    /// "__swift_bridge__SomeType_SomeTrait_some_method"
+    pub(crate) fn implementor_fn_ident(&self, implementor: &Ident, fn_name: &str) -> Ident {
+        Ident::new(
+            &format!(
+                "{}{}_{}_{}",
+                SWIFT_BRIDGE_PREFIX, implementor, self.name, fn_name
+            ),
+            implementor.span(),
+        )
+    }
+
+    /// The Rust struct that implements the trait by calling the methods of a retained Swift
+    /// value.
+    ///
+    /// For `trait SomeTrait` this would be: `__swift_bridge__SwiftSomeTrait`
+    pub(crate) fn swift_impl_struct_ident(&self) -> Ident {
+        // We use the call site span so that the `non_camel_case_types` lint doesn't point users
+        // at their trait for a struct that they never wrote.
+        Ident::new(
+            &format!("{}Swift{}", SWIFT_BRIDGE_PREFIX, self.name),
+            Span::call_site(),
+        )
+    }
+
+    /// The Swift class that retains a Swift value that conforms to the trait's protocol while
+    /// Rust owns it.
+    ///
+    /// For `trait SomeTrait` this would be: "__private__SwiftSomeTrait"
+    pub(crate) fn swift_box_class_name(&self) -> String {
+        format!("__private__Swift{}", self.name)
+    }
+
+    /// The name of a function in the vtable that Rust uses to call the methods of a Swift value.
+    ///
+    /// For `trait SomeTrait` and "some_method" this would be:
+    /// "__swift_bridge__$SwiftSomeTrait$some_method"
+    pub(crate) fn swift_vtable_fn_ffi_name(&self, fn_name: &str) -> String {
+        format!("{}$Swift{}${}", SWIFT_BRIDGE_PREFIX, self.name, fn_name)
+    }
+
+    /// The function in the vtable that Rust uses to call the methods of a Swift value.
+    ///
+    /// For `trait SomeTrait` and "some_method" this would be:
+    /// "__swift_bridge__SwiftSomeTrait_some_method"
+    pub(crate) fn swift_vtable_fn_ident(&self, fn_name: &str) -> Ident {
+        Ident::new(
+            &format!("{}Swift{}_{}", SWIFT_BRIDGE_PREFIX, self.name, fn_name),
+            self.name.span(),
+        )
+    }
+}
@@ -0,0 +1,743 @@
+//! Validation of how bridged types are used. This runs after the entire bridge module has been
+//! parsed, since a type can be used before the block that declares it.
+
+use crate::bridged_type::{
+    pat_type_pat_is_self, BridgedType, CustomBridgedType, StdLibType, StructFields, TypePosition,
+};
+use crate::errors::{ParseError, ParseErrors};
+use crate::parse::{
+    HostLang, OpaqueForeignTypeDeclaration, SharedTypeDeclaration, TypeDeclaration,
+    TypeDeclarations,
+};
+use crate::parsed_extern_fn::{type_contains_reference, ParsedExternFn};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{FnArg, GenericArgument, PathArguments, ReturnType, Type};
+
+/// Push errors for types that resolve to a bridged type that we can't generate code for, such as
+/// `Option<[u8; 4]>`, or that are used in a way that we can't support, such as an iterator that
+/// borrows from a `&str` argument or a `Result` that isn't returned from an async Rust function.
+pub(super) fn validate_types(
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    let mut used_types: Vec<Type> = vec![];
+    let mut async_rust_return_types: Vec<Type> = vec![];
+
+    for function in functions {
+        if function.return_iterator(types).is_some() {
+            validate_iterator_fn_args(function, types, errors);
+        }
+        if function.is_swift_initializer {
+            validate_initializer(function, types, errors);
+        }
+
+        for arg in function.func.sig.inputs.iter() {
+            if let FnArg::Typed(pat_ty) = arg {
+                used_types.push(pat_ty.ty.as_ref().clone());
+            }
+        }
+        if let ReturnType::Type(_, ty) = &function.func.sig.output {
+            if function.func.sig.asyncness.is_some() && function.host_lang.is_rust() {
+                async_rust_return_types.push(ty.as_ref().clone());
+            } else {
+                used_types.push(ty.as_ref().clone());
+            }
+        }
+    }
+
+    for trait_declaration in types.traits() {
+        for method in trait_declaration.methods.iter() {
+            used_types.extend(method.args.iter().map(|(_, ty)| ty.clone()));
+            if let ReturnType::Type(_, ty) = &method.output {
+                used_types.push(ty.as_ref().clone());
+            }
+        }
+    }
+
+    for ty in types.types() {
+        match ty {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                shared_struct.operators.validate(types, errors);
+                if shared_struct.codable {
+                    validate_codable_fields(&shared_struct.fields, types, errors);
+                }
+                for field in shared_struct.fields.normalized_fields() {
+                    // Function signatures get checked along with the other unresolved types.
+                    if let Some(array) = unsupported_array(&field.ty, types) {
+                        errors.push(ParseError::UnsupportedArrayType { ty: array.clone() });
+                    }
+                    used_types.push(field.ty);
+                }
+            }
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
+                if shared_enum.codable {
+                    for variant in shared_enum.variants.iter() {
+                        if !variant.fields.is_empty() {
+                            errors.push(ParseError::CodableEnumVariantWithFields {
+                                variant: variant.name.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+            TypeDeclaration::Opaque(opaque) => {
+                opaque.attributes.operators.validate(types, errors);
+                validate_conversions(opaque, types, errors);
+                if opaque.host_lang.is_rust() && opaque.attributes.has_conversions() {
+                    validate_initializer_signatures(opaque, functions, types, errors);
+                }
+            }
+        }
+    }
+
+    for ty in used_types {
+        if let Some(bridged_ty) = BridgedType::new_with_type(&ty, types) {
+            validate_bridged_type(&ty, &bridged_ty, errors);
+        }
+    }
+
+    // An async Rust function that returns a `Result` becomes an `async throws` Swift function, so
+    // only the `Ok` and `Err` types need to be bridged.
+    for ty in async_rust_return_types {
+        match BridgedType::new_with_type(&ty, types) {
+            Some(BridgedType::StdLib(StdLibType::Result(result))) => {
+                validate_bridged_type(&ty, &result.ok_ty, errors);
+                validate_bridged_type(&ty, &result.err_ty, errors);
+            }
+            Some(bridged_ty) => validate_bridged_type(&ty, &bridged_ty, errors),
+            None => {}
+        }
+    }
+}
+
+fn validate_bridged_type(ty: &Type, bridged_ty: &BridgedType, errors: &mut ParseErrors) {
+    match bridged_ty {
+        BridgedType::StdLib(StdLibType::Option(option)) if !option.supports_inner_type() => {
+            errors.push(ParseError::UnsupportedOptionType { ty: ty.clone() });
+            return;
+        }
+        BridgedType::StdLib(StdLibType::Result(_)) => {
+            errors.push(ParseError::UnsupportedResultType { ty: ty.clone() });
+            return;
+        }
+        BridgedType::StdLib(StdLibType::Vec(vec))
+            if matches!(vec.ty.as_ref(), BridgedType::StdLib(StdLibType::Array(_))) =>
+        {
+            errors.push(ParseError::UnsupportedArrayType { ty: ty.clone() });
+            return;
+        }
+        _ => {}
+    }
+
+    for inner in bridged_ty.inner_types() {
+        validate_bridged_type(ty, inner, errors);
+    }
+}
+
+/// Push an error for each field that the generated Swift `Codable` conformance doesn't know how
+/// to encode.
+fn validate_codable_fields(
+    fields: &StructFields,
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    for field in fields.normalized_fields() {
+        let is_codable = match BridgedType::new_with_type(&field.ty, types) {
+            Some(BridgedType::StdLib(StdLibType::Option(option))) => {
+                is_codable_non_optional(&option.ty)
+            }
+            Some(bridged_ty) => is_codable_non_optional(&bridged_ty),
+            None => continue,
+        };
+
+        if !is_codable {
+            errors.push(ParseError::UnsupportedCodableField {
+                field: field.ffi_field_name(),
+                ty: field.ty,
+            });
+        }
+    }
+}
+
+fn is_codable_non_optional(ty: &BridgedType) -> bool {
+    match ty {
+        BridgedType::StdLib(StdLibType::String) => true,
+        BridgedType::StdLib(stdlib) => stdlib.is_primitive(),
+        BridgedType::Foreign(CustomBridgedType::Shared(_)) => true,
+        _ => false,
+    }
+}
+
+/// Find an array within the type that can't be bridged, such as the `[String; 2]` in
+/// `Option<[String; 2]>`.
+pub(super) fn unsupported_array<'a>(ty: &'a Type, types: &TypeDeclarations) -> Option<&'a Type> {
+    match ty {
+        Type::Array(_) => {
+            if BridgedType::new_with_type(ty, types).is_none() {
+                Some(ty)
+            } else {
+                None
+            }
+        }
+        Type::Reference(reference) => unsupported_array(&reference.elem, types),
+        Type::Slice(slice) => unsupported_array(&slice.elem, types),
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .find_map(|elem| unsupported_array(elem, types)),
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .filter_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => Some(args.args.iter()),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(ty) => unsupported_array(ty, types),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+/// Push an error for `TryFrom` errors that Swift can't throw.
+fn validate_conversions(
+    opaque: &OpaqueForeignTypeDeclaration,
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    for try_from in opaque.attributes.try_from.iter() {
+        let error = match try_from.error.as_ref() {
+            Some(error) => error,
+            None => continue,
+        };
+
+        let is_supported = match BridgedType::new_with_type(error, types) {
+            Some(BridgedType::StdLib(StdLibType::String)) => true,
+            Some(BridgedType::Foreign(CustomBridgedType::Opaque(error))) => {
+                error.host_lang.is_rust()
+                    && !error.reference
+                    && !error.has_swift_bridge_copy_annotation
+            }
+            Some(_) => false,
+            None => continue,
+        };
+        if !is_supported {
+            errors.push(ParseError::UnsupportedTryFromError { ty: error.clone() });
+        }
+    }
+}
+
+/// Swift can't overload initializers on whether or not they are failable or throw, so the
+/// `#[swift_bridge(init)]` functions and conversion initializers of a type must all take different
+/// Swift argument types.
+fn validate_initializer_signatures(
+    opaque: &OpaqueForeignTypeDeclaration,
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    let swift_arg_ty = |ty: Option<BridgedType>| {
+        ty.map(|ty| ty.to_swift_type(TypePosition::FnArg(HostLang::Rust), types))
+    };
+
+    let mut initializers: Vec<(TokenStream, Option<Vec<String>>)> = vec![];
+    for function in functions {
+        let is_initializer = function.is_swift_initializer
+            && matches!(
+                function.associated_type.as_ref(),
+                Some(TypeDeclaration::Opaque(ty)) if ty.ty == opaque.ty
+            );
+        if !is_initializer {
+            continue;
+        }
+
+        let args = function
+            .func
+            .sig
+            .inputs
+            .iter()
+            .map(|arg| swift_arg_ty(BridgedType::new_with_fn_arg(arg, types)))
+            .collect();
+        initializers.push((function.func.sig.to_token_stream(), args));
+    }
+
+    let conversions = opaque.attributes.from.iter().chain(
+        opaque
+            .attributes
+            .try_from
+            .iter()
+            .map(|try_from| &try_from.ty),
+    );
+    for ty in conversions {
+        let arg = swift_arg_ty(BridgedType::new_with_type(ty, types));
+        initializers.push((ty.to_token_stream(), arg.map(|arg| vec![arg])));
+    }
+
+    if opaque.attributes.from_str {
+        initializers.push((quote! { FromStr }, Some(vec!["String".to_string()])));
+    }
+
+    let mut seen: Vec<Vec<String>> = vec![];
+    for (initializer, args) in initializers {
+        // Undeclared argument types have already been reported.
+        let args = match args {
+            Some(args) => args,
+            None => continue,
+        };
+
+        if seen.contains(&args) {
+            let swift_signature = format!(
+                "init({})",
+                args.iter()
+                    .map(|arg| format!("_: {}", arg))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            errors.push(ParseError::ConflictingInitializer {
+                initializer,
+                swift_signature,
+            });
+        } else {
+            seen.push(args);
+        }
+    }
+}
+
+/// A parameterless initializer would be a redeclaration of the `init()` that
+/// `#[swift_bridge(Default)]` generates.
+fn validate_initializer(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    if !function.func.sig.inputs.is_empty() {
+        return;
+    }
+
+    let ty = match function.associated_type.as_ref() {
+        Some(TypeDeclaration::Opaque(opaque)) => opaque.ty_name_ident(),
+        _ => return,
+    };
+
+    if let Some(TypeDeclaration::Opaque(opaque)) = types.get(&ty.to_string()) {
+        if opaque.attributes.default {
+            errors.push(ParseError::DefaultConflictsWithInit {
+                sig: function.func.sig.clone(),
+            });
+        }
+    }
+}
+
+/// The Swift `RustIterator` keeps `self` and any borrowed opaque Rust arguments alive, so those
+/// are the only things that the iterator is allowed to borrow from.
+fn validate_iterator_fn_args(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    for arg in function.func.sig.inputs.iter() {
+        let pat_ty = match arg {
+            FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+            _ => continue,
+        };
+
+        if !type_contains_reference(&pat_ty.ty) {
+            continue;
+        }
+
+        let is_kept_alive = matches!(
+            BridgedType::new_with_fn_arg(arg, types),
+            Some(BridgedType::Foreign(CustomBridgedType::Opaque(opaque)))
+                if opaque.host_lang.is_rust()
+                    && opaque.reference
+                    && !opaque.has_swift_bridge_copy_annotation
+        );
+        if !is_kept_alive {
+            errors.push(ParseError::IteratorFnArgBorrow { arg: arg.clone() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::test_utils::parse_errors;
+    use quote::{quote, ToTokens};
+
+    /// Verify that we push an error for `Option`s of types that can't be wrapped in an `Option`.
+    #[test]
+    fn error_if_unsupported_option_type() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: Option<[u8; 4]>
+                }
+
+                extern "Rust" {
+                    fn a(arg: Option<[u8; 32]>);
+                    fn b() -> Option<Result<u8, String>>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        let mut tys = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedOptionType { ty } => {
+                    tys.push(ty.to_token_stream().to_string());
+                }
+                _ => panic!(),
+            }
+        }
+        assert!(tys.contains(&"Option < [u8 ; 4] >".to_string()));
+        assert!(tys.contains(&"Option < [u8 ; 32] >".to_string()));
+        assert!(tys.contains(&"Option < Result < u8 , String > >".to_string()));
+    }
+
+    /// Verify that we push an error for arrays that can't be passed over FFI, instead of asking
+    /// for them to be declared as a type.
+    #[test]
+    fn error_if_unsupported_array_type() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: [String; 2],
+                    supported: [u8; 2],
+                }
+
+                extern "Rust" {
+                    type Foo;
+
+                    fn a(arg: [String; 2]);
+                    fn b(arg: [Foo; 2]);
+                    fn c(arg: [[u8; 2]; 2]);
+                    fn d() -> [char; 4];
+                    fn e(arg: [u8; N]);
+                    fn f(arg: [u8; 0]);
+                    fn g(arg: Option<[String; 2]>);
+                    fn h(arg: Vec<[u8; 4]>);
+                    fn i(arg: [u8; 4]) -> [SomeStruct; 2];
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let mut tys = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedArrayType { ty } => {
+                    tys.push(ty.to_token_stream().to_string());
+                }
+                _ => panic!(),
+            }
+        }
+        assert_eq!(
+            tys,
+            vec![
+                "[String ; 2]",
+                "[Foo ; 2]",
+                "[[u8 ; 2] ; 2]",
+                "[char ; 4]",
+                "[u8 ; N]",
+                "[u8 ; 0]",
+                "[String ; 2]",
+                "[String ; 2]",
+                "Vec < [u8 ; 4] >",
+            ]
+        );
+    }
+
+    /// Verify that we push an error for `Result`s that aren't the return type of an async Rust
+    /// function.
+    #[test]
+    fn error_if_unsupported_result_type() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: Result<u8, String>
+                }
+
+                extern "Rust" {
+                    fn a() -> Result<u32, String>;
+                    fn b(arg: Result<u32, String>);
+                    async fn c(arg: Result<u32, String>);
+                    async fn d() -> Result<Result<u8, u8>, String>;
+                    async fn e() -> Result<u32, String>;
+                }
+
+                extern "Swift" {
+                    async fn f() -> Result<u32, String>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 6);
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedResultType { ty: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error if a function that returns an iterator borrows an argument
+    /// that Swift doesn't keep alive for as long as the iterator.
+    #[test]
+    fn error_if_iterator_fn_borrows_arg() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a(arg: &str) -> impl Iterator<Item = u8>;
+                    fn b(arg: &[u8]) -> Box<dyn Iterator<Item = u8>>;
+                    fn c(&self, arg: &SomeType) -> impl Iterator<Item = u8>;
+                    fn d(arg: &str) -> u8;
+                    fn e(arg: String) -> impl Iterator<Item = u8>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::IteratorFnArgBorrow { arg: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for a parameterless initializer on a type that uses the
+    /// `Default` attribute.
+    #[test]
+    fn error_if_default_conflicts_with_init() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Default)]
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new() -> SomeType;
+                    #[swift_bridge(init)]
+                    fn with_value(value: u8) -> SomeType;
+                }
+
+                extern "Rust" {
+                    type AnotherType;
+
+                    #[swift_bridge(init)]
+                    fn new() -> AnotherType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::DefaultConflictsWithInit { sig } => {
+                assert_eq!(sig.ident.to_string(), "new");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error for each field of a Codable struct that the Swift `Codable`
+    /// conformance can't encode.
+    #[test]
+    fn error_if_unsupported_codable_field() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct SomeStruct {
+                    supported: Option<String>,
+                    large: u128,
+                    signed_large: i128,
+                    letter: char,
+                    elapsed: Duration,
+                    time: SystemTime,
+                    array: [u8; 4],
+                    list: Vec<u8>,
+                    path: PathBuf,
+                    opaque: SomeType,
+                    optional_array: Option<[u8; 2]>,
+                }
+
+                #[swift_bridge(swift_repr = "struct", Codable)]
+                struct TupleStruct(u8, char);
+
+                extern "Rust" {
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let mut fields = vec![];
+        for error in errors.iter() {
+            if let ParseError::UnsupportedCodableField { field, ty: _ } = error {
+                fields.push(field.as_str());
+            }
+        }
+        assert_eq!(
+            fields,
+            vec![
+                "large",
+                "signed_large",
+                "letter",
+                "elapsed",
+                "time",
+                "array",
+                "list",
+                "path",
+                "opaque",
+                "optional_array",
+                "_1"
+            ]
+        );
+    }
+
+    /// Verify that we push an error for each variant with fields of a Codable enum.
+    #[test]
+    fn error_if_codable_enum_variant_has_fields() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(Codable)]
+                enum SomeEnum {
+                    Unit,
+                    Tuple(u8),
+                    Named { field: u8 },
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        let mut variants = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::CodableEnumVariantWithFields { variant } => {
+                    variants.push(variant.to_string());
+                }
+                _ => panic!(),
+            }
+        }
+        assert_eq!(variants, vec!["Tuple", "Named"]);
+    }
+
+    /// Verify that we push an error for `TryFrom` errors that Swift can't throw.
+    #[test]
+    fn error_if_unsupported_try_from_error() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(
+                        TryFrom(u8, Error = u8),
+                        TryFrom(i8, Error = String),
+                        TryFrom(u16, Error = SomeError),
+                        TryFrom(i16, Error = &SomeError)
+                    )]
+                    type SomeType;
+
+                    type SomeError;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        let mut tys = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedTryFromError { ty } => {
+                    tys.push(ty.to_token_stream().to_string());
+                }
+                _ => panic!(),
+            }
+        }
+        assert_eq!(tys, vec!["u8", "& SomeError"]);
+    }
+
+    /// Verify that we push an error for conversion initializers that have the same Swift signature
+    /// as another initializer of the type.
+    #[test]
+    fn error_if_conflicting_initializer() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(From(u8))]
+                    type InitAndFrom;
+
+                    #[swift_bridge(init)]
+                    fn new(a: u8) -> InitAndFrom;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(From(u16), From(u16))]
+                    type FromTwice;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(From(u32), TryFrom(u32))]
+                    type FromAndTryFrom;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(From(u64), TryFrom(i64), FromStr, From(String))]
+                    type NoConflicts;
+
+                    #[swift_bridge(init)]
+                    fn new(a: u64, b: u64) -> NoConflicts;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        let mut signatures = vec![];
+        for error in errors.iter() {
+            match error {
+                ParseError::ConflictingInitializer {
+                    initializer: _,
+                    swift_signature,
+                } => signatures.push(swift_signature.as_str()),
+                _ => panic!(),
+            }
+        }
+        signatures.sort();
+        assert_eq!(
+            signatures,
+            vec!["init(_: UInt16)", "init(_: UInt32)", "init(_: UInt8)"]
+        );
+    }
+}
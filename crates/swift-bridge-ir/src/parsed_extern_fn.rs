@@ -1,11 +1,13 @@
-use crate::bridged_type::{pat_type_pat_is_self, BridgedType, CustomBridgedType};
+use crate::bridged_type::{pat_type_pat_is_self, BridgedType, CustomBridgedType, StdLibType};
 use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use std::ops::Deref;
 use syn::spanned::Spanned;
-use syn::{FnArg, ForeignItemFn, Lifetime, Path, ReturnType, Token, Type};
+use syn::{
+    FnArg, ForeignItemFn, GenericArgument, Lifetime, Path, PathArguments, ReturnType, Token, Type,
+};
 
 mod to_extern_c_fn;
 mod to_extern_c_param_names_and_types;
@@ -75,6 +77,11 @@ pub(crate) struct ParsedExternFn {
     pub args_into: Option<Vec<Ident>>,
     /// Get one of the associated type's fields
     pub get_field: Option<GetField>,
+    /// `#[swift_bridge(local)]`
+    ///
+    /// The async function's future does not need to be `Send`. Instead of being spawned on the
+    /// async executor it gets polled on the main thread, which is where Swift calls it from.
+    pub is_local: bool,
 }
 
 pub(crate) enum GetField {
@@ -164,6 +171,12 @@ impl ParsedExternFn {
         let sig = &self.func.sig;
 
         if let Some(ret) = BridgedType::new_with_return_type(&sig.output, types) {
+            // The `Err` of a `Result<T, E>` gets passed to a separate callback.
+            let ret = match ret {
+                BridgedType::StdLib(StdLibType::Result(result)) => *result.ok_ty,
+                ret => ret,
+            };
+
             let ty = ret.to_ffi_compatible_rust_type(swift_bridge_path, types);
             if ty.to_string() == "()" {
                 None
@@ -255,7 +268,15 @@ impl ParsedExternFn {
 
                     if let Some(built_in) = BridgedType::new_with_type(&pat_ty.ty, types) {
                         if self.host_lang.is_rust() {
-                            arg = built_in.convert_ffi_value_to_rust_value(&arg, pat_ty.ty.span());
+                            // The argument was already converted before creating the future.
+                            if self.copies_async_fn_args() {
+                                if async_fn_arg_is_copied(&pat_ty.ty) {
+                                    arg = quote! { &#arg };
+                                }
+                            } else {
+                                arg = built_in
+                                    .convert_ffi_value_to_rust_value(&arg, pat_ty.ty.span());
+                            }
 
                             if self.args_into_contains_arg(fn_arg) {
                                 arg = quote_spanned! {pat_ty.span()=>
@@ -283,6 +304,21 @@ impl ParsedExternFn {
         }
     }
 
+    /// Whether or not this is an async Rust function that copies any of its `&str` or `&[T]`
+    /// arguments.
+    ///
+    /// The future borrows from the copies, so it gets created inside of an `async move` block
+    /// that owns them. The rest of the arguments get converted before the block, since raw
+    /// pointers can't be sent to the executor.
+    pub(crate) fn copies_async_fn_args(&self) -> bool {
+        self.host_lang.is_rust()
+            && self.sig.asyncness.is_some()
+            && self.sig.inputs.iter().any(|arg| match arg {
+                FnArg::Typed(pat_ty) => async_fn_arg_is_copied(&pat_ty.ty),
+                FnArg::Receiver(_) => false,
+            })
+    }
+
     // fn foo (&self, arg1: u8, arg2: u32)
     //  becomes..
     // void* self, uint8_t u8, uint32_t arg2
@@ -355,10 +391,20 @@ impl ParsedExternFn {
         let mut includes = vec![];
 
         if let ReturnType::Type(_, ty) = &self.func.sig.output {
-            if let Some(ty) = BridgedType::new_with_type(&ty, types) {
-                if let Some(include) = ty.c_include() {
-                    includes.push(include);
+            match BridgedType::new_with_type(&ty, types) {
+                Some(BridgedType::StdLib(StdLibType::Result(result))) => {
+                    for ty in [result.ok_ty, result.err_ty] {
+                        if let Some(include) = ty.c_include() {
+                            includes.push(include);
+                        }
+                    }
+                }
+                Some(ty) => {
+                    if let Some(include) = ty.c_include() {
+                        includes.push(include);
+                    }
                 }
+                None => {}
             }
         }
 
@@ -379,6 +425,26 @@ impl ParsedExternFn {
         }
     }
 
+    pub fn swift_imports(&self, types: &TypeDeclarations) -> Vec<&'static str> {
+        let mut imports = vec![];
+
+        if let ReturnType::Type(_, ty) = &self.func.sig.output {
+            if let Some(ty) = BridgedType::new_with_type(ty, types) {
+                imports.extend(ty.swift_import());
+            }
+        }
+
+        for param in &self.func.sig.inputs {
+            if let FnArg::Typed(pat_ty) = param {
+                if let Some(ty) = BridgedType::new_with_type(&pat_ty.ty, types) {
+                    imports.extend(ty.swift_import());
+                }
+            }
+        }
+
+        imports
+    }
+
     fn push_self_param(&self, params: &mut Vec<String>) {
         let param = if self.is_copy_method_on_opaque_type() {
             format!(
@@ -475,6 +541,46 @@ pub(crate) fn fn_arg_is_mutable_reference(fn_arg: &FnArg) -> bool {
     }
 }
 
+/// Whether or not an argument to an async Rust function gets copied before the future is created.
+///
+/// `&str` and `&[T]` arguments point to memory that Swift only keeps alive until the function
+/// returns, so the future borrows from an owned copy instead.
+pub(crate) fn async_fn_arg_is_copied(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_none() => {
+            match reference.elem.deref() {
+                Type::Slice(_) => true,
+                Type::Path(path) => path.path.is_ident("str"),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Whether or not the type borrows anything, such as `&Foo` or `Option<&str>`.
+pub(crate) fn type_contains_reference(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(_) => true,
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .any(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(generics) => generics.args.iter().any(
+                    |arg| matches!(arg, GenericArgument::Type(ty) if type_contains_reference(ty)),
+                ),
+                _ => false,
+            }),
+        Type::Tuple(tuple) => tuple.elems.iter().any(type_contains_reference),
+        Type::Array(array) => type_contains_reference(&array.elem),
+        Type::Slice(slice) => type_contains_reference(&slice.elem),
+        Type::Paren(paren) => type_contains_reference(&paren.elem),
+        Type::Group(group) => type_contains_reference(&group.elem),
+        _ => false,
+    }
+}
+
 pub(crate) fn fn_arg_is_opaque_copy_type(
     associated: &Option<TypeDeclaration>,
     fn_arg: &FnArg,
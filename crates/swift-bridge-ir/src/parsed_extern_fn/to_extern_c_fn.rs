@@ -1,9 +1,13 @@
-use crate::bridged_type::BridgedType;
+use crate::bridged_type::pat_type_pat_is_self;
+use crate::bridged_type::{BridgedType, BuiltInIterator, BuiltInResult, BuiltInStream, StdLibType};
 use crate::parse::{HostLang, OpaqueCopy, TypeDeclaration, TypeDeclarations};
-use crate::parsed_extern_fn::{GetField, GetFieldDirect, GetFieldWith, ParsedExternFn};
+use crate::parsed_extern_fn::{
+    async_fn_arg_is_copied, GetField, GetFieldDirect, GetFieldWith, ParsedExternFn,
+};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::Path;
+use syn::spanned::Spanned;
+use syn::{FnArg, Path};
 
 impl ParsedExternFn {
     /// Generates:
@@ -33,8 +37,6 @@ impl ParsedExternFn {
 
         let prefixed_fn_name = self.prefixed_fn_name();
 
-        let ret = self.rust_fn_sig_return_tokens(swift_bridge_path, types);
-
         match self.host_lang {
             HostLang::Rust => {
                 let call_fn = self.call_fn_tokens(swift_bridge_path, types);
@@ -44,66 +46,184 @@ impl ParsedExternFn {
                 let is_async = self.sig.asyncness.is_some();
 
                 if !is_async {
+                    let ret = self.rust_fn_sig_return_tokens(swift_bridge_path, types);
+
+                    let iterator_functions = match self.return_iterator(types) {
+                        Some(iterator) => iterator.generate_next_and_free_functions(
+                            &link_name,
+                            &prefixed_fn_name,
+                            swift_bridge_path,
+                            types,
+                        ),
+                        None => quote! {},
+                    };
+                    let stream_functions = match self.return_stream(types) {
+                        Some(stream) => stream.generate_next_and_free_functions(
+                            &link_name,
+                            &prefixed_fn_name,
+                            swift_bridge_path,
+                            types,
+                        ),
+                        None => quote! {},
+                    };
+
                     quote! {
                         #[export_name = #link_name]
                         pub extern "C" fn #prefixed_fn_name ( #params ) #ret {
                             #call_fn
                         }
+
+                        #iterator_functions
+                        #stream_functions
                     }
                 } else {
-                    let (await_fut, call_callback) = if maybe_return_ty.is_some() {
+                    let on_complete = if let Some(result) = self.return_result(types) {
+                        let on_ok = if maybe_return_ty.is_some() {
+                            let val = result.ok_ty.convert_rust_value_to_ffi_compatible_value(
+                                &quote! {val},
+                                swift_bridge_path,
+                                types,
+                            );
+                            quote! {
+                                Some(Ok(Ok(val))) => (callback)(callback_wrapper, #val),
+                            }
+                        } else {
+                            quote! {
+                                Some(Ok(Ok(()))) => (callback)(callback_wrapper),
+                            }
+                        };
+                        let err = result.err_ty.convert_rust_value_to_ffi_compatible_value(
+                            &quote! {err},
+                            swift_bridge_path,
+                            types,
+                        );
+
+                        quote! {
+                            #on_ok
+                            Some(Ok(Err(err))) => (on_error)(callback_wrapper, #err),
+                        }
+                    } else if maybe_return_ty.is_some() {
                         let return_ty = self.return_ty_built_in(types).unwrap();
-                        let awaited_val = return_ty.convert_rust_value_to_ffi_compatible_value(
-                            &quote! {fut.await},
+                        let val = return_ty.convert_rust_value_to_ffi_compatible_value(
+                            &quote! {val},
                             swift_bridge_path,
                             types,
                         );
 
+                        quote! {
+                            Some(Ok(val)) => (callback)(callback_wrapper, #val),
+                        }
+                    } else {
+                        quote! {
+                            Some(Ok(())) => (callback)(callback_wrapper),
+                        }
+                    };
+
+                    let maybe_on_error = self.return_result(types).map(|result| {
+                        let err_ty = result
+                            .err_ty
+                            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+                        quote! {
+                            on_error: extern "C" fn(*mut std::ffi::c_void, #err_ty) -> (),
+                        }
+                    });
+
+                    // Local futures don't need to be `Send`, so they get polled on the thread that
+                    // Swift called the function from instead of being spawned on the executor.
+                    let (maybe_schedule, spawn_task) = if self.is_local {
                         (
                             quote! {
-                                let val = #awaited_val;
+                                schedule: extern "C" fn(*mut std::ffi::c_void) -> (),
                             },
                             quote! {
-                                (callback)(callback_wrapper, val)
+                                swift_bridge::async_support::LocalTask::spawn(task, schedule)
                             },
                         )
                     } else {
                         (
+                            quote! {},
                             quote! {
-                                fut.await;
-                            },
-                            quote! {
-                                (callback)(callback_wrapper)
+                                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
                             },
                         )
                     };
 
+                    let create_future = if self.copies_async_fn_args() {
+                        let args = self.convert_async_fn_args(types);
+                        let maybe_this = if self.is_method() {
+                            let this = self.this_tokens();
+                            quote! {
+                                let this = #this;
+                            }
+                        } else {
+                            quote! {}
+                        };
+
+                        quote! {
+                            #(#args)*
+                            #maybe_this
+                            let fut = async move { #call_fn.await };
+                        }
+                    } else {
+                        quote! {
+                            let fut = #call_fn;
+                        }
+                    };
+
+                    // If the method borrows `self`, the future borrows from a value that is owned
+                    // by Swift. Swift keeps its owner alive until we call `callback`, `on_panic` or
+                    // `on_cancelled`, all of which only happen after the future has been dropped.
                     quote! {
                         #[export_name = #link_name]
                         pub extern "C" fn #prefixed_fn_name (
+                            cancellation_token: *mut std::ffi::c_void,
                             callback_wrapper: *mut std::ffi::c_void,
                             callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
+                            #maybe_on_error
+                            on_panic: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                            on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
+                            #maybe_schedule
                             #params
                         ) {
-                            let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
-                            let fut = #call_fn;
+                            let cancellation_token = unsafe { swift_bridge::async_support::CancellationToken::from_raw(cancellation_token) };
+                            let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
+                            #create_future
                             let task = async move {
-                                #await_fut
+                                let val = cancellation_token.run_until_cancelled(swift_bridge::async_support::catch_unwind(fut)).await;
 
-                                let callback_wrapper = callback_wrapper;
-                                let callback_wrapper = callback_wrapper.0;
+                                let callback_wrapper = callback_wrapper.into_raw();
 
-                                #call_callback
+                                match val {
+                                    #on_complete
+                                    Some(Err(panic)) => (on_panic)(callback_wrapper, swift_bridge::string::RustString(panic).box_into_raw()),
+                                    None => (on_cancelled)(callback_wrapper, false),
+                                }
                             };
-                            swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                            #spawn_task
                         }
                     }
                 }
             }
             HostLang::Swift => {
-                quote! {
-                    #[link_name = #link_name]
-                    fn #prefixed_fn_name ( #params ) #ret;
+                if self.sig.asyncness.is_some() {
+                    let maybe_return_ty =
+                        self.maybe_async_rust_fn_return_ty(swift_bridge_path, types);
+
+                    quote! {
+                        #[link_name = #link_name]
+                        fn #prefixed_fn_name (
+                            callback_wrapper: *mut std::ffi::c_void,
+                            callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
+                            #params
+                        ) -> *mut std::ffi::c_void;
+                    }
+                } else {
+                    let ret = self.rust_fn_sig_return_tokens(swift_bridge_path, types);
+
+                    quote! {
+                        #[link_name = #link_name]
+                        fn #prefixed_fn_name ( #params ) #ret;
+                    }
                 }
             }
         }
@@ -130,7 +250,12 @@ impl ParsedExternFn {
         };
 
         let mut call_fn = if self.is_method() {
-            self.call_method_tokens(&call_fn)
+            let this = if self.copies_async_fn_args() {
+                quote! { this }
+            } else {
+                self.this_tokens()
+            };
+            self.call_method_tokens(&call_fn, this)
         } else {
             self.call_function_tokens(&call_fn)
         };
@@ -159,9 +284,37 @@ impl ParsedExternFn {
         call_fn
     }
 
-    /// Generate tokens for calling a method.
-    fn call_method_tokens(&self, call_fn: &TokenStream) -> TokenStream {
-        let this = if self.is_copy_method_on_opaque_type() {
+    /// Convert the arguments of an async function that copies its `&str` and `&[T]` arguments.
+    ///
+    /// A `&str` argument becomes `let arg = arg.to_str().to_owned();`.
+    fn convert_async_fn_args(&self, types: &TypeDeclarations) -> Vec<TokenStream> {
+        let mut args = vec![];
+
+        for fn_arg in self.func.sig.inputs.iter() {
+            let pat_ty = match fn_arg {
+                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+                _ => continue,
+            };
+
+            let pat = &pat_ty.pat;
+            let mut arg = BridgedType::new_with_type(&pat_ty.ty, types)
+                .unwrap()
+                .convert_ffi_value_to_rust_value(&quote! {#pat}, pat_ty.ty.span());
+            if async_fn_arg_is_copied(&pat_ty.ty) {
+                arg = quote! { #arg.to_owned() };
+            }
+
+            args.push(quote! {
+                let #pat = #arg;
+            });
+        }
+
+        args
+    }
+
+    /// Generate tokens for getting the value that a method gets called on.
+    fn this_tokens(&self) -> TokenStream {
+        if self.is_copy_method_on_opaque_type() {
             quote! {
                 this.into_rust_repr()
             }
@@ -178,8 +331,11 @@ impl ParsedExternFn {
                     ( * unsafe { Box::from_raw(this) } )
                 }
             }
-        };
+        }
+    }
 
+    /// Generate tokens for calling a method.
+    fn call_method_tokens(&self, call_fn: &TokenStream, this: TokenStream) -> TokenStream {
         match &self.get_field {
             Some(GetField::Direct(get_direct)) => {
                 let GetFieldDirect {
@@ -235,6 +391,31 @@ impl ParsedExternFn {
         BridgedType::new_with_return_type(&self.func.sig.output, types)
     }
 
+    /// If the function returns an `impl Iterator<Item = T>` or `Box<dyn Iterator<Item = T>>`.
+    pub(crate) fn return_iterator(&self, types: &TypeDeclarations) -> Option<BuiltInIterator> {
+        match self.return_ty_built_in(types)? {
+            BridgedType::StdLib(StdLibType::Iterator(iterator)) => Some(iterator),
+            _ => None,
+        }
+    }
+
+    /// If the function returns an `impl Stream<Item = T>` or
+    /// `Pin<Box<dyn Stream<Item = T> + Send>>`.
+    pub(crate) fn return_stream(&self, types: &TypeDeclarations) -> Option<BuiltInStream> {
+        match self.return_ty_built_in(types)? {
+            BridgedType::StdLib(StdLibType::Stream(stream)) => Some(stream),
+            _ => None,
+        }
+    }
+
+    /// If the function returns a `Result<T, E>`.
+    pub(crate) fn return_result(&self, types: &TypeDeclarations) -> Option<BuiltInResult> {
+        match self.return_ty_built_in(types)? {
+            BridgedType::StdLib(StdLibType::Result(result)) => Some(result),
+            _ => None,
+        }
+    }
+
     /// Whether or not this is a method on a type that is using `#[swift_bridge(Copy(...))]`
     pub(crate) fn is_copy_method_on_opaque_type(&self) -> bool {
         self.maybe_copy_descriptor().is_some()
@@ -59,6 +59,15 @@ impl ParsedExternFn {
         let call_args = self.to_call_rust_args(swift_bridge_path, types);
         let linked_fn_name = self.extern_swift_linked_fn_new();
 
+        if sig.asyncness.is_some() {
+            return self.to_async_rust_fn_that_calls_a_swift_extern(
+                &params,
+                &call_args,
+                swift_bridge_path,
+                types,
+            );
+        }
+
         let mut inner = quote! {
             unsafe { #linked_fn_name(#call_args) }
         };
@@ -76,6 +85,88 @@ impl ParsedExternFn {
         }
     }
 
+    /// An `async fn` in an `extern "Swift"` block becomes an `async fn` that awaits a
+    /// `SwiftFuture`. Swift completes the future by calling the `on_complete` callback once the
+    /// Swift async function returns. Dropping the future cancels the Swift `Task`.
+    ///
+    /// ```no_run,ignore
+    /// pub async fn some_function(arg: u8) -> u16 {
+    ///     extern "C" fn on_complete(future_state: *mut std::ffi::c_void, ret: u16) {
+    ///         let val = ret;
+    ///         unsafe { swift_bridge::async_support::SwiftFuture::<u16>::complete(future_state, val) }
+    ///     }
+    ///
+    ///     let future = {
+    ///         let (future, future_state) = swift_bridge::async_support::SwiftFuture::<u16>::new();
+    ///         let task = unsafe { __swift_bridge__some_function(future_state, on_complete, arg) };
+    ///         unsafe { future.with_swift_task(task) }
+    ///     };
+    ///     future.await
+    /// }
+    /// ```
+    fn to_async_rust_fn_that_calls_a_swift_extern(
+        &self,
+        params: &TokenStream,
+        call_args: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let sig = &self.func.sig;
+        let fn_name = &sig.ident;
+        let linked_fn_name = self.extern_swift_linked_fn_new();
+
+        // Unresolved return types are reported while parsing.
+        let return_ty = BridgedType::new_with_return_type(&sig.output, types).unwrap();
+
+        let (ret, rust_ret_ty) = match &sig.output {
+            ReturnType::Default => (quote! {}, quote! { () }),
+            ReturnType::Type(arrow, _ty) => {
+                let ty = return_ty.maybe_convert_pointer_to_super_pointer();
+                let return_ty_span = sig.output.span();
+
+                (quote_spanned! {return_ty_span=> #arrow #ty}, ty)
+            }
+        };
+
+        let (maybe_val_param, maybe_convert_val, val) = if return_ty.is_null() {
+            (quote! {}, quote! {}, quote! { () })
+        } else {
+            let ffi_ty = return_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+            let val = return_ty.convert_ffi_value_to_rust_value(&quote! { ret }, sig.output.span());
+
+            (
+                quote! { , ret: #ffi_ty },
+                quote! { let val = #val; },
+                quote! { val },
+            )
+        };
+
+        let maybe_call_args = if sig.inputs.is_empty() {
+            quote! {}
+        } else {
+            quote! { , #call_args }
+        };
+
+        quote! {
+            pub async fn #fn_name(#params) #ret {
+                extern "C" fn on_complete(future_state: *mut std::ffi::c_void #maybe_val_param) {
+                    #maybe_convert_val
+                    unsafe {
+                        #swift_bridge_path::async_support::SwiftFuture::<#rust_ret_ty>::complete(future_state, #val)
+                    }
+                }
+
+                let future = {
+                    let (future, future_state) =
+                        #swift_bridge_path::async_support::SwiftFuture::<#rust_ret_ty>::new();
+                    let task = unsafe { #linked_fn_name(future_state, on_complete #maybe_call_args) };
+                    unsafe { future.with_swift_task(task) }
+                };
+                future.await
+            }
+        }
+    }
+
     // All of the params but with explicit types removed from `self`.
     //
     // `self: Foo` becomes `self`,
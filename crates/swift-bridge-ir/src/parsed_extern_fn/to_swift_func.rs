@@ -1,4 +1,4 @@
-use crate::bridged_type::{pat_type_pat_is_self, BridgedType, TypePosition};
+use crate::bridged_type::{pat_type_pat_is_self, BridgedType, StdLibType, TypePosition};
 use crate::parse::TypeDeclarations;
 use crate::parsed_extern_fn::ParsedExternFn;
 use quote::ToTokens;
@@ -126,6 +126,13 @@ impl ParsedExternFn {
             ReturnType::Default => "".to_string(),
             ReturnType::Type(_, ty) => {
                 if let Some(built_in) = BridgedType::new_with_type(&ty, types) {
+                    // `async fn f() -> Result<(), E>` becomes `func f() async throws`.
+                    if let BridgedType::StdLib(StdLibType::Result(result)) = &built_in {
+                        if result.ok_ty.is_null() {
+                            return "".to_string();
+                        }
+                    }
+
                     format!(
                         " -> {}",
                         built_in.to_swift_type(TypePosition::FnReturn(self.host_lang,), types)
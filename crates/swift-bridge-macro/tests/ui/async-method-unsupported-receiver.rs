@@ -0,0 +1,37 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=async-method-unsupported-receiver.rs
+
+// We declare async methods with receivers that we do not support.
+//
+// Smart pointer receivers are not supported, and async methods on Copy types cannot borrow `self`
+// since the future would outlive the copy of `self` that gets passed to Rust.
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeType;
+
+        async fn some_method(self: Arc<Self>);
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Copy(1))]
+        type SomeCopyType;
+
+        async fn some_copy_method(&self);
+    }
+}
+
+pub struct SomeType;
+
+impl SomeType {
+    async fn some_method(self: std::sync::Arc<Self>) {}
+}
+
+#[derive(Copy, Clone)]
+pub struct SomeCopyType(u8);
+
+impl SomeCopyType {
+    async fn some_copy_method(&self) {}
+}
+
+fn main() {}
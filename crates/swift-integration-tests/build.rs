@@ -5,26 +5,40 @@ fn main() {
     let out_dir = PathBuf::from(out_dir);
 
     let bridges = vec![
+        "src/array.rs",
         "src/async_function.rs",
         "src/expose_opaque_rust_type.rs",
         "src/import_opaque_swift_class.rs",
         "src/bool.rs",
+        "src/char.rs",
         "src/generics.rs",
+        "src/int128.rs",
+        "src/iterator.rs",
+        "src/non_zero.rs",
+        "src/operators.rs",
         "src/option.rs",
+        "src/path.rs",
         "src/pointer.rs",
         "src/string.rs",
         "src/vec.rs",
         "src/slice.rs",
+        "src/stream.rs",
+        "src/time.rs",
+        "src/traits.rs",
         "src/shared_types/shared_struct.rs",
         "src/shared_types/shared_enum.rs",
+        "src/shared_types/codable.rs",
         "src/rust_function_uses_opaque_swift_type.rs",
         "src/swift_function_uses_opaque_rust_type.rs",
         "src/conditional_compilation.rs",
         "src/opaque_type_attributes/already_declared.rs",
+        "src/opaque_type_attributes/conversions.rs",
         "src/opaque_type_attributes/copy.rs",
+        "src/opaque_type_attributes/protocols.rs",
         "src/function_attributes/get.rs",
         "src/function_attributes/identifiable.rs",
         "src/struct_attributes/already_declared.rs",
+        "src/struct_attributes/default.rs",
         "src/struct_attributes/swift_name.rs",
     ];
     for path in &bridges {
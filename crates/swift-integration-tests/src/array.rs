@@ -0,0 +1,45 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/array_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructWithArrayField {
+        hash: [u8; 4],
+    }
+
+    #[swift_bridge(swift_repr = "struct")]
+    struct ArrayTestPoint {
+        x: i32,
+        y: i32,
+    }
+
+    extern "Rust" {
+        fn rust_reflect_array_u8(arg: [u8; 4]) -> [u8; 4];
+        fn rust_reflect_array_f64(arg: [f64; 3]) -> [f64; 3];
+        fn rust_reflect_array_bool(arg: [bool; 2]) -> [bool; 2];
+        fn rust_reflect_array_of_structs(arg: [ArrayTestPoint; 2]) -> [ArrayTestPoint; 2];
+        fn rust_reflect_struct_with_array_field(arg: StructWithArrayField) -> StructWithArrayField;
+    }
+}
+
+use self::ffi::{ArrayTestPoint, StructWithArrayField};
+
+fn rust_reflect_array_u8(arg: [u8; 4]) -> [u8; 4] {
+    arg
+}
+
+fn rust_reflect_array_f64(arg: [f64; 3]) -> [f64; 3] {
+    arg
+}
+
+fn rust_reflect_array_bool(arg: [bool; 2]) -> [bool; 2] {
+    arg
+}
+
+fn rust_reflect_array_of_structs(arg: [ArrayTestPoint; 2]) -> [ArrayTestPoint; 2] {
+    arg
+}
+
+fn rust_reflect_struct_with_array_field(arg: StructWithArrayField) -> StructWithArrayField {
+    arg
+}
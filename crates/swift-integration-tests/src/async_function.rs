@@ -16,9 +16,67 @@ mod ffi {
         #[swift_bridg(init)]
         fn new() -> TestRustAsyncSelf;
         async fn reflect_u16(&self, arg: u16) -> u16;
+        fn add_to_count(&mut self, amount: u32) -> u32;
+    }
+
+    extern "Rust" {
+        type AsyncRustError;
+
+        fn message(&self) -> String;
+
+        async fn rust_async_result_u8(succeed: bool) -> Result<u8, String>;
+        async fn rust_async_result_opaque_err(succeed: bool) -> Result<(), AsyncRustError>;
+        async fn rust_async_result_panics() -> Result<u8, String>;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(local)]
+        async fn rust_async_local_rc_sum(a: u32, b: u32) -> u32;
+    }
+
+    extern "Rust" {
+        async fn rust_async_str_len(string: &str) -> usize;
+        async fn rust_async_sum_slice(slice: &[u8]) -> u32;
+        async fn rust_async_take_opaque(arg: TestRustAsyncSelf) -> u32;
+    }
+
+    extern "Swift" {
+        type AsyncSwiftArg;
+
+        fn value(&self) -> u32;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(local)]
+        async fn rust_async_local_read_swift_arg(arg: AsyncSwiftArg) -> u32;
+    }
+
+    extern "Rust" {
+        async fn rust_async_pending_until_cancelled();
+        async fn rust_async_panics();
+        fn rust_async_pending_future_was_dropped() -> bool;
+    }
+
+    extern "Rust" {
+        async fn rust_calls_swift_async_reflect_u8(arg: u8) -> u8;
+        async fn rust_calls_swift_async_return_string() -> String;
+        fn rust_drops_swift_async_future();
+    }
+
+    extern "Swift" {
+        async fn swift_async_reflect_u8(arg: u8) -> u8;
+        async fn swift_async_return_string() -> String;
+        async fn swift_async_pending_until_cancelled();
     }
 }
 
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
 async fn rust_async_return_null() {}
 
 async fn rust_async_reflect_u8(arg: u8) -> u8 {
@@ -33,14 +91,159 @@ async fn rust_async_return_struct() -> ffi::AsyncRustFnReturnStruct {
     ffi::AsyncRustFnReturnStruct
 }
 
-pub struct TestRustAsyncSelf;
+pub struct TestRustAsyncSelf {
+    count: u32,
+}
 
 impl TestRustAsyncSelf {
     fn new() -> Self {
-        TestRustAsyncSelf
+        TestRustAsyncSelf { count: 0 }
     }
 
     async fn reflect_u16(&self, arg: u16) -> u16 {
         arg
     }
+
+    fn add_to_count(&mut self, amount: u32) -> u32 {
+        self.count += amount;
+        self.count
+    }
+}
+
+pub struct AsyncRustError {
+    message: String,
+}
+
+impl AsyncRustError {
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+async fn rust_async_result_u8(succeed: bool) -> Result<u8, String> {
+    if succeed {
+        Ok(123)
+    } else {
+        Err("error".to_string())
+    }
+}
+
+async fn rust_async_result_opaque_err(succeed: bool) -> Result<(), AsyncRustError> {
+    if succeed {
+        Ok(())
+    } else {
+        Err(AsyncRustError {
+            message: "opaque error".to_string(),
+        })
+    }
+}
+
+async fn rust_async_result_panics() -> Result<u8, String> {
+    panic!("async panic")
+}
+
+/// Holds an `Rc` across an await point, so the future is not `Send`.
+async fn rust_async_local_rc_sum(a: u32, b: u32) -> u32 {
+    let a = Rc::new(a);
+    WakeFromAnotherThread { woken: false }.await;
+
+    *a + b
+}
+
+/// Returns `Pending` once and wakes itself up from another thread.
+struct WakeFromAnotherThread {
+    woken: bool,
+}
+
+impl Future for WakeFromAnotherThread {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.woken {
+            return Poll::Ready(());
+        }
+
+        self.woken = true;
+        let waker = cx.waker().clone();
+        std::thread::spawn(move || waker.wake());
+
+        Poll::Pending
+    }
+}
+
+/// Swift frees the string once this returns, so we wait until after then to read it.
+async fn rust_async_str_len(string: &str) -> usize {
+    WakeFromAnotherThread { woken: false }.await;
+    string.len()
+}
+
+async fn rust_async_sum_slice(slice: &[u8]) -> u32 {
+    WakeFromAnotherThread { woken: false }.await;
+    slice.iter().map(|num| *num as u32).sum()
+}
+
+async fn rust_async_take_opaque(arg: TestRustAsyncSelf) -> u32 {
+    WakeFromAnotherThread { woken: false }.await;
+    arg.count
+}
+
+/// Swift's reference to the argument might be gone by the time we read it, so the future needs to
+/// keep it retained.
+async fn rust_async_local_read_swift_arg(arg: ffi::AsyncSwiftArg) -> u32 {
+    WakeFromAnotherThread { woken: false }.await;
+    arg.value()
+}
+
+static PENDING_FUTURE_WAS_DROPPED: AtomicBool = AtomicBool::new(false);
+
+/// Never completes, so the only way for the returned future to get dropped is for the Swift task
+/// that is awaiting it to get cancelled.
+fn rust_async_pending_until_cancelled() -> impl Future<Output = ()> {
+    let set_on_drop = SetOnDrop(&PENDING_FUTURE_WAS_DROPPED);
+
+    async move {
+        let _set_on_drop = set_on_drop;
+        std::future::pending::<()>().await
+    }
+}
+
+async fn rust_async_panics() {
+    panic!("async panic without a result")
+}
+
+fn rust_async_pending_future_was_dropped() -> bool {
+    PENDING_FUTURE_WAS_DROPPED.load(Ordering::SeqCst)
+}
+
+struct SetOnDrop(&'static AtomicBool);
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+async fn rust_calls_swift_async_reflect_u8(arg: u8) -> u8 {
+    ffi::swift_async_reflect_u8(arg).await
+}
+
+async fn rust_calls_swift_async_return_string() -> String {
+    ffi::swift_async_return_string().await
+}
+
+/// Start the Swift async function and then drop its future before the function completes, which
+/// should cancel the Swift task.
+fn rust_drops_swift_async_future() {
+    struct NoopWaker;
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut future = Box::pin(ffi::swift_async_pending_until_cancelled());
+
+    assert!(future
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+    drop(future);
 }
@@ -0,0 +1,38 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/char_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructWithCharField {
+        letter: char,
+    }
+
+    extern "Rust" {
+        fn rust_reflect_char(arg: char) -> char;
+        fn rust_reflect_option_char(arg: Option<char>) -> Option<char>;
+        fn rust_reflect_struct_with_char_field(arg: StructWithCharField) -> StructWithCharField;
+        fn rust_calls_swift_reflect_char(arg: char) -> char;
+    }
+
+    extern "Swift" {
+        fn swift_reflect_char(arg: char) -> char;
+    }
+}
+
+use self::ffi::StructWithCharField;
+
+fn rust_reflect_char(arg: char) -> char {
+    arg
+}
+
+fn rust_reflect_option_char(arg: Option<char>) -> Option<char> {
+    arg
+}
+
+fn rust_reflect_struct_with_char_field(arg: StructWithCharField) -> StructWithCharField {
+    arg
+}
+
+fn rust_calls_swift_reflect_char(arg: char) -> char {
+    ffi::swift_reflect_char(arg)
+}
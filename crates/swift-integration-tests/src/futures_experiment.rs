@@ -2,13 +2,14 @@
 pub extern "C" fn async_rust_fn(
     callback_wrapper: *mut std::ffi::c_void,
     callback: extern "C" fn(*mut std::ffi::c_void, i32) -> (),
+    on_cancelled: extern "C" fn(*mut std::ffi::c_void, bool) -> (),
 ) {
-    let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+    let callback_wrapper =
+        swift_bridge::async_support::SwiftCallbackWrapper::new(callback_wrapper, on_cancelled);
     let task = async move {
         let val = async_rust_fn_to_expose().await;
 
-        let callback_wrapper = callback_wrapper;
-        let callback_wrapper = callback_wrapper.0;
+        let callback_wrapper = callback_wrapper.into_raw();
         (callback)(callback_wrapper, val)
     };
     swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task));
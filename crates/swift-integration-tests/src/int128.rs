@@ -0,0 +1,37 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/int128_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_reflect_u128(arg: u128) -> u128;
+        fn rust_reflect_i128(arg: i128) -> i128;
+        fn rust_reflect_option_u128(arg: Option<u128>) -> Option<u128>;
+        fn rust_reflect_option_i128(arg: Option<i128>) -> Option<i128>;
+        fn rust_u128_max() -> u128;
+        fn rust_i128_min() -> i128;
+    }
+}
+
+fn rust_reflect_u128(arg: u128) -> u128 {
+    arg
+}
+
+fn rust_reflect_i128(arg: i128) -> i128 {
+    arg
+}
+
+fn rust_reflect_option_u128(arg: Option<u128>) -> Option<u128> {
+    arg
+}
+
+fn rust_reflect_option_i128(arg: Option<i128>) -> Option<i128> {
+    arg
+}
+
+fn rust_u128_max() -> u128 {
+    u128::MAX
+}
+
+fn rust_i128_min() -> i128 {
+    i128::MIN
+}
@@ -0,0 +1,73 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/iterator_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct IteratorTestPoint {
+        x: u32,
+        y: u32,
+    }
+
+    extern "Rust" {
+        type IteratorTestTable;
+        type IteratorTestRow;
+
+        #[swift_bridge(init)]
+        fn new(row_count: u32) -> IteratorTestTable;
+
+        fn rows(self: &IteratorTestTable) -> impl Iterator<Item = IteratorTestRow> + '_;
+        fn id(self: &IteratorTestRow) -> u32;
+    }
+
+    extern "Rust" {
+        fn rust_count_up_to(max: u32) -> impl Iterator<Item = u32>;
+        fn rust_words(text: String) -> Box<dyn Iterator<Item = String>>;
+        fn rust_diagonal_points(count: u32) -> impl Iterator<Item = IteratorTestPoint>;
+        fn rust_empty_iterator() -> impl Iterator<Item = u8>;
+    }
+}
+
+use ffi::IteratorTestPoint;
+
+pub struct IteratorTestTable {
+    row_ids: Vec<u32>,
+}
+
+pub struct IteratorTestRow {
+    id: u32,
+}
+
+impl IteratorTestTable {
+    fn new(row_count: u32) -> Self {
+        IteratorTestTable {
+            row_ids: (0..row_count).collect(),
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = IteratorTestRow> + '_ {
+        self.row_ids.iter().map(|id| IteratorTestRow { id: *id })
+    }
+}
+
+impl IteratorTestRow {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+fn rust_count_up_to(max: u32) -> impl Iterator<Item = u32> {
+    1..=max
+}
+
+fn rust_words(text: String) -> Box<dyn Iterator<Item = String>> {
+    let words: Vec<String> = text.split_whitespace().map(|s| s.to_string()).collect();
+    Box::new(words.into_iter())
+}
+
+fn rust_diagonal_points(count: u32) -> impl Iterator<Item = IteratorTestPoint> {
+    (0..count).map(|i| IteratorTestPoint { x: i, y: i })
+}
+
+fn rust_empty_iterator() -> impl Iterator<Item = u8> {
+    std::iter::empty()
+}
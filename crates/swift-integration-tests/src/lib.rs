@@ -1,17 +1,27 @@
 mod expose_opaque_rust_type;
 mod import_opaque_swift_class;
 
+mod array;
 mod async_function;
 mod bool;
+mod char;
 mod conditional_compilation;
 mod generics;
+mod int128;
+mod iterator;
+mod non_zero;
+mod operators;
 mod option;
+mod path;
 mod pointer;
 mod rust_function_uses_opaque_swift_type;
 mod shared_types;
 mod slice;
+mod stream;
 mod string;
 mod swift_function_uses_opaque_rust_type;
+mod time;
+mod traits;
 mod vec;
 
 mod function_attributes;
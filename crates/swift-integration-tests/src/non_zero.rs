@@ -0,0 +1,38 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/non_zero_codegen_tests.rs
+
+use std::num::{NonZeroI64, NonZeroU32, NonZeroU8};
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructWithNonZeroField {
+        id: std::num::NonZeroU64,
+    }
+
+    extern "Rust" {
+        fn rust_reflect_non_zero_u32(arg: NonZeroU32) -> NonZeroU32;
+        fn rust_reflect_non_zero_i64(arg: NonZeroI64) -> NonZeroI64;
+        fn rust_reflect_option_non_zero_u8(arg: Option<NonZeroU8>) -> Option<NonZeroU8>;
+        fn rust_reflect_struct_with_non_zero_field(
+            arg: StructWithNonZeroField,
+        ) -> StructWithNonZeroField;
+    }
+}
+
+use self::ffi::StructWithNonZeroField;
+
+fn rust_reflect_non_zero_u32(arg: NonZeroU32) -> NonZeroU32 {
+    arg
+}
+
+fn rust_reflect_non_zero_i64(arg: NonZeroI64) -> NonZeroI64 {
+    arg
+}
+
+fn rust_reflect_option_non_zero_u8(arg: Option<NonZeroU8>) -> Option<NonZeroU8> {
+    arg
+}
+
+fn rust_reflect_struct_with_non_zero_field(arg: StructWithNonZeroField) -> StructWithNonZeroField {
+    arg
+}
@@ -1,2 +1,4 @@
 mod already_declared;
+mod conversions;
 mod copy;
+mod protocols;
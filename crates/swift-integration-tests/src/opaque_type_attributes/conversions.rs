@@ -0,0 +1,109 @@
+//! Swift initializers that Swift classes get from their Rust type's `From`, `TryFrom` and
+//! `FromStr` implementations.
+//! See SwiftRustIntegrationTestRunnerTests/ConversionInitializerTests.swift
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(From(u8), TryFrom(i64), FromStr, CustomStringConvertible)]
+        type Percentage;
+
+        fn value(&self) -> u8;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(TryFrom(String, Error = String))]
+        type Username;
+
+        fn name(&self) -> String;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(From(&str))]
+        type Label;
+
+        fn text(&self) -> String;
+    }
+}
+
+pub struct Percentage(u8);
+
+impl Percentage {
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Values above 100 are clamped.
+impl From<u8> for Percentage {
+    fn from(value: u8) -> Self {
+        Percentage(value.min(100))
+    }
+}
+
+impl TryFrom<i64> for Percentage {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0..=100 => Ok(Percentage(value as u8)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Parses "42" or "42%".
+impl FromStr for Percentage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: i64 = s.trim_end_matches('%').parse().map_err(|_| ())?;
+        Percentage::try_from(value)
+    }
+}
+
+impl Display for Percentage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+pub struct Username(String);
+
+impl Username {
+    fn name(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        if name.is_empty() {
+            return Err("A username can't be empty.".to_string());
+        }
+        if name.contains(char::is_whitespace) {
+            return Err(format!("The username {:?} contains whitespace.", name));
+        }
+
+        Ok(Username(name))
+    }
+}
+
+pub struct Label(String);
+
+impl Label {
+    fn text(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl From<&str> for Label {
+    fn from(text: &str) -> Self {
+        Label(text.to_string())
+    }
+}
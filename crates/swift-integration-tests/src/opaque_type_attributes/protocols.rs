@@ -0,0 +1,189 @@
+//! Protocol conformances that Swift classes get from their Rust type's trait implementations.
+//! See SwiftRustIntegrationTestRunnerTests/OpaqueTypeProtocolTests.swift
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Hashable)]
+        type HashableRustType;
+
+        #[swift_bridge(init)]
+        fn new(id: u32, name: String) -> HashableRustType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Equatable)]
+        type EquatableRustType;
+
+        #[swift_bridge(init)]
+        fn new(value: f32) -> EquatableRustType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Comparable)]
+        type ComparableRustType;
+
+        #[swift_bridge(init)]
+        fn new(priority: u8) -> ComparableRustType;
+
+        fn priority(&self) -> u8;
+        fn reference_to(&self) -> &ComparableRustType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+        type StringConvertibleRustType;
+
+        #[swift_bridge(init)]
+        fn new(x: i32, y: i32) -> StringConvertibleRustType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Clone)]
+        type CloneableRustType;
+
+        #[swift_bridge(init)]
+        fn new(value: u32) -> CloneableRustType;
+
+        fn value(&self) -> u32;
+        fn set_value(&mut self, value: u32);
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Default)]
+        type DefaultRustType;
+
+        #[swift_bridge(init)]
+        fn new(value: u32) -> DefaultRustType;
+
+        fn value(&self) -> u32;
+    }
+}
+
+/// The hash only includes the `id`, so values with the same `id` and different names are unequal
+/// but have the same hash.
+pub struct HashableRustType {
+    id: u32,
+    name: String,
+}
+
+impl HashableRustType {
+    fn new(id: u32, name: String) -> Self {
+        HashableRustType { id, name }
+    }
+}
+
+impl PartialEq for HashableRustType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.name == other.name
+    }
+}
+
+impl std::hash::Hash for HashableRustType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Only implements `PartialEq`, since `f32` is not `Eq` or `Hash`.
+#[derive(PartialEq)]
+pub struct EquatableRustType {
+    value: f32,
+}
+
+impl EquatableRustType {
+    fn new(value: f32) -> Self {
+        EquatableRustType { value }
+    }
+}
+
+/// Ordered by priority, from highest to lowest.
+#[derive(PartialEq, Eq)]
+pub struct ComparableRustType {
+    priority: u8,
+}
+
+impl ComparableRustType {
+    fn new(priority: u8) -> Self {
+        ComparableRustType { priority }
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn reference_to(&self) -> &ComparableRustType {
+        self
+    }
+}
+
+impl PartialOrd for ComparableRustType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ComparableRustType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[derive(Debug)]
+pub struct StringConvertibleRustType {
+    x: i32,
+    y: i32,
+}
+
+impl StringConvertibleRustType {
+    fn new(x: i32, y: i32) -> Self {
+        StringConvertibleRustType { x, y }
+    }
+}
+
+impl std::fmt::Display for StringConvertibleRustType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Clone)]
+pub struct CloneableRustType {
+    value: Box<u32>,
+}
+
+impl CloneableRustType {
+    fn new(value: u32) -> Self {
+        CloneableRustType {
+            value: Box::new(value),
+        }
+    }
+
+    fn value(&self) -> u32 {
+        *self.value
+    }
+
+    fn set_value(&mut self, value: u32) {
+        *self.value = value;
+    }
+}
+
+pub struct DefaultRustType {
+    value: u32,
+}
+
+impl DefaultRustType {
+    fn new(value: u32) -> Self {
+        DefaultRustType { value }
+    }
+
+    fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+impl Default for DefaultRustType {
+    fn default() -> Self {
+        DefaultRustType { value: 7 }
+    }
+}
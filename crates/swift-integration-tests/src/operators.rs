@@ -0,0 +1,182 @@
+//! See SwiftRustIntegrationTestRunnerTests/OperatorTests.swift
+
+use std::ops::{Add, Index, Mul, Neg, Sub};
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct", operators(Add, Sub, Neg, Index(Output = f64)))]
+    struct OperatorsVec2 {
+        x: f64,
+        y: f64,
+    }
+
+    extern "Rust" {
+        #[swift_bridge(operators(Add, Mul, Neg, Index(Output = i64)))]
+        type OperatorsPolynomial;
+
+        #[swift_bridge(init)]
+        fn new(constant: i64, linear: i64, quadratic: i64) -> OperatorsPolynomial;
+
+        fn degree(&self) -> usize;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Clone, operators(Sub, Neg))]
+        type OperatorsCounter;
+
+        #[swift_bridge(init)]
+        fn new(count: i32) -> OperatorsCounter;
+
+        fn count(&self) -> i32;
+    }
+}
+
+impl Add for ffi::OperatorsVec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ffi::OperatorsVec2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for ffi::OperatorsVec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ffi::OperatorsVec2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Neg for ffi::OperatorsVec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ffi::OperatorsVec2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Index<usize> for ffi::OperatorsVec2 {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("OperatorsVec2 index out of bounds: {}", index),
+        }
+    }
+}
+
+/// A polynomial, stored as its coefficients from lowest to highest degree.
+pub struct OperatorsPolynomial {
+    coefficients: Vec<i64>,
+}
+
+impl OperatorsPolynomial {
+    fn new(constant: i64, linear: i64, quadratic: i64) -> Self {
+        OperatorsPolynomial {
+            coefficients: vec![constant, linear, quadratic],
+        }
+        .trimmed()
+    }
+
+    fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    fn trimmed(mut self) -> Self {
+        while self.coefficients.last() == Some(&0) {
+            self.coefficients.pop();
+        }
+        self
+    }
+}
+
+impl Add for &OperatorsPolynomial {
+    type Output = OperatorsPolynomial;
+
+    fn add(self, rhs: Self) -> OperatorsPolynomial {
+        let len = self.coefficients.len().max(rhs.coefficients.len());
+        let coefficients = (0..len).map(|idx| self[idx] + rhs[idx]).collect();
+
+        OperatorsPolynomial { coefficients }.trimmed()
+    }
+}
+
+impl Mul for &OperatorsPolynomial {
+    type Output = OperatorsPolynomial;
+
+    fn mul(self, rhs: Self) -> OperatorsPolynomial {
+        let mut coefficients = vec![0; self.coefficients.len() + rhs.coefficients.len()];
+
+        for (i, lhs) in self.coefficients.iter().enumerate() {
+            for (j, rhs) in rhs.coefficients.iter().enumerate() {
+                coefficients[i + j] += lhs * rhs;
+            }
+        }
+
+        OperatorsPolynomial { coefficients }.trimmed()
+    }
+}
+
+impl Neg for &OperatorsPolynomial {
+    type Output = OperatorsPolynomial;
+
+    fn neg(self) -> OperatorsPolynomial {
+        OperatorsPolynomial {
+            coefficients: self.coefficients.iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl Index<usize> for OperatorsPolynomial {
+    type Output = i64;
+
+    /// Coefficients past the polynomial's degree are zero.
+    fn index(&self, index: usize) -> &i64 {
+        self.coefficients.get(index).unwrap_or(&0)
+    }
+}
+
+/// Implements its operators on owned values, so its operands get cloned.
+#[derive(Clone)]
+pub struct OperatorsCounter {
+    count: i32,
+}
+
+impl OperatorsCounter {
+    fn new(count: i32) -> Self {
+        OperatorsCounter { count }
+    }
+
+    fn count(&self) -> i32 {
+        self.count
+    }
+}
+
+impl Sub for OperatorsCounter {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        OperatorsCounter {
+            count: self.count - rhs.count,
+        }
+    }
+}
+
+impl Neg for OperatorsCounter {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        OperatorsCounter { count: -self.count }
+    }
+}
@@ -0,0 +1,47 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/path_codegen_tests.rs
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_path_file_name(path: &Path) -> String;
+        fn rust_path_join(path: PathBuf, child: &str) -> PathBuf;
+        fn rust_reflect_os_string(arg: OsString) -> OsString;
+        fn rust_reflect_option_path_buf(arg: Option<PathBuf>) -> Option<PathBuf>;
+        fn rust_option_ref_path_is_some(arg: Option<&Path>) -> bool;
+        fn rust_static_os_str() -> &'static OsStr;
+        fn rust_static_non_utf8_path() -> &'static Path;
+    }
+}
+
+fn rust_path_file_name(path: &Path) -> String {
+    path.file_name().unwrap().to_string_lossy().into_owned()
+}
+
+fn rust_path_join(path: PathBuf, child: &str) -> PathBuf {
+    path.join(child)
+}
+
+fn rust_reflect_os_string(arg: OsString) -> OsString {
+    arg
+}
+
+fn rust_reflect_option_path_buf(arg: Option<PathBuf>) -> Option<PathBuf> {
+    arg
+}
+
+fn rust_option_ref_path_is_some(arg: Option<&Path>) -> bool {
+    arg.is_some()
+}
+
+fn rust_static_os_str() -> &'static OsStr {
+    OsStr::new("/tmp/héllo")
+}
+
+fn rust_static_non_utf8_path() -> &'static Path {
+    use std::os::unix::ffi::OsStrExt;
+
+    Path::new(OsStr::from_bytes(b"/tmp/\xFF"))
+}
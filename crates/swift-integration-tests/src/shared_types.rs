@@ -1,2 +1,3 @@
+mod codable;
 mod shared_enum;
 mod shared_struct;
@@ -0,0 +1,35 @@
+//! See SwiftRustIntegrationTestRunnerTests/CodableTests.swift
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct", Codable)]
+    struct CodableStruct {
+        id: u32,
+        name: String,
+        score: Option<f64>,
+        size: CodableEnum,
+        point: CodableTupleStruct,
+    }
+
+    #[swift_bridge(swift_repr = "struct", Codable)]
+    struct CodableTupleStruct(i32, i32);
+
+    #[swift_bridge(Codable)]
+    enum CodableEnum {
+        Small,
+        Large,
+    }
+
+    extern "Rust" {
+        fn rust_serialize_codable_struct(value: CodableStruct) -> String;
+        fn rust_deserialize_codable_struct(json: String) -> CodableStruct;
+    }
+}
+
+fn rust_serialize_codable_struct(value: ffi::CodableStruct) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn rust_deserialize_codable_struct(json: String) -> ffi::CodableStruct {
+    serde_json::from_str(&json).unwrap()
+}
@@ -0,0 +1,134 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/stream_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_count_stream(max: u32) -> impl Stream<Item = u32>;
+        fn rust_words_stream(text: String) -> Pin<Box<dyn Stream<Item = String> + Send>>;
+        fn rust_never_ending_stream() -> impl Stream<Item = u8>;
+        fn rust_live_stream_count() -> u32;
+        fn rust_chars_stream(text: String) -> impl Stream<Item = char>;
+        fn rust_panicking_stream() -> impl Stream<Item = u8>;
+    }
+}
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::{Context, Poll};
+use swift_bridge::async_support::Stream;
+
+/// The number of `CountStream`s that have not been dropped yet.
+static LIVE_STREAMS: AtomicU32 = AtomicU32::new(0);
+
+/// Yields `1..=max`, or counts forever if there is no max.
+struct CountStream {
+    count: u32,
+    max: Option<u32>,
+}
+
+impl CountStream {
+    fn new(max: Option<u32>) -> Self {
+        LIVE_STREAMS.fetch_add(1, Ordering::SeqCst);
+        CountStream { count: 0, max }
+    }
+}
+
+impl Stream for CountStream {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        if self.max == Some(self.count) {
+            return Poll::Ready(None);
+        }
+
+        self.count += 1;
+        Poll::Ready(Some(self.count))
+    }
+}
+
+impl Drop for CountStream {
+    fn drop(&mut self) {
+        LIVE_STREAMS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct WordStream {
+    words: std::vec::IntoIter<String>,
+    _live: CountStream,
+}
+
+impl Stream for WordStream {
+    type Item = String;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<String>> {
+        Poll::Ready(self.words.next())
+    }
+}
+
+/// Never yields an item, so the only way to stop iterating is to cancel the Swift task.
+struct PendingStream {
+    _live: CountStream,
+}
+
+impl Stream for PendingStream {
+    type Item = u8;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        Poll::Pending
+    }
+}
+
+fn rust_count_stream(max: u32) -> impl Stream<Item = u32> {
+    CountStream::new(Some(max))
+}
+
+fn rust_words_stream(text: String) -> Pin<Box<dyn Stream<Item = String> + Send>> {
+    let words: Vec<String> = text.split_whitespace().map(|s| s.to_string()).collect();
+
+    Box::pin(WordStream {
+        words: words.into_iter(),
+        _live: CountStream::new(None),
+    })
+}
+
+fn rust_never_ending_stream() -> impl Stream<Item = u8> {
+    PendingStream {
+        _live: CountStream::new(None),
+    }
+}
+
+fn rust_live_stream_count() -> u32 {
+    LIVE_STREAMS.load(Ordering::SeqCst)
+}
+
+struct CharStream {
+    chars: std::vec::IntoIter<char>,
+}
+
+impl Stream for CharStream {
+    type Item = char;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<char>> {
+        Poll::Ready(self.chars.next())
+    }
+}
+
+fn rust_chars_stream(text: String) -> impl Stream<Item = char> {
+    CharStream {
+        chars: text.chars().collect::<Vec<_>>().into_iter(),
+    }
+}
+
+struct PanickingStream;
+
+impl Stream for PanickingStream {
+    type Item = u8;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        panic!("stream panic")
+    }
+}
+
+fn rust_panicking_stream() -> impl Stream<Item = u8> {
+    PanickingStream
+}
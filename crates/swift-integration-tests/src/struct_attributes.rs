@@ -1,2 +1,3 @@
 mod already_declared;
+mod default;
 mod swift_name;
@@ -0,0 +1,21 @@
+//! See SwiftRustIntegrationTestRunnerTests/SharedStructAttributeTests.swift
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct", Default)]
+    struct DefaultStruct {
+        count: u32,
+        ratio: f64,
+        enabled: bool,
+    }
+}
+
+impl Default for ffi::DefaultStruct {
+    fn default() -> Self {
+        ffi::DefaultStruct {
+            count: 3,
+            ratio: 0.5,
+            enabled: true,
+        }
+    }
+}
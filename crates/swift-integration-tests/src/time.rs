@@ -0,0 +1,56 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/time_codegen_tests.rs
+
+use std::time::{Duration, SystemTime};
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructWithTimeFields {
+        timeout: std::time::Duration,
+        created_at: std::time::SystemTime,
+    }
+
+    extern "Rust" {
+        fn rust_reflect_duration(arg: Duration) -> Duration;
+        fn rust_reflect_system_time(arg: SystemTime) -> SystemTime;
+        fn rust_reflect_option_duration(arg: Option<Duration>) -> Option<Duration>;
+        fn rust_reflect_option_system_time(arg: Option<SystemTime>) -> Option<SystemTime>;
+        fn rust_reflect_struct_with_time_fields(arg: StructWithTimeFields) -> StructWithTimeFields;
+        fn rust_duration_secs_f64(arg: Duration) -> f64;
+        fn rust_calls_swift_add_one_second(arg: SystemTime) -> SystemTime;
+    }
+
+    extern "Swift" {
+        fn swift_add_one_second(arg: SystemTime) -> SystemTime;
+    }
+}
+
+use self::ffi::StructWithTimeFields;
+
+fn rust_reflect_duration(arg: Duration) -> Duration {
+    arg
+}
+
+fn rust_reflect_system_time(arg: SystemTime) -> SystemTime {
+    arg
+}
+
+fn rust_reflect_option_duration(arg: Option<Duration>) -> Option<Duration> {
+    arg
+}
+
+fn rust_reflect_option_system_time(arg: Option<SystemTime>) -> Option<SystemTime> {
+    arg
+}
+
+fn rust_reflect_struct_with_time_fields(arg: StructWithTimeFields) -> StructWithTimeFields {
+    arg
+}
+
+fn rust_duration_secs_f64(arg: Duration) -> f64 {
+    arg.as_secs_f64()
+}
+
+fn rust_calls_swift_add_one_second(arg: SystemTime) -> SystemTime {
+    ffi::swift_add_one_second(arg)
+}
@@ -0,0 +1,90 @@
+//! See SwiftRustIntegrationTestRunnerTests/TraitTests.swift
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type Circle;
+
+        #[swift_bridge(init)]
+        fn new(radius: f64) -> Circle;
+    }
+
+    extern "Rust" {
+        type Square;
+    }
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn name(&self) -> String;
+        fn scale(&mut self, factor: f64);
+    }
+
+    impl Shape for Circle {}
+    impl Shape for Square {}
+
+    extern "Rust" {
+        fn make_square(side: f64) -> Box<dyn Shape>;
+        fn shape_area(shape: Box<dyn Shape>) -> f64;
+        fn scale_shape(shape: Box<dyn Shape>, factor: f64) -> Box<dyn Shape>;
+        fn describe_shape(shape: Box<dyn Shape>) -> String;
+    }
+}
+
+pub struct Circle {
+    radius: f64,
+}
+
+impl Circle {
+    fn new(radius: f64) -> Self {
+        Circle { radius }
+    }
+}
+
+impl ffi::Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn name(&self) -> String {
+        "circle".to_string()
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.radius *= factor;
+    }
+}
+
+pub struct Square {
+    side: f64,
+}
+
+impl ffi::Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn name(&self) -> String {
+        "square".to_string()
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.side *= factor;
+    }
+}
+
+fn make_square(side: f64) -> Box<dyn ffi::Shape> {
+    Box::new(Square { side })
+}
+
+fn shape_area(shape: Box<dyn ffi::Shape>) -> f64 {
+    shape.area()
+}
+
+fn scale_shape(mut shape: Box<dyn ffi::Shape>, factor: f64) -> Box<dyn ffi::Shape> {
+    shape.scale(factor);
+    shape
+}
+
+fn describe_shape(shape: Box<dyn ffi::Shape>) -> String {
+    format!("{} with an area of {}", shape.name(), shape.area())
+}
@@ -8,7 +8,7 @@ group.enter()
 
 print("We're in Swift about to call our async Rust function.")
 Task {
-    let ipAddress = await get_my_ip_from_rust()
+    let ipAddress = try await get_my_ip_from_rust()
     print("Now we're in Swift again. IP address: \(ipAddress.origin.toString())")
 
     group.leave()
@@ -1,25 +1,308 @@
-use once_cell::sync::Lazy;
+//! Support for bridging async functions.
+//!
+//! By default, async Rust functions that are called from Swift run on a tokio runtime that
+//! `swift-bridge` starts on a dedicated thread.
+//!
+//! Applications that already have their own runtime can instead register an executor using
+//! [`set_executor`] before the first async function gets called.
+//!
+//! ```no_run
+//! # #[cfg(feature = "tokio")]
+//! # fn main() {
+//! let runtime = tokio::runtime::Builder::new_multi_thread()
+//!     .worker_threads(2)
+//!     .enable_all()
+//!     .build()
+//!     .unwrap();
+//!
+//! swift_bridge::async_support::set_executor(runtime.handle().clone()).unwrap();
+//! # std::mem::forget(runtime);
+//! # }
+//! # #[cfg(not(feature = "tokio"))]
+//! # fn main() {}
+//! ```
+//!
+//! The `async-runtime-agnostic` feature enables async function bridging without depending on
+//! tokio. In that case an executor must be registered, since there is no built-in runtime to fall
+//! back to.
+
+use once_cell::sync::OnceCell;
+use std::any::Any;
+use std::cell::RefCell;
 use std::future::Future;
+use std::panic::AssertUnwindSafe;
 use std::pin::Pin;
-use std::sync::mpsc::{Receiver, SyncSender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
 
-#[doc(hidden)]
-pub static ASYNC_RUNTIME: Lazy<TokioRuntime> = Lazy::new(|| {
-    let (sender, receiver) = std::sync::mpsc::sync_channel(10_000);
+/// A task that drives an async Rust function that was called from Swift.
+pub type AsyncTask = Pin<Box<dyn Future<Output = ()> + 'static + Send>>;
+
+/// Spawns the tasks that drive async Rust functions that were called from Swift.
+///
+/// This is implemented for closures that take an [`AsyncTask`], as well as for
+/// `tokio::runtime::Handle` when the `async` feature is enabled.
+pub trait AsyncExecutor: Send + Sync + 'static {
+    /// Run the task to completion in the background.
+    fn spawn(&self, task: AsyncTask);
+}
+
+impl<F> AsyncExecutor for F
+where
+    F: Fn(AsyncTask) + Send + Sync + 'static,
+{
+    fn spawn(&self, task: AsyncTask) {
+        (self)(task)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncExecutor for tokio::runtime::Handle {
+    fn spawn(&self, task: AsyncTask) {
+        tokio::runtime::Handle::spawn(self, task);
+    }
+}
+
+/// Returned by [`set_executor`] if an executor is already in use.
+#[derive(Debug)]
+pub struct ExecutorAlreadySet;
+
+impl std::fmt::Display for ExecutorAlreadySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("An async executor has already been set.")
+    }
+}
+
+impl std::error::Error for ExecutorAlreadySet {}
+
+static EXECUTOR: OnceCell<Box<dyn AsyncExecutor>> = OnceCell::new();
+
+/// Use the given executor to run async Rust functions that are called from Swift.
+///
+/// This must be called before the first async function gets called. After that point the
+/// executor can no longer be changed, so an error is returned.
+pub fn set_executor(executor: impl AsyncExecutor) -> Result<(), ExecutorAlreadySet> {
+    EXECUTOR
+        .set(Box::new(executor))
+        .map_err(|_| ExecutorAlreadySet)
+}
 
-    let runtime = TokioRuntime { sender };
+/// Returned by [`shutdown`] if some tasks were still running when the timeout elapsed.
+///
+/// These tasks get dropped, and the Swift tasks that were awaiting them throw a
+/// `RustAsyncRuntimeShutDown`.
+#[derive(Debug)]
+pub struct ShutdownTimedOut {
+    /// The number of tasks that had not completed.
+    pub in_flight_tasks: usize,
+}
+
+impl std::fmt::Display for ShutdownTimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Timed out waiting for {} async task(s) to complete.",
+            self.in_flight_tasks
+        )
+    }
+}
 
-    runtime.start_runtime(receiver);
+impl std::error::Error for ShutdownTimedOut {}
 
-    runtime
+/// Whether the runtime was shut down and how many tasks are in flight.
+///
+/// Both live behind the same lock so that a task can't get spawned after [`shutdown`] has seen
+/// that no tasks are in flight.
+static RUNTIME_STATE: Mutex<RuntimeState> = Mutex::new(RuntimeState {
+    is_shut_down: false,
+    in_flight_tasks: 0,
 });
-type AsyncFnToSpawn = Pin<Box<dyn Future<Output = ()> + 'static + Send>>;
+static IN_FLIGHT_TASKS_CHANGED: Condvar = Condvar::new();
+
+struct RuntimeState {
+    is_shut_down: bool,
+    in_flight_tasks: usize,
+}
+
+/// The number of async Rust functions that were called from Swift and are still running.
+///
+/// This does not include functions that use `#[swift_bridge(local)]`, since they run on the main
+/// thread instead of the executor.
+pub fn in_flight_tasks() -> usize {
+    RUNTIME_STATE.lock().unwrap().in_flight_tasks
+}
+
+/// Stop accepting new tasks and wait up to `timeout` for the in-flight tasks to complete.
+///
+/// Async Rust functions that get called after this point throw a `RustAsyncRuntimeShutDown` in
+/// Swift.
+///
+/// If the built-in tokio runtime is in use it gets shut down, dropping any tasks that are still
+/// running. An executor that was registered using [`set_executor`] is left running, since the
+/// application owns it.
+///
+/// This blocks the current thread, so it must not be called from within an async task.
+pub fn shutdown(timeout: Duration) -> Result<(), ShutdownTimedOut> {
+    let deadline = Instant::now() + timeout;
+    let mut state = RUNTIME_STATE.lock().unwrap();
+    state.is_shut_down = true;
+    while state.in_flight_tasks > 0 {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+
+        state = IN_FLIGHT_TASKS_CHANGED
+            .wait_timeout(state, deadline - now)
+            .unwrap()
+            .0;
+    }
+    let in_flight_tasks = state.in_flight_tasks;
+    drop(state);
+
+    #[cfg(feature = "tokio")]
+    if let Some(runtime) = TOKIO_RUNTIME.get() {
+        runtime.stop();
+    }
+
+    if in_flight_tasks == 0 {
+        Ok(())
+    } else {
+        Err(ShutdownTimedOut { in_flight_tasks })
+    }
+}
+
+/// Tracks a task for [`in_flight_tasks`] until the task is either completed or dropped.
+struct InFlightTask;
+
+impl InFlightTask {
+    /// Start tracking a task, or return `None` if the runtime was shut down.
+    fn new() -> Option<Self> {
+        let mut state = RUNTIME_STATE.lock().unwrap();
+        if state.is_shut_down {
+            return None;
+        }
+
+        state.in_flight_tasks += 1;
+        Some(InFlightTask)
+    }
+}
+
+impl Drop for InFlightTask {
+    fn drop(&mut self) {
+        RUNTIME_STATE.lock().unwrap().in_flight_tasks -= 1;
+        IN_FLIGHT_TASKS_CHANGED.notify_all();
+    }
+}
 
 #[doc(hidden)]
-pub struct TokioRuntime {
-    sender: SyncSender<AsyncFnToSpawn>,
+pub static ASYNC_RUNTIME: AsyncRuntime = AsyncRuntime { _private: () };
+
+#[doc(hidden)]
+pub struct AsyncRuntime {
+    _private: (),
 }
 
+#[doc(hidden)]
+impl AsyncRuntime {
+    /// Spawn the task on the executor.
+    ///
+    /// After [`shutdown`] the task gets dropped instead, and its [`SwiftCallbackWrapper`] tells
+    /// Swift that the runtime was shut down.
+    pub fn spawn_task(&self, task: AsyncTask) {
+        let in_flight = match InFlightTask::new() {
+            Some(in_flight) => in_flight,
+            None => return,
+        };
+
+        executor().spawn(Box::pin(async move {
+            let _in_flight = in_flight;
+            task.await
+        }));
+    }
+}
+
+fn is_shut_down() -> bool {
+    RUNTIME_STATE.lock().unwrap().is_shut_down
+}
+
+#[cfg(feature = "tokio")]
+static TOKIO_RUNTIME: OnceCell<TokioRuntime> = OnceCell::new();
+
+#[cfg(feature = "tokio")]
+fn executor() -> &'static dyn AsyncExecutor {
+    EXECUTOR
+        .get_or_init(|| {
+            let runtime = TOKIO_RUNTIME.get_or_init(TokioRuntime::start);
+            Box::new(move |task| runtime.spawn(task))
+        })
+        .as_ref()
+}
+
+#[cfg(not(feature = "tokio"))]
+fn executor() -> &'static dyn AsyncExecutor {
+    EXECUTOR
+        .get()
+        .expect(
+            "No async executor has been set. Call `swift_bridge::async_support::set_executor` \
+before calling any async Rust functions from Swift.",
+        )
+        .as_ref()
+}
+
+/// The runtime that we use when the application does not set its own executor.
+#[cfg(feature = "tokio")]
+struct TokioRuntime {
+    sender: Mutex<Option<std::sync::mpsc::SyncSender<AsyncTask>>>,
+    thread: Mutex<Option<std::thread::JoinHandle<tokio::runtime::Runtime>>>,
+}
+
+#[cfg(feature = "tokio")]
+impl TokioRuntime {
+    fn start() -> Self {
+        let (sender, receiver) = std::sync::mpsc::sync_channel::<AsyncTask>(10_000);
+
+        let thread = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            while let Ok(task) = receiver.recv() {
+                runtime.spawn(task);
+            }
+
+            runtime
+        });
+
+        TokioRuntime {
+            sender: Mutex::new(Some(sender)),
+            thread: Mutex::new(Some(thread)),
+        }
+    }
+
+    fn spawn(&self, task: AsyncTask) {
+        // If the runtime was stopped the task gets dropped.
+        if let Some(sender) = self.sender.lock().unwrap().as_ref() {
+            let _ = sender.send(task);
+        }
+    }
+
+    /// Drop any tasks that are still running and then stop the runtime's threads.
+    fn stop(&self) {
+        self.sender.lock().unwrap().take();
+
+        if let Some(thread) = self.thread.lock().unwrap().take() {
+            let runtime = thread.join().unwrap();
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// The Swift callback wrapper that an async Rust function hands its result to.
+///
+/// If the task gets dropped before it completes, such as when the runtime is shut down, the
+/// wrapper calls the function's `on_cancelled` callback so that the awaiting Swift task doesn't
+/// hang. The callback's second argument tells Swift whether the runtime was shut down.
+//
 // TODO: Audit to make sure that this is safe to be Send/Sync.
 //  Need to research Swift class thread safety. If there are cases where this can be unsafe then
 //  we can just have one tokio runtime per thread (lazily initialized) and then run async functions
@@ -28,25 +311,428 @@ pub struct TokioRuntime {
 //  Make sure to think through the implications of non thread-safe types in the async function's
 //  arguments or its return type.
 #[doc(hidden)]
-pub struct SwiftCallbackWrapper(pub *mut std::ffi::c_void);
+pub struct SwiftCallbackWrapper {
+    ptr: *mut std::ffi::c_void,
+    on_dropped: Option<extern "C" fn(*mut std::ffi::c_void, bool)>,
+}
 unsafe impl Send for SwiftCallbackWrapper {}
 unsafe impl Sync for SwiftCallbackWrapper {}
 
 #[doc(hidden)]
-impl TokioRuntime {
-    pub fn spawn_task(&self, task: AsyncFnToSpawn) {
-        self.sender.send(task).unwrap();
-    }
-
-    fn start_runtime(&self, receiver: Receiver<AsyncFnToSpawn>) {
-        std::thread::spawn(move || {
-            tokio::runtime::Runtime::new()
-                .unwrap()
-                .block_on(async move {
-                    while let Ok(task) = receiver.recv() {
-                        tokio::spawn(task);
-                    }
-                })
+impl SwiftCallbackWrapper {
+    pub fn new(
+        ptr: *mut std::ffi::c_void,
+        on_dropped: extern "C" fn(*mut std::ffi::c_void, bool),
+    ) -> Self {
+        SwiftCallbackWrapper {
+            ptr,
+            on_dropped: Some(on_dropped),
+        }
+    }
+
+    /// Get the pointer to hand to one of the function's callbacks.
+    pub fn into_raw(mut self) -> *mut std::ffi::c_void {
+        self.on_dropped = None;
+        self.ptr
+    }
+}
+
+impl Drop for SwiftCallbackWrapper {
+    fn drop(&mut self) {
+        if let Some(on_dropped) = self.on_dropped {
+            (on_dropped)(self.ptr, is_shut_down());
+        }
+    }
+}
+
+/// Lets Swift cancel the task that is running a Rust async function.
+///
+/// Swift creates a token before calling an async Rust function and cancels it when the Swift
+/// `Task` that is awaiting the function gets cancelled. The Rust future is then dropped the next
+/// time that its task gets polled, and Swift gets notified through the function's `on_cancelled`
+/// callback instead of its completion callback.
+#[doc(hidden)]
+pub struct CancellationToken {
+    state: Mutex<CancellationState>,
+}
+
+struct CancellationState {
+    is_cancelled: bool,
+    waker: Option<Waker>,
+}
+
+#[doc(hidden)]
+impl CancellationToken {
+    /// Get a handle to a token that was created by `__swift_bridge__$CancellationToken$new`.
+    ///
+    /// # Safety
+    ///
+    /// The `token` must not have been freed yet.
+    pub unsafe fn from_raw(token: *mut std::ffi::c_void) -> Arc<CancellationToken> {
+        let token = token as *const CancellationToken;
+
+        Arc::increment_strong_count(token);
+        Arc::from_raw(token)
+    }
+
+    /// Run the future until it either completes or the token is cancelled.
+    ///
+    /// Returns `None` if the token was cancelled, in which case the future has already been
+    /// dropped.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        let mut future = Box::pin(future);
+
+        std::future::poll_fn(move |cx| {
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.is_cancelled {
+                    return Poll::Ready(None);
+                }
+                state.waker = Some(cx.waker().clone());
+            }
+
+            future.as_mut().poll(cx).map(Some)
+        })
+        .await
+    }
+
+    fn cancel(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        state.is_cancelled = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Drives the future of an async function that uses `#[swift_bridge(local)]`.
+///
+/// These futures don't need to be `Send`, so they can't be spawned on the executor. Swift calls
+/// local functions on the main thread, where the future gets polled for the first time. Whenever
+/// the future gets woken up Rust calls the function's `schedule` callback, which asks Swift to
+/// poll the task again on the main thread using `__swift_bridge__$LocalTask$poll`.
+#[doc(hidden)]
+pub struct LocalTask {
+    future: RefCell<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+    thread: ThreadId,
+    schedule: extern "C" fn(*mut std::ffi::c_void),
+}
+
+// The future is only ever polled or dropped on the thread that spawned it. Wakers on other threads
+// only use the `schedule` callback.
+unsafe impl Send for LocalTask {}
+unsafe impl Sync for LocalTask {}
+
+#[doc(hidden)]
+impl LocalTask {
+    /// Poll the future on the current thread until it is no longer able to make progress.
+    pub fn spawn(
+        future: impl Future<Output = ()> + 'static,
+        schedule: extern "C" fn(*mut std::ffi::c_void),
+    ) {
+        let task = Arc::new(LocalTask {
+            future: RefCell::new(Some(Box::pin(future))),
+            thread: std::thread::current().id(),
+            schedule,
         });
+
+        task.poll();
+    }
+
+    fn poll(self: &Arc<Self>) {
+        assert_eq!(
+            std::thread::current().id(),
+            self.thread,
+            "A local async function's future must be polled on the thread that called it."
+        );
+
+        let mut future = self.future.borrow_mut();
+        let ready = match future.as_mut() {
+            Some(future) => {
+                let waker = Waker::from(self.clone());
+                let mut cx = Context::from_waker(&waker);
+
+                future.as_mut().poll(&mut cx).is_ready()
+            }
+            // The task was woken up after it had already completed.
+            None => false,
+        };
+
+        if ready {
+            *future = None;
+        }
+    }
+}
+
+impl Wake for LocalTask {
+    fn wake(self: Arc<Self>) {
+        (self.schedule)(Arc::into_raw(self) as *mut std::ffi::c_void);
+    }
+}
+
+impl Drop for LocalTask {
+    fn drop(&mut self) {
+        // If the last waker gets dropped on another thread before the future completes, we leak
+        // the future instead of dropping it on the wrong thread.
+        if std::thread::current().id() != self.thread {
+            if let Some(future) = self.future.get_mut().take() {
+                std::mem::forget(future);
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$LocalTask$poll"]
+pub extern "C" fn __swift_bridge__LocalTask_poll(task: *mut std::ffi::c_void) {
+    let task = unsafe { Arc::from_raw(task as *const LocalTask) };
+    task.poll();
+}
+
+/// Run the future, catching any panic that happens while it is being polled.
+///
+/// Without this a panic would tear down the task without ever calling back into Swift, leaving
+/// the awaiting Swift task suspended forever. Returns the panic's message if the future panicked.
+#[doc(hidden)]
+pub async fn catch_unwind<F: Future>(future: F) -> Result<F::Output, String> {
+    let mut future = Box::pin(future);
+
+    std::future::poll_fn(move |cx| {
+        match std::panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(panic_message(payload))),
+        }
+    })
+    .await
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&'static str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
     }
 }
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$CancellationToken$new"]
+pub extern "C" fn __swift_bridge__CancellationToken_new() -> *mut std::ffi::c_void {
+    let token = CancellationToken {
+        state: Mutex::new(CancellationState {
+            is_cancelled: false,
+            waker: None,
+        }),
+    };
+
+    Arc::into_raw(Arc::new(token)) as *mut std::ffi::c_void
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$CancellationToken$cancel"]
+pub extern "C" fn __swift_bridge__CancellationToken_cancel(token: *mut std::ffi::c_void) {
+    let token = unsafe { &*(token as *const CancellationToken) };
+    token.cancel();
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$CancellationToken$_free"]
+pub extern "C" fn __swift_bridge__CancellationToken__free(token: *mut std::ffi::c_void) {
+    let token = unsafe { Arc::from_raw(token as *const CancellationToken) };
+    drop(token);
+}
+
+/// A future that gets completed when an `async` function that was declared in an `extern "Swift"`
+/// block returns.
+///
+/// The generated Rust function passes a pointer to the future's state to Swift along with an
+/// `extern "C"` callback. Swift runs the async function in a `Task` and then hands the result to
+/// the callback, which calls [`SwiftFuture::complete`].
+///
+/// Swift returns a handle to the `Task` that is running the function. Dropping the `SwiftFuture`
+/// cancels the task, and the task's result gets dropped once Swift completes it.
+#[doc(hidden)]
+pub struct SwiftFuture<T> {
+    state: Arc<Mutex<SwiftFutureState<T>>>,
+    swift_task: *mut std::ffi::c_void,
+}
+
+// The Swift task handle is only used to cancel and release the task, both of which can be done
+// from any thread.
+unsafe impl<T: Send> Send for SwiftFuture<T> {}
+unsafe impl<T: Send> Sync for SwiftFuture<T> {}
+
+struct SwiftFutureState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+#[doc(hidden)]
+impl<T> SwiftFuture<T> {
+    /// Create a future along with the pointer that Swift will later pass back to `complete`.
+    pub fn new() -> (Self, *mut std::ffi::c_void) {
+        let state = Arc::new(Mutex::new(SwiftFutureState {
+            value: None,
+            waker: None,
+        }));
+        let state_ptr = Arc::into_raw(state.clone()) as *mut std::ffi::c_void;
+
+        (
+            SwiftFuture {
+                state,
+                swift_task: std::ptr::null_mut(),
+            },
+            state_ptr,
+        )
+    }
+
+    /// Hold on to the Swift `Task` that will complete this future, so that the task can be
+    /// cancelled if the future gets dropped.
+    ///
+    /// # Safety
+    ///
+    /// The `swift_task` must be a retained `__private__SwiftTask` that this future now owns.
+    pub unsafe fn with_swift_task(mut self, swift_task: *mut std::ffi::c_void) -> Self {
+        self.swift_task = swift_task;
+        self
+    }
+
+    /// Complete the future, waking up whoever is awaiting it.
+    ///
+    /// # Safety
+    ///
+    /// The `state` must be a pointer that was returned from `SwiftFuture::<T>::new`, and it must
+    /// only be completed once.
+    pub unsafe fn complete(state: *mut std::ffi::c_void, value: T) {
+        let state = Arc::from_raw(state as *const Mutex<SwiftFutureState<T>>);
+        let mut state = state.lock().unwrap();
+
+        state.value = Some(value);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for SwiftFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(value) = state.value.take() {
+            Poll::Ready(value)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for SwiftFuture<T> {
+    fn drop(&mut self) {
+        if self.swift_task.is_null() {
+            return;
+        }
+
+        // Cancelling a task that has already completed does nothing.
+        unsafe {
+            __swift_bridge__SwiftTask_cancel(self.swift_task);
+            __swift_bridge__SwiftTask__free(self.swift_task);
+        }
+    }
+}
+
+// Defined in `async_runtime.swift`. These are only referenced by `SwiftFuture`, which is only used
+// by code that gets linked against the generated Swift.
+extern "C" {
+    #[link_name = "__swift_bridge__$SwiftTask$cancel"]
+    fn __swift_bridge__SwiftTask_cancel(task: *mut std::ffi::c_void);
+
+    #[link_name = "__swift_bridge__$SwiftTask$_free"]
+    fn __swift_bridge__SwiftTask__free(task: *mut std::ffi::c_void);
+}
+
+/// A stream of values, such as the stream returned by an `extern "Rust"` function that returns
+/// `impl Stream<Item = T>`.
+///
+/// Swift receives these as a `RustStream<T>`, which conforms to `AsyncSequence`.
+pub use futures_core::Stream;
+
+/// A Rust stream that is owned by Swift.
+///
+/// Swift polls the stream for one item at a time by calling the generated `$next` function, so
+/// the stream only makes progress while Swift is iterating over it.
+#[doc(hidden)]
+pub type BoxedStream<T> = Pin<Box<dyn Stream<Item = T> + Send + 'static>>;
+
+/// Move a stream to the heap so that it can be handed to Swift.
+#[doc(hidden)]
+pub fn box_stream<T>(stream: impl Stream<Item = T> + Send + 'static) -> *mut std::ffi::c_void {
+    let stream: BoxedStream<T> = Box::pin(stream);
+    Box::into_raw(Box::new(stream)) as *mut std::ffi::c_void
+}
+
+/// Wait for the next item in a stream that was created using [`box_stream`].
+///
+/// # Safety
+///
+/// The `stream` must not have been freed yet, and it must not be polled by anything else until
+/// the returned future has been dropped.
+#[doc(hidden)]
+pub unsafe fn stream_next<T>(stream: *mut std::ffi::c_void) -> StreamNext<T> {
+    StreamNext {
+        stream: stream as *mut BoxedStream<T>,
+    }
+}
+
+/// Frees a stream that was created using [`box_stream`].
+///
+/// # Safety
+///
+/// The `stream` must not have been freed yet and must not be in the middle of being polled.
+#[doc(hidden)]
+pub unsafe fn stream_free<T>(stream: *mut std::ffi::c_void) {
+    drop(Box::from_raw(stream as *mut BoxedStream<T>));
+}
+
+/// Returned by [`stream_next`].
+#[doc(hidden)]
+pub struct StreamNext<T> {
+    stream: *mut BoxedStream<T>,
+}
+
+// The stream itself is `Send`, and Swift guarantees that only one `StreamNext` exists for a
+// stream at any time.
+unsafe impl<T> Send for StreamNext<T> {}
+
+impl<T> Future for StreamNext<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream;
+        unsafe { (*stream).as_mut() }.poll_next(cx)
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$AsyncRuntime$in_flight_tasks"]
+pub extern "C" fn __swift_bridge__AsyncRuntime_in_flight_tasks() -> usize {
+    in_flight_tasks()
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$AsyncRuntime$shutdown"]
+pub extern "C" fn __swift_bridge__AsyncRuntime_shutdown(timeout_secs: f64) -> bool {
+    // `max` treats NaN as zero.
+    let timeout_secs = timeout_secs.max(0.).min(u32::MAX as f64);
+    shutdown(Duration::from_secs_f64(timeout_secs)).is_ok()
+}
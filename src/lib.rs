@@ -6,12 +6,16 @@ pub use swift_bridge_macro::bridge;
 
 mod std_bridge;
 
-pub use self::std_bridge::{option, string};
+pub use self::std_bridge::{int128, option, string, time};
 
-#[doc(hidden)]
-#[cfg(feature = "async")]
+#[cfg(feature = "async-runtime-agnostic")]
 pub mod async_support;
 
+// Used by the code generated for `#[swift_bridge(Codable)]` shared types.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+
 #[doc(hidden)]
 #[repr(C)]
 pub struct FfiSlice<T> {
@@ -19,6 +23,14 @@ pub struct FfiSlice<T> {
     pub len: usize,
 }
 
+// Rust arrays are not FFI safe when passed by value, so we wrap them in a `#[repr(C)]` struct.
+// Swift sees the corresponding C struct's `array` field as a tuple.
+#[doc(hidden)]
+#[repr(C)]
+pub struct FfiArray<T, const N: usize> {
+    pub array: [T; N],
+}
+
 // Unlike the Swift pointer wrapper types that we generate, this type does not implement drop.
 // So we can freely construct it and pass it over the FFI boundary without worrying about drop
 //
@@ -43,6 +55,18 @@ impl<T> FfiSlice<T> {
     }
 }
 
+impl<T, const N: usize> FfiArray<T, N> {
+    /// Create an FfiArray from an array.
+    pub fn from_array(array: [T; N]) -> Self {
+        FfiArray { array }
+    }
+
+    /// Get the array that this FfiArray holds.
+    pub fn into_array(self) -> [T; N] {
+        self.array
+    }
+}
+
 // The code generation automatically implements this for all shared structs.
 // This trait is private and should not be used outside of swift-bridge.
 #[doc(hidden)]
@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
 
+pub mod int128;
 pub mod option;
 mod rust_vec;
 pub mod string;
+pub mod time;
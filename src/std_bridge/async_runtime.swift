@@ -0,0 +1,53 @@
+/// The runtime that async Rust functions run on.
+public enum RustAsyncRuntime {
+    /// The number of async Rust functions that were called from Swift and are still running.
+    public static var inFlightTasks: Int {
+        Int(__swift_bridge__$AsyncRuntime$in_flight_tasks())
+    }
+
+    /// Stop accepting new tasks and wait up to `timeout` seconds for the in-flight tasks to
+    /// complete.
+    ///
+    /// Async Rust functions that get called after this point throw a `RustAsyncRuntimeShutDown`.
+    ///
+    /// Returns `false` if some tasks were still running when the timeout elapsed.
+    ///
+    /// This blocks the current thread.
+    @discardableResult
+    public static func shutdown(timeout: TimeInterval) -> Bool {
+        __swift_bridge__$AsyncRuntime$shutdown(timeout)
+    }
+}
+
+/// Thrown by an async Rust function, or by a `RustStream`, if the Rust async runtime was shut down
+/// before the function or stream's task completed.
+public struct RustAsyncRuntimeShutDown: Error {}
+
+/// The error for an async Rust task that was dropped before it completed.
+func rustTaskDroppedError(_ runtimeShutDown: Bool) -> Error {
+    if runtimeShutDown {
+        return RustAsyncRuntimeShutDown()
+    }
+    return CancellationError()
+}
+
+/// The `Task` that runs an async Swift function on behalf of Rust.
+///
+/// Rust owns the task and cancels it if it drops the future that is awaiting the function.
+public class __private__SwiftTask {
+    let task: Task<Void, Never>
+
+    public init(_ task: Task<Void, Never>) {
+        self.task = task
+    }
+}
+
+@_cdecl("__swift_bridge__$SwiftTask$cancel")
+func __swift_bridge__SwiftTask_cancel(_ task: UnsafeMutableRawPointer) {
+    Unmanaged<__private__SwiftTask>.fromOpaque(task).takeUnretainedValue().task.cancel()
+}
+
+@_cdecl("__swift_bridge__$SwiftTask$_free")
+func __swift_bridge__SwiftTask__free(_ task: UnsafeMutableRawPointer) {
+    Unmanaged<__private__SwiftTask>.fromOpaque(task).release()
+}
@@ -0,0 +1,47 @@
+//! Swift's standard library only gained `UInt128` and `Int128` in Swift 6, and C has no
+//! portable 128 bit integer type, so we pass 128 bit integers across the FFI boundary as a
+//! pair of 64 bit halves.
+
+/// The FFI representation of a `u128`.
+#[repr(C)]
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RustU128 {
+    pub low: u64,
+    pub high: u64,
+}
+
+/// The FFI representation of an `i128`.
+#[repr(C)]
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RustI128 {
+    pub low: u64,
+    pub high: i64,
+}
+
+impl RustU128 {
+    pub fn from_u128(val: u128) -> Self {
+        RustU128 {
+            low: val as u64,
+            high: (val >> 64) as u64,
+        }
+    }
+
+    pub fn into_u128(self) -> u128 {
+        ((self.high as u128) << 64) | self.low as u128
+    }
+}
+
+impl RustI128 {
+    pub fn from_i128(val: i128) -> Self {
+        RustI128 {
+            low: val as u64,
+            high: (val >> 64) as i64,
+        }
+    }
+
+    pub fn into_i128(self) -> i128 {
+        ((self.high as i128) << 64) | self.low as i128
+    }
+}
@@ -0,0 +1,117 @@
+extension RustU128 {
+    public init(_ value: UInt64) {
+        self.init(low: value, high: 0)
+    }
+}
+extension RustU128: ExpressibleByIntegerLiteral {
+    public init(integerLiteral value: UInt64) {
+        self.init(value)
+    }
+}
+extension RustU128: Equatable {
+    public static func == (lhs: RustU128, rhs: RustU128) -> Bool {
+        lhs.low == rhs.low && lhs.high == rhs.high
+    }
+}
+extension RustU128: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(low)
+        hasher.combine(high)
+    }
+}
+extension RustU128: CustomStringConvertible {
+    public var description: String {
+        __swift_bridge__u128Description(high: high, low: low)
+    }
+}
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, *)
+extension RustU128 {
+    public init(_ value: UInt128) {
+        self.init(low: UInt64(truncatingIfNeeded: value), high: UInt64(truncatingIfNeeded: value >> 64))
+    }
+}
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, *)
+extension UInt128 {
+    public init(_ value: RustU128) {
+        self = (UInt128(value.high) << 64) | UInt128(value.low)
+    }
+}
+#endif
+
+extension RustI128 {
+    public init(_ value: Int64) {
+        self.init(low: UInt64(bitPattern: value), high: value < 0 ? -1 : 0)
+    }
+}
+extension RustI128: ExpressibleByIntegerLiteral {
+    public init(integerLiteral value: Int64) {
+        self.init(value)
+    }
+}
+extension RustI128: Equatable {
+    public static func == (lhs: RustI128, rhs: RustI128) -> Bool {
+        lhs.low == rhs.low && lhs.high == rhs.high
+    }
+}
+extension RustI128: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(low)
+        hasher.combine(high)
+    }
+}
+extension RustI128: CustomStringConvertible {
+    public var description: String {
+        if high >= 0 {
+            return __swift_bridge__u128Description(high: UInt64(high), low: low)
+        }
+
+        // Two's complement negation to get the magnitude.
+        let low = ~self.low &+ 1
+        let high = ~UInt64(bitPattern: self.high) &+ (low == 0 ? 1 : 0)
+        return "-" + __swift_bridge__u128Description(high: high, low: low)
+    }
+}
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, *)
+extension RustI128 {
+    public init(_ value: Int128) {
+        self.init(low: UInt64(truncatingIfNeeded: value), high: Int64(truncatingIfNeeded: value >> 64))
+    }
+}
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, *)
+extension Int128 {
+    public init(_ value: RustI128) {
+        self = (Int128(value.high) << 64) | Int128(value.low)
+    }
+}
+#endif
+
+/// Format an unsigned 128 bit integer, given as two 64 bit halves, in base 10.
+func __swift_bridge__u128Description(high: UInt64, low: UInt64) -> String {
+    if high == 0 {
+        return String(low)
+    }
+
+    // Repeatedly divide by 10^19, the largest power of ten that fits in a UInt64.
+    let divisor: UInt64 = 10_000_000_000_000_000_000
+    var high = high
+    var low = low
+    var chunks: [UInt64] = []
+
+    while high != 0 {
+        let highQuotient = high / divisor
+        let highRemainder = high % divisor
+        let (lowQuotient, remainder) = divisor.dividingFullWidth((high: highRemainder, low: low))
+        chunks.append(remainder)
+        high = highQuotient
+        low = lowQuotient
+    }
+
+    var description = String(low)
+    for chunk in chunks.reversed() {
+        let digits = String(chunk)
+        description += String(repeating: "0", count: 19 - digits.count) + digits
+    }
+    return description
+}
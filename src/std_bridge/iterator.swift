@@ -0,0 +1,37 @@
+/// A Rust iterator, such as one returned by a Rust function that returns an
+/// `impl Iterator<Item = T>` or a `Box<dyn Iterator<Item = T>>`.
+///
+/// Items are lazily pulled from Rust one at a time, so the full sequence is never collected
+/// into memory up front.
+public class RustIterator<T> {
+    var ptr: UnsafeMutableRawPointer
+    // The Rust iterator might be borrowing from these, so we keep them alive for as long as
+    // the iterator.
+    let owners: [AnyObject]
+    let nextItem: (UnsafeMutableRawPointer) -> T?
+    let free: (UnsafeMutableRawPointer) -> ()
+
+    init(
+        ptr: UnsafeMutableRawPointer,
+        owners: [AnyObject],
+        next: @escaping (UnsafeMutableRawPointer) -> T?,
+        free: @escaping (UnsafeMutableRawPointer) -> ()
+    ) {
+        self.ptr = ptr
+        self.owners = owners
+        self.nextItem = next
+        self.free = free
+    }
+
+    deinit {
+        free(ptr)
+    }
+}
+
+extension RustIterator: IteratorProtocol {
+    public func next() -> T? {
+        nextItem(ptr)
+    }
+}
+
+extension RustIterator: Sequence {}
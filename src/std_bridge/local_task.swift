@@ -0,0 +1,9 @@
+/// Passed to async Rust functions that use `#[swift_bridge(local)]`.
+///
+/// Rust calls this whenever the function's future is able to make progress, possibly from another
+/// thread, and we poll the future again on the main thread.
+func scheduleLocalRustTask(task: UnsafeMutableRawPointer?) {
+    DispatchQueue.main.async {
+        __swift_bridge__$LocalTask$poll(task)
+    }
+}
@@ -54,6 +54,20 @@ pub struct OptionI64 {
     pub is_some: bool,
 }
 
+#[repr(C)]
+#[doc(hidden)]
+pub struct OptionU128 {
+    pub val: crate::int128::RustU128,
+    pub is_some: bool,
+}
+
+#[repr(C)]
+#[doc(hidden)]
+pub struct OptionI128 {
+    pub val: crate::int128::RustI128,
+    pub is_some: bool,
+}
+
 #[repr(C)]
 #[doc(hidden)]
 pub struct OptionUsize {
@@ -88,3 +102,17 @@ pub struct OptionBool {
     pub val: bool,
     pub is_some: bool,
 }
+
+#[repr(C)]
+#[doc(hidden)]
+pub struct OptionDuration {
+    pub val: crate::time::FfiDuration,
+    pub is_some: bool,
+}
+
+#[repr(C)]
+#[doc(hidden)]
+pub struct OptionSystemTime {
+    pub val: crate::time::FfiSystemTime,
+    pub is_some: bool,
+}
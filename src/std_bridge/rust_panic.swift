@@ -0,0 +1,6 @@
+/// Thrown by an async Rust function, or by a `RustStream` while it is being polled, if Rust
+/// panics.
+public struct RustPanic: Error {
+    /// The message that Rust panicked with.
+    public let message: String
+}
@@ -0,0 +1,94 @@
+/// A Rust stream, such as one returned by a Rust function that returns an
+/// `impl Stream<Item = T>` or a `Pin<Box<dyn Stream<Item = T> + Send>>`.
+///
+/// Rust only polls the stream when Swift asks for the next item, so a slow consumer applies
+/// backpressure to the stream. The Rust stream is dropped when the `RustStream` is deinitialized,
+/// and cancelling the task that is iterating over it stops waiting for the pending item.
+public class RustStream<T> {
+    var ptr: UnsafeMutableRawPointer
+    let pollNext: (UnsafeMutableRawPointer, UnsafeMutableRawPointer?, UnsafeMutableRawPointer) -> ()
+    let free: (UnsafeMutableRawPointer) -> ()
+    var hasIterator = false
+
+    init(
+        ptr: UnsafeMutableRawPointer,
+        next: @escaping (UnsafeMutableRawPointer, UnsafeMutableRawPointer?, UnsafeMutableRawPointer) -> (),
+        free: @escaping (UnsafeMutableRawPointer) -> ()
+    ) {
+        self.ptr = ptr
+        self.pollNext = next
+        self.free = free
+    }
+
+    deinit {
+        free(ptr)
+    }
+
+    func nextItem() async throws -> T? {
+        let cancellationToken = __swift_bridge__$CancellationToken$new()
+        defer {
+            __swift_bridge__$CancellationToken$_free(cancellationToken)
+        }
+
+        return try await withTaskCancellationHandler(operation: {
+            try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<T?, Error>) in
+                let wrapper = RustStreamCbWrapper<T>(cb: { result in continuation.resume(with: result) })
+                let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+                self.pollNext(self.ptr, cancellationToken, wrapperPtr)
+            })
+        }, onCancel: {
+            __swift_bridge__$CancellationToken$cancel(cancellationToken)
+        })
+    }
+}
+
+extension RustStream: AsyncSequence {
+    public typealias Element = T
+
+    /// Throws a `CancellationError` if the task that is iterating over the stream gets cancelled,
+    /// or a `RustPanic` if polling the Rust stream panics.
+    public struct AsyncIterator: AsyncIteratorProtocol {
+        let stream: RustStream<T>
+        var isFinished = false
+
+        public mutating func next() async throws -> T? {
+            if isFinished {
+                return nil
+            }
+
+            do {
+                guard let item = try await stream.nextItem() else {
+                    isFinished = true
+                    return nil
+                }
+                return item
+            } catch {
+                isFinished = true
+                throw error
+            }
+        }
+    }
+
+    /// A Rust stream can only be polled by one iterator.
+    public func makeAsyncIterator() -> AsyncIterator {
+        precondition(!hasIterator, "A RustStream can only be iterated over once.")
+        hasIterator = true
+
+        return AsyncIterator(stream: self)
+    }
+}
+
+/// Holds on to the continuation that is waiting for a Rust stream's next item.
+public class RustStreamCbWrapper<T> {
+    var cb: (Result<T?, Error>) -> ()
+
+    init(cb: @escaping (Result<T?, Error>) -> ()) {
+        self.cb = cb
+    }
+
+    public static func complete(_ cbWrapperPtr: UnsafeMutableRawPointer?, _ result: Result<T?, Error>) {
+        let wrapper = Unmanaged<RustStreamCbWrapper<T>>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(result)
+    }
+}
@@ -1,5 +1,7 @@
 pub use self::ffi::*;
 
+use std::ffi::{OsStr, OsString};
+
 #[swift_bridge_macro::bridge(swift_bridge_path = crate)]
 mod ffi {
     extern "Rust" {
@@ -59,6 +61,18 @@ impl RustString {
     pub fn box_into_raw(self) -> *mut RustString {
         Box::into_raw(Box::new(self))
     }
+
+    /// Create a RustString from a `PathBuf` or `OsString`.
+    ///
+    /// Swift Strings are always valid unicode, so any invalid UTF-8 gets replaced with
+    /// U+FFFD REPLACEMENT CHARACTER.
+    pub fn from_os_string(os_string: impl Into<OsString>) -> Self {
+        let string = os_string
+            .into()
+            .into_string()
+            .unwrap_or_else(|os_string| os_string.to_string_lossy().into_owned());
+        RustString(string)
+    }
 }
 
 impl RustStr {
@@ -82,4 +96,40 @@ impl RustStr {
             len: str.len(),
         }
     }
+
+    /// Point to the raw bytes of a `&Path` or `&OsStr`.
+    ///
+    /// These bytes might not be valid UTF-8, so on the Swift side they should be turned into a
+    /// file URL instead of a String.
+    #[cfg(unix)]
+    pub fn from_os_str(os_str: &OsStr) -> Self {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = os_str.as_bytes();
+        RustStr {
+            start: bytes.as_ptr(),
+            len: bytes.len(),
+        }
+    }
+
+    /// Point to the bytes of a `&Path` or `&OsStr`.
+    #[cfg(not(unix))]
+    pub fn from_os_str(os_str: &OsStr) -> Self {
+        Self::from_str(os_str.to_str().expect("Path is not valid UTF-8"))
+    }
+
+    pub fn to_os_str<'a>(self) -> &'a OsStr {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let bytes = unsafe { std::slice::from_raw_parts(self.start, self.len) };
+            OsStr::from_bytes(bytes)
+        }
+
+        #[cfg(not(unix))]
+        {
+            OsStr::new(self.to_str())
+        }
+    }
 }
@@ -15,9 +15,27 @@ extension RustStr {
         return bytes
     }
 
+    /// A `&str` is always valid UTF-8, but a `&Path` or `&OsStr` might not be. Since a Swift `String`
+    /// must always be valid unicode, any bytes that are not valid UTF-8 get replaced with `U+FFFD`.
+    /// Use `toFileURL()` to keep the path's raw bytes.
     public func toString() -> String {
         let bytes = self.toBufferPointer()
-        return String(bytes: bytes, encoding: .utf8)!
+        return String(decoding: bytes, as: UTF8.self)
+    }
+}
+extension RustStr {
+    /// Create a file URL from a `&Path` or `&OsStr` that was passed from Rust.
+    ///
+    /// Unlike `toString()`, this also works for paths that are not valid UTF-8.
+    public func toFileURL() -> URL {
+        var fileSystemRepresentation = Array(self.toBufferPointer()).map { CChar(bitPattern: $0) }
+        fileSystemRepresentation.append(0)
+
+        return URL(
+            fileURLWithFileSystemRepresentation: fileSystemRepresentation,
+            isDirectory: false,
+            relativeTo: nil
+        )
     }
 }
 extension RustStr: Identifiable {
@@ -27,10 +45,9 @@ extension RustStr: Identifiable {
 }
 extension RustStr: Equatable {
     public static func == (lhs: RustStr, rhs: RustStr) -> Bool {
-        // TODO: Rather than compare Strings, we can avoid allocating by calling a function
-        // on the Rust side that compares the underlying byte slices.
-        return
-            lhs.toString() == rhs.toString()
+        // Compare the bytes rather than the Strings, since paths that are not valid UTF-8 get
+        // converted lossily.
+        return lhs.toBufferPointer().elementsEqual(rhs.toBufferPointer())
     }
 }
 
@@ -54,12 +71,30 @@ extension String: IntoRustString {
     }
 }
 
+extension RustString {
+    /// Create a file URL from a `PathBuf` or `OsString` that was passed from Rust.
+    public func toFileURL() -> URL {
+        URL(fileURLWithPath: self.toString())
+    }
+}
+
+extension URL: IntoRustString {
+    /// Pass a file URL to Rust as a `PathBuf` or `OsString`.
+    public func intoRustString() -> RustString {
+        precondition(self.isFileURL, "Only file URLs can be converted into a Rust path.")
+        return RustString(self.path)
+    }
+}
+
 extension RustString: IntoRustString {
     public func intoRustString() -> RustString {
         self
     }
 }
 
+/// Lets an async Rust function that returns a `Result<T, String>` throw its error.
+extension RustString: Error {}
+
 /// If the String is Some:
 ///   Safely get a scoped pointer to the String and then call the callback with a RustStr
 ///   that uses that pointer.
@@ -103,3 +138,19 @@ func optionalRustStrToRustStr<S: ToRustStr, T>(_ str: Optional<S>, _ withUnsafeR
         return withUnsafeRustStr(RustStr(start: nil, len: 0))
     }
 }
+
+extension URL: ToRustStr {
+    /// Safely get a scoped pointer to the file system representation of a file URL and then call
+    /// the callback with a RustStr that uses that pointer.
+    public func toRustStr<T> (_ withUnsafeRustStr: (RustStr) -> T) -> T {
+        precondition(self.isFileURL, "Only file URLs can be converted into a Rust path.")
+
+        return self.withUnsafeFileSystemRepresentation({ cString in
+            let rustStr = RustStr(
+                start: UnsafeMutableRawPointer(mutating: cString!).assumingMemoryBound(to: UInt8.self),
+                len: UInt(strlen(cString!))
+            )
+            return withUnsafeRustStr(rustStr)
+        })
+    }
+}
@@ -0,0 +1,73 @@
+//! `Duration` and `SystemTime` are passed across the FFI boundary as a number of seconds plus a
+//! number of nanoseconds.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The FFI representation of a `std::time::Duration`.
+#[repr(C)]
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FfiDuration {
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+/// The FFI representation of a `std::time::SystemTime`, relative to the UNIX epoch.
+///
+/// Times before the epoch have negative `secs`. `nanos` is always positive and counts forwards
+/// from `secs`, so one nanosecond before the epoch is `{ secs: -1, nanos: 999_999_999 }`.
+#[repr(C)]
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FfiSystemTime {
+    pub secs: i64,
+    pub nanos: u32,
+}
+
+impl FfiDuration {
+    pub fn from_duration(duration: Duration) -> Self {
+        FfiDuration {
+            secs: duration.as_secs(),
+            nanos: duration.subsec_nanos(),
+        }
+    }
+
+    pub fn into_duration(self) -> Duration {
+        Duration::new(self.secs, self.nanos)
+    }
+}
+
+impl FfiSystemTime {
+    pub fn from_system_time(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after_epoch) => FfiSystemTime {
+                secs: after_epoch.as_secs() as i64,
+                nanos: after_epoch.subsec_nanos(),
+            },
+            Err(err) => {
+                let before_epoch = err.duration();
+                let secs = before_epoch.as_secs() as i64;
+                let nanos = before_epoch.subsec_nanos();
+
+                if nanos == 0 {
+                    FfiSystemTime { secs: -secs, nanos }
+                } else {
+                    FfiSystemTime {
+                        secs: -secs - 1,
+                        nanos: 1_000_000_000 - nanos,
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn into_system_time(self) -> SystemTime {
+        let nanos = Duration::from_nanos(self.nanos as u64);
+
+        if self.secs >= 0 {
+            UNIX_EPOCH + Duration::from_secs(self.secs as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(self.secs.unsigned_abs()) + nanos
+        }
+    }
+}
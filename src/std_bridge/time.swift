@@ -0,0 +1,28 @@
+extension __private__FfiDuration {
+    func toTimeInterval() -> TimeInterval {
+        TimeInterval(self.secs) + TimeInterval(self.nanos) / 1_000_000_000
+    }
+}
+extension TimeInterval {
+    func toFfiDuration() -> __private__FfiDuration {
+        precondition(self >= 0, "Rust Durations cannot be negative.")
+
+        let secs = self.rounded(.down)
+        let nanos = min(((self - secs) * 1_000_000_000).rounded(), 999_999_999)
+        return __private__FfiDuration(secs: UInt64(secs), nanos: UInt32(nanos))
+    }
+}
+
+extension __private__FfiSystemTime {
+    func toDate() -> Date {
+        Date(timeIntervalSince1970: TimeInterval(self.secs) + TimeInterval(self.nanos) / 1_000_000_000)
+    }
+}
+extension Date {
+    func toFfiSystemTime() -> __private__FfiSystemTime {
+        let sinceEpoch = self.timeIntervalSince1970
+        let secs = sinceEpoch.rounded(.down)
+        let nanos = min(((sinceEpoch - secs) * 1_000_000_000).rounded(), 999_999_999)
+        return __private__FfiSystemTime(secs: Int64(secs), nanos: UInt32(nanos))
+    }
+}
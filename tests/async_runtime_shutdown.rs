@@ -0,0 +1,82 @@
+//! Verify that calling an async Rust function after the async runtime has been shut down tells
+//! Swift that the runtime was shut down, instead of dropping the call without a trace.
+//!
+//! Shutting down can't be undone, so this lives in its own test binary.
+
+use std::ffi::c_void;
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+use swift_bridge::string::RustString;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        async fn add_one(num: u8) -> u8;
+    }
+}
+
+async fn add_one(num: u8) -> u8 {
+    num + 1
+}
+
+/// Which of the function's callbacks Rust called.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Completed(u8),
+    Panicked,
+    Cancelled { runtime_shut_down: bool },
+}
+
+/// Call the generated `extern "C"` function the way that the generated Swift code does, with our
+/// own callbacks standing in for Swift's.
+fn call_add_one(num: u8) -> Outcome {
+    let (sender, receiver) = channel();
+    let callback_wrapper = Box::into_raw(Box::new(sender)) as *mut c_void;
+    let cancellation_token = swift_bridge::async_support::__swift_bridge__CancellationToken_new();
+
+    ffi::__swift_bridge__add_one(
+        cancellation_token,
+        callback_wrapper,
+        on_complete,
+        on_panic,
+        on_cancelled,
+        num,
+    );
+
+    let outcome = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    swift_bridge::async_support::__swift_bridge__CancellationToken__free(cancellation_token);
+    outcome
+}
+
+extern "C" fn on_complete(callback_wrapper: *mut c_void, val: u8) {
+    send(callback_wrapper, Outcome::Completed(val));
+}
+
+extern "C" fn on_panic(callback_wrapper: *mut c_void, message: *mut RustString) {
+    drop(unsafe { Box::from_raw(message) });
+    send(callback_wrapper, Outcome::Panicked);
+}
+
+extern "C" fn on_cancelled(callback_wrapper: *mut c_void, runtime_shut_down: bool) {
+    send(callback_wrapper, Outcome::Cancelled { runtime_shut_down });
+}
+
+fn send(callback_wrapper: *mut c_void, outcome: Outcome) {
+    let sender = unsafe { Box::from_raw(callback_wrapper as *mut Sender<Outcome>) };
+    sender.send(outcome).unwrap();
+}
+
+#[test]
+fn async_fn_called_after_shutdown_reports_runtime_shut_down() {
+    assert_eq!(call_add_one(1), Outcome::Completed(2));
+
+    swift_bridge::async_support::shutdown(Duration::from_secs(5)).unwrap();
+
+    assert_eq!(
+        call_add_one(1),
+        Outcome::Cancelled {
+            runtime_shut_down: true
+        }
+    );
+    assert_eq!(swift_bridge::async_support::in_flight_tasks(), 0);
+}